@@ -53,6 +53,8 @@ fn main() {
     let mut my_datapack = Datapack {
         pack: pack_meta,
         namespaces: BTreeMap::new(),
+        overlays: BTreeMap::new(),
+        other_files: BTreeMap::new(),
     };
 
     my_datapack.add_namespace("mydp", my_namespace);
@@ -1,22 +1,17 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Attribute, Data, DeriveInput, Fields, parse_macro_input};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, parse_macro_input};
 
 #[proc_macro_derive(HasMacro, attributes(has_macro))]
 pub fn has_macro_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
 
-    let crate_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
-    let is_internal = crate_name == "minecraft-command-types";
-    let crate_path = if is_internal {
-        quote! { crate }
-    } else {
-        quote! { ::minecraft_command_types }
-    };
+    let crate_path = crate_path();
 
     let has_macro_body = generate_body(&input.data, "has_macro");
     let has_macro_conflict_body = generate_body(&input.data, "has_macro_conflict");
+    let collect_macro_variables_body = generate_collect_body(&input.data);
 
     let expanded = quote! {
         impl #crate_path::has_macro::HasMacro for #name {
@@ -27,12 +22,27 @@ pub fn has_macro_derive(input: TokenStream) -> TokenStream {
             fn has_macro_conflict(&self) -> bool {
                 #has_macro_conflict_body
             }
+
+            fn collect_macro_variables(&self, variables: &mut ::std::collections::BTreeSet<::std::string::String>) {
+                #collect_macro_variables_body
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
 
+fn crate_path() -> proc_macro2::TokenStream {
+    let crate_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let is_internal = crate_name == "minecraft-command-types";
+
+    if is_internal {
+        quote! { crate }
+    } else {
+        quote! { ::minecraft_command_types }
+    }
+}
+
 fn should_ignore(attrs: &[Attribute]) -> bool {
     for attr in attrs {
         if attr.path().is_ident("has_macro") {
@@ -144,3 +154,316 @@ fn generate_body(data: &Data, method_name: &str) -> proc_macro2::TokenStream {
         Data::Union(_) => panic!("HasMacro derive macro cannot be used on unions"),
     }
 }
+
+fn generate_collect_body(data: &Data) -> proc_macro2::TokenStream {
+    match data {
+        Data::Struct(data) => {
+            let collects = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| !should_ignore(&f.attrs))
+                .map(|(i, f)| match &f.ident {
+                    Some(field_name) => {
+                        quote! { self.#field_name.collect_macro_variables(variables); }
+                    }
+                    None => {
+                        let index = syn::Index::from(i);
+                        quote! { self.#index.collect_macro_variables(variables); }
+                    }
+                });
+
+            quote! {
+                let _ = &variables;
+                #(#collects)*
+            }
+        }
+        Data::Enum(data) => {
+            let variant_collects = data.variants.iter().map(|v| {
+                let variant_name = &v.ident;
+                let bindings: Vec<_> = v
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| {
+                        if should_ignore(&f.attrs) {
+                            quote::format_ident!("_")
+                        } else {
+                            quote::format_ident!("field_{}", i)
+                        }
+                    })
+                    .collect();
+                let collects = bindings
+                    .iter()
+                    .filter(|binding| *binding != "_")
+                    .map(|binding| quote! { #binding.collect_macro_variables(variables); });
+
+                match &v.fields {
+                    Fields::Named(fields) => {
+                        let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
+
+                        quote! {
+                            Self::#variant_name { #(#field_names: #bindings),* } => {
+                                #(#collects)*
+                            }
+                        }
+                    }
+                    Fields::Unnamed(_) => quote! {
+                        Self::#variant_name(#(#bindings),*) => {
+                            #(#collects)*
+                        }
+                    },
+                    Fields::Unit => quote! { Self::#variant_name => {} },
+                }
+            });
+
+            quote! {
+                let _ = &variables;
+
+                match self {
+                    #(#variant_collects),*
+                }
+            }
+        }
+        Data::Union(_) => panic!("HasMacro derive macro cannot be used on unions"),
+    }
+}
+
+/// Reads `#[visit(hook = name)]`, which routes the type through the `visit_<name>`,
+/// `visit_<name>_mut` and `fold_<name>` methods of the visitor.
+fn hook_name(attrs: &[Attribute]) -> Option<Ident> {
+    let mut hook = None;
+
+    for attr in attrs {
+        if attr.path().is_ident("visit") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("hook") {
+                    hook = Some(meta.value()?.parse::<Ident>()?);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    hook
+}
+
+/// Generates a statement per field with `call`, which receives the field expression.
+fn generate_each_field(
+    data: &Data,
+    call: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match data {
+        Data::Struct(data) => {
+            let calls = data.fields.iter().enumerate().map(|(i, f)| match &f.ident {
+                Some(field_name) => call(quote! { self.#field_name }),
+                None => {
+                    let index = syn::Index::from(i);
+                    call(quote! { self.#index })
+                }
+            });
+
+            quote! { #(#calls;)* }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|v| {
+                let variant_name = &v.ident;
+                let bindings: Vec<_> = (0..v.fields.len())
+                    .map(|i| quote::format_ident!("field_{}", i))
+                    .collect();
+                let calls = bindings.iter().map(|binding| call(quote! { *#binding }));
+
+                match &v.fields {
+                    Fields::Named(fields) => {
+                        let field_names = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
+
+                        quote! {
+                            Self::#variant_name { #(#field_names: #bindings),* } => {
+                                #(#calls;)*
+                            }
+                        }
+                    }
+                    Fields::Unnamed(_) => quote! {
+                        Self::#variant_name(#(#bindings),*) => {
+                            #(#calls;)*
+                        }
+                    },
+                    Fields::Unit => quote! { Self::#variant_name => {} },
+                }
+            });
+
+            quote! {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+        Data::Union(_) => panic!("Visitor derive macros cannot be used on unions"),
+    }
+}
+
+#[proc_macro_derive(Visit, attributes(visit))]
+pub fn visit_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let crate_path = crate_path();
+
+    let body = generate_each_field(&input.data, |field| {
+        quote! { #crate_path::visit::Visit::visit(&#field, visitor) }
+    });
+    let hook = hook_name(&input.attrs).map(|hook| {
+        let method = quote::format_ident!("visit_{}", hook);
+
+        quote! {
+            fn visit<V: #crate_path::visit::Visitor + ?Sized>(&self, visitor: &mut V) {
+                visitor.#method(self);
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl #crate_path::visit::Visit for #name {
+            fn visit_children<V: #crate_path::visit::Visitor + ?Sized>(&self, visitor: &mut V) {
+                let _ = &visitor;
+                #body
+            }
+
+            #hook
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(VisitMut, attributes(visit))]
+pub fn visit_mut_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let crate_path = crate_path();
+
+    let body = generate_each_field(&input.data, |field| {
+        quote! { #crate_path::visit::VisitMut::visit_mut(&mut #field, visitor) }
+    });
+    let hook = hook_name(&input.attrs).map(|hook| {
+        let method = quote::format_ident!("visit_{}_mut", hook);
+
+        quote! {
+            fn visit_mut<V: #crate_path::visit::VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.#method(self);
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl #crate_path::visit::VisitMut for #name {
+            fn visit_children_mut<V: #crate_path::visit::VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                let _ = &visitor;
+                #body
+            }
+
+            #hook
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+fn generate_fold_body(
+    data: &Data,
+    crate_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let fold = |field: proc_macro2::TokenStream| {
+        quote! { #crate_path::visit::Fold::fold(#field, folder) }
+    };
+
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let folds = fields.named.iter().map(|f| {
+                    let field_name = f.ident.as_ref().unwrap();
+                    let folded = fold(quote! { self.#field_name });
+
+                    quote! { #field_name: #folded }
+                });
+
+                quote! { Self { #(#folds),* } }
+            }
+            Fields::Unnamed(fields) => {
+                let folds = (0..fields.unnamed.len()).map(|i| {
+                    let index = syn::Index::from(i);
+                    fold(quote! { self.#index })
+                });
+
+                quote! { Self(#(#folds),*) }
+            }
+            Fields::Unit => quote! { self },
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|v| {
+                let variant_name = &v.ident;
+                let bindings: Vec<_> = (0..v.fields.len())
+                    .map(|i| quote::format_ident!("field_{}", i))
+                    .collect();
+                let folds = bindings.iter().map(|binding| fold(quote! { #binding }));
+
+                match &v.fields {
+                    Fields::Named(fields) => {
+                        let field_names: Vec<_> = fields
+                            .named
+                            .iter()
+                            .map(|f| f.ident.as_ref().unwrap())
+                            .collect();
+
+                        quote! {
+                            Self::#variant_name { #(#field_names: #bindings),* } => {
+                                Self::#variant_name { #(#field_names: #folds),* }
+                            }
+                        }
+                    }
+                    Fields::Unnamed(_) => quote! {
+                        Self::#variant_name(#(#bindings),*) => Self::#variant_name(#(#folds),*)
+                    },
+                    Fields::Unit => quote! { Self::#variant_name => Self::#variant_name },
+                }
+            });
+
+            quote! {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+        Data::Union(_) => panic!("Fold derive macro cannot be used on unions"),
+    }
+}
+
+#[proc_macro_derive(Fold, attributes(visit))]
+pub fn fold_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let crate_path = crate_path();
+
+    let body = generate_fold_body(&input.data, &crate_path);
+    let hook = hook_name(&input.attrs).map(|hook| {
+        let method = quote::format_ident!("fold_{}", hook);
+
+        quote! {
+            fn fold<F: #crate_path::visit::Folder + ?Sized>(self, folder: &mut F) -> Self {
+                folder.#method(self)
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl #crate_path::visit::Fold for #name {
+            fn fold_children<F: #crate_path::visit::Folder + ?Sized>(self, folder: &mut F) -> Self {
+                let _ = &folder;
+                #body
+            }
+
+            #hook
+        }
+    };
+
+    TokenStream::from(expanded)
+}
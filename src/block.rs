@@ -1,14 +1,19 @@
 use crate::nbt_path::SNBTCompound;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
 use crate::resource_location::ResourceLocation;
-use crate::snbt::fmt_snbt_compound;
-use minecraft_command_types_derive::HasMacro;
+use crate::snbt::{fmt_snbt_compound, parse_snbt_compound};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockState {
+    #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
     pub id: ResourceLocation,
     pub block_states: BTreeMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))]
     pub data_tags: Option<SNBTCompound>,
 }
 
@@ -42,3 +47,128 @@ impl BlockState {
         }
     }
 }
+
+fn parse_block_states(
+    reader: &mut StringReader<'_>,
+) -> Result<BTreeMap<String, String>, ParseError> {
+    let mut block_states = BTreeMap::new();
+
+    reader.expect('[')?;
+    reader.skip_whitespace();
+
+    if reader.eat(']') {
+        return Ok(block_states);
+    }
+
+    loop {
+        reader.skip_whitespace();
+
+        let key_start = reader.cursor();
+        let key = reader.read_string()?;
+        if key.is_empty() {
+            return Err(reader.expected("block state property"));
+        }
+
+        if block_states.contains_key(&key) {
+            return Err(ParseError::new(
+                key_start,
+                ParseErrorKind::RepeatedKey("block state property", key),
+            ));
+        }
+
+        reader.skip_whitespace();
+        reader.expect('=')?;
+        reader.skip_whitespace();
+
+        let value = reader.read_string()?;
+        if value.is_empty() {
+            return Err(reader.expected("block state value"));
+        }
+
+        block_states.insert(key, value);
+
+        reader.skip_whitespace();
+
+        if reader.eat(']') {
+            return Ok(block_states);
+        }
+
+        reader.expect(',')?;
+    }
+}
+
+impl Parse for BlockState {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let id = ResourceLocation::parse(reader)?;
+
+        let block_states = if reader.peek() == Some('[') {
+            parse_block_states(reader)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let data_tags = if reader.peek() == Some('{') {
+            Some(parse_snbt_compound(reader)?)
+        } else {
+            None
+        };
+
+        Ok(BlockState {
+            id,
+            block_states,
+            data_tags,
+        })
+    }
+}
+
+impl FromStr for BlockState {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseErrorKind;
+
+    #[test]
+    fn test_parse() {
+        let block: BlockState = "minecraft:chest[facing=north,waterlogged=true]{Lock:\"key\"}"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            block.id,
+            ResourceLocation::new_namespace_path("minecraft", "chest")
+        );
+        assert_eq!(
+            block.block_states.get("facing").map(String::as_str),
+            Some("north")
+        );
+        assert!(block.data_tags.is_some());
+        assert_eq!(
+            block.to_string(),
+            "chest[facing=north, waterlogged=true]{Lock:\"key\"}"
+        );
+
+        let tag: BlockState = "#minecraft:logs[axis=y]".parse().unwrap();
+        assert!(tag.id.is_tag);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "stone[facing]".parse::<BlockState>().unwrap_err();
+        assert_eq!(error.cursor, 12);
+        assert_eq!(error.kind, ParseErrorKind::ExpectedCharacter('='));
+
+        let error = "stone[a=b,a=c]".parse::<BlockState>().unwrap_err();
+        assert_eq!(error.cursor, 10);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::RepeatedKey("block state property", "a".to_string())
+        );
+    }
+}
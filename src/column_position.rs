@@ -1,8 +1,11 @@
 use crate::coordinate::WorldCoordinate;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_all};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnPosition {
     pub x: WorldCoordinate,
     pub z: WorldCoordinate,
@@ -21,3 +24,21 @@ impl Display for ColumnPosition {
         write!(f, "{} {}", self.x, self.z)
     }
 }
+
+impl Parse for ColumnPosition {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let x = WorldCoordinate::parse(reader)?;
+        reader.expect_separator()?;
+        let z = WorldCoordinate::parse(reader)?;
+
+        Ok(ColumnPosition::new(x, z))
+    }
+}
+
+impl FromStr for ColumnPosition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
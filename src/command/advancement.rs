@@ -1,25 +1,40 @@
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional, parse_word};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdvancementCommand {
     /// Adds or removes all loaded advancements.
     Everything,
     /// Adds or removes a single advancement or criterion.
-    Only(ResourceLocation, Option<String>),
+    Only(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<String>,
+    ),
     /// Adds or removes an advancement and all its child advancements.
     /// Think of specifying everything from that advancement to the end.
     /// The exact order the operation is carried out in is `specified advancement > child > child's child > ...` When it operates on a child that branches, it iterates through all its children before continuing.
-    From(ResourceLocation),
+    From(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     /// Specifies an advancement, and adds or removes all its parent advancements, and all its child advancements.
     /// Think of specifying everything through the specified advancement, going both backward and forward.
     /// The exact order the operation is as if the command were executed with "until" specified, then with "from" specified: `parent > parent's parent > ... > root > specified advancement > child > child's child > ...`
-    Through(ResourceLocation),
+    Through(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     /// Adds or removes an advancement and all its parent advancements until the root for addition/removal.
     /// Think of specifying everything from the start until that advancement.
     /// The exact order the operation is carried out in is: `parent > parent's parent > ... > root > specified advancement`.
-    Until(ResourceLocation),
+    Until(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for AdvancementCommand {
@@ -27,7 +42,7 @@ impl Display for AdvancementCommand {
         match self {
             AdvancementCommand::Everything => f.write_str("everything"),
             AdvancementCommand::Only(advancement, criterion) => {
-                advancement.fmt(f)?;
+                write!(f, "only {}", advancement)?;
 
                 if let Some(criterion) = criterion {
                     write!(f, " {}", criterion)?;
@@ -35,9 +50,28 @@ impl Display for AdvancementCommand {
 
                 Ok(())
             }
-            AdvancementCommand::From(advancement) => advancement.fmt(f),
-            AdvancementCommand::Through(advancement) => advancement.fmt(f),
-            AdvancementCommand::Until(advancement) => advancement.fmt(f),
+            AdvancementCommand::From(advancement) => write!(f, "from {}", advancement),
+            AdvancementCommand::Through(advancement) => write!(f, "through {}", advancement),
+            AdvancementCommand::Until(advancement) => write!(f, "until {}", advancement),
         }
     }
 }
+
+impl Parse for AdvancementCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "everything" => AdvancementCommand::Everything,
+            "only" => AdvancementCommand::Only(
+                parse_argument(reader)?,
+                parse_optional(reader, parse_word)?,
+            ),
+            "from" => AdvancementCommand::From(parse_argument(reader)?),
+            "through" => AdvancementCommand::Through(parse_argument(reader)?),
+            "until" => AdvancementCommand::Until(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
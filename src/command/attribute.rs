@@ -1,12 +1,16 @@
 use crate::command::enums::attribute::AttributeAddModifier;
+use crate::parser::{
+    Parse, ParseError, StringReader, expect_literal, parse_argument, parse_optional_argument,
+};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
 type F32 = NotNan<f32>;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BaseAttributeCommand {
     /// Returns the base value of the specified attribute.
     Get(Option<F32>),
@@ -34,14 +38,27 @@ impl Display for BaseAttributeCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ModifierAttributeCommand {
     /// Adds an attribute modifier with the specified properties if no modifier with the same ID already existed.
-    Add(ResourceLocation, F32, AttributeAddModifier),
+    Add(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        F32,
+        AttributeAddModifier,
+    ),
     /// Removes the attribute modifier with the specified ID.
-    Remove(ResourceLocation),
+    Remove(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     /// Returns the value of the modifier with the specified ID.
-    Get(ResourceLocation, Option<F32>),
+    Get(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<F32>,
+    ),
 }
 
 impl Display for ModifierAttributeCommand {
@@ -66,7 +83,8 @@ impl Display for ModifierAttributeCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttributeCommand {
     /// Returns the total value of the specified attribute.
     Get(Option<F32>),
@@ -95,3 +113,57 @@ impl Display for AttributeCommand {
         }
     }
 }
+
+impl Parse for BaseAttributeCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "get" => BaseAttributeCommand::Get(parse_optional_argument(reader)?),
+            "set" => BaseAttributeCommand::Set(parse_argument(reader)?),
+            "reset" => BaseAttributeCommand::Reset,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for ModifierAttributeCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => ModifierAttributeCommand::Add(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            "remove" => ModifierAttributeCommand::Remove(parse_argument(reader)?),
+            "value" => {
+                reader.expect_separator()?;
+                expect_literal(reader, "get")?;
+
+                ModifierAttributeCommand::Get(
+                    parse_argument(reader)?,
+                    parse_optional_argument(reader)?,
+                )
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for AttributeCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "get" => AttributeCommand::Get(parse_optional_argument(reader)?),
+            "base" => AttributeCommand::Base(parse_argument(reader)?),
+            "modifier" => AttributeCommand::Modifier(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -2,19 +2,21 @@ use crate::command::enums::bossbar_color::BossbarColor;
 use crate::command::enums::bossbar_get_type::BossbarGetType;
 use crate::command::enums::bossbar_style::BossbarStyle;
 use crate::entity_selector::EntitySelector;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::resource_location::ResourceLocation;
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BossbarSetType {
     /// Set the text color (if no color was specified as part of a text component) and bar color. Defaults to `white` upon creation.
     Color(BossbarColor),
     /// Set the bossbar's maximum value. Defaults to `100` upon creation.
     Max(i32),
     /// Set the bossbar's name.
-    Name(SNBT),
+    Name(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
     /// Change the set of players to whom the bar is visible. Defaults to none upon creation.
     Players(Option<EntitySelector>),
     /// Set the bossbar's visual amount of segments: continuous, 6 segments, 10 segments, 12 segments, or 20 segments. Defaults to `progress` upon creation.
@@ -47,13 +49,29 @@ impl Display for BossbarSetType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BossbarCommand {
-    Add(ResourceLocation, SNBT),
-    Get(ResourceLocation, BossbarGetType),
+    Add(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
+    Get(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        BossbarGetType,
+    ),
     List,
-    Remove(ResourceLocation),
-    Set(ResourceLocation, BossbarSetType),
+    Remove(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Set(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        BossbarSetType,
+    ),
 }
 
 impl Display for BossbarCommand {
@@ -67,3 +85,37 @@ impl Display for BossbarCommand {
         }
     }
 }
+
+impl Parse for BossbarSetType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "color" => BossbarSetType::Color(parse_argument(reader)?),
+            "max" => BossbarSetType::Max(parse_argument(reader)?),
+            "name" => BossbarSetType::Name(parse_argument(reader)?),
+            "players" => BossbarSetType::Players(parse_optional_argument(reader)?),
+            "style" => BossbarSetType::Style(parse_argument(reader)?),
+            "value" => BossbarSetType::Value(parse_argument(reader)?),
+            "visible" => BossbarSetType::Visible(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for BossbarCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => BossbarCommand::Add(parse_argument(reader)?, parse_argument(reader)?),
+            "get" => BossbarCommand::Get(parse_argument(reader)?, parse_argument(reader)?),
+            "list" => BossbarCommand::List,
+            "remove" => BossbarCommand::Remove(parse_argument(reader)?),
+            "set" => BossbarCommand::Set(parse_argument(reader)?, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,12 +1,17 @@
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, HasMacro)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CloneMaskMode {
     Replace,
     Masked,
-    Filtered(ResourceLocation),
+    Filtered(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for CloneMaskMode {
@@ -18,3 +23,17 @@ impl Display for CloneMaskMode {
         }
     }
 }
+
+impl Parse for CloneMaskMode {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "replace" => CloneMaskMode::Replace,
+            "masked" => CloneMaskMode::Masked,
+            "filtered" => CloneMaskMode::Filtered(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,9 +1,13 @@
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{
+    Parse, ParseError, StringReader, expect_literal, parse_argument, parse_optional,
+};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DamageType {
     At(Coordinates),
     By(EntitySelector, Option<EntitySelector>),
@@ -25,3 +29,22 @@ impl Display for DamageType {
         }
     }
 }
+
+impl Parse for DamageType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "at" => DamageType::At(parse_argument(reader)?),
+            "by" => DamageType::By(
+                parse_argument(reader)?,
+                parse_optional(reader, |reader| {
+                    expect_literal(reader, "from")?;
+                    parse_argument(reader)
+                })?,
+            ),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
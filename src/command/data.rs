@@ -1,17 +1,25 @@
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
 use crate::nbt_path::NbtPath;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_chained_argument,
+    parse_optional_argument,
+};
 use crate::resource_location::ResourceLocation;
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataTarget {
     Block(Coordinates),
     Entity(EntitySelector),
-    Storage(ResourceLocation),
+    Storage(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for DataTarget {
@@ -30,11 +38,12 @@ impl Display for DataTarget {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataCommandModification {
     From(DataTarget, Option<NbtPath>),
     String(DataTarget, Option<NbtPath>, Option<i32>, Option<i32>),
-    Value(SNBT),
+    Value(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
 }
 
 impl Display for DataCommandModification {
@@ -73,7 +82,8 @@ impl Display for DataCommandModification {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataCommandModificationMode {
     Append,
     Prepend,
@@ -94,10 +104,14 @@ impl Display for DataCommandModificationMode {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataCommand {
     Get(DataTarget, Option<NbtPath>, Option<NotNan<f32>>),
-    Merge(DataTarget, SNBT),
+    Merge(
+        DataTarget,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
     Modify(
         DataTarget,
         NbtPath,
@@ -139,3 +153,83 @@ impl Display for DataCommand {
         }
     }
 }
+
+impl Parse for DataTarget {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "block" => DataTarget::Block(parse_argument(reader)?),
+            "entity" => DataTarget::Entity(parse_argument(reader)?),
+            "storage" => DataTarget::Storage(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for DataCommandModification {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "from" => DataCommandModification::From(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "string" => {
+                let source = parse_argument(reader)?;
+                let path = parse_optional_argument(reader)?;
+                let start = parse_chained_argument(reader, &path)?;
+                let end = parse_chained_argument(reader, &start)?;
+
+                DataCommandModification::String(source, path, start, end)
+            }
+            "value" => DataCommandModification::Value(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for DataCommandModificationMode {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "append" => DataCommandModificationMode::Append,
+            "prepend" => DataCommandModificationMode::Prepend,
+            "insert" => DataCommandModificationMode::Insert(parse_argument(reader)?),
+            "merge" => DataCommandModificationMode::Merge,
+            "set" => DataCommandModificationMode::Set,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for DataCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "get" => {
+                let target = parse_argument(reader)?;
+                let path = parse_optional_argument(reader)?;
+                let scale = parse_chained_argument(reader, &path)?;
+
+                DataCommand::Get(target, path, scale)
+            }
+            "merge" => DataCommand::Merge(parse_argument(reader)?, parse_argument(reader)?),
+            "modify" => DataCommand::Modify(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            "remove" => DataCommand::Remove(parse_argument(reader)?, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,9 +1,11 @@
 use crate::command::enums::datapack_list_type::DatapackListType;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DatapackLoadPriority {
     First,
     Last,
@@ -22,12 +24,16 @@ impl Display for DatapackLoadPriority {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DatapackCommand {
     Disable(String),
     Enable(String, Option<DatapackLoadPriority>),
     List(Option<DatapackListType>),
-    Create(String, SNBT),
+    Create(
+        String,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
 }
 
 impl Display for DatapackCommand {
@@ -60,3 +66,44 @@ impl Display for DatapackCommand {
         }
     }
 }
+
+fn parse_pack_name(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    reader.expect_separator()?;
+
+    match reader.peek() {
+        Some('"' | '\'') => reader.read_quoted_string(),
+        _ => reader.read_required_word("pack name").map(str::to_string),
+    }
+}
+
+impl Parse for DatapackLoadPriority {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "first" => DatapackLoadPriority::First,
+            "last" => DatapackLoadPriority::Last,
+            "before" => DatapackLoadPriority::Before(parse_pack_name(reader)?),
+            "after" => DatapackLoadPriority::After(parse_pack_name(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for DatapackCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "disable" => DatapackCommand::Disable(parse_pack_name(reader)?),
+            "enable" => {
+                DatapackCommand::Enable(parse_pack_name(reader)?, parse_optional_argument(reader)?)
+            }
+            "list" => DatapackCommand::List(parse_optional_argument(reader)?),
+            "create" => DatapackCommand::Create(parse_pack_name(reader)?, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,12 +1,17 @@
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DebugCommandType {
     Start,
     Stop,
-    Function(ResourceLocation),
+    Function(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for DebugCommandType {
@@ -18,3 +23,17 @@ impl Display for DebugCommandType {
         }
     }
 }
+
+impl Parse for DebugCommandType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "start" => DebugCommandType::Start,
+            "stop" => DebugCommandType::Stop,
+            "function" => DebugCommandType::Function(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
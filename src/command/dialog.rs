@@ -1,11 +1,17 @@
 use crate::entity_selector::EntitySelector;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DialogCommand {
-    Show(EntitySelector, ResourceLocation),
+    Show(
+        EntitySelector,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Clear(EntitySelector),
 }
 
@@ -21,3 +27,16 @@ impl Display for DialogCommand {
         }
     }
 }
+
+impl Parse for DialogCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "show" => DialogCommand::Show(parse_argument(reader)?, parse_argument(reader)?),
+            "clear" => DialogCommand::Clear(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
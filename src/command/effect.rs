@@ -1,9 +1,14 @@
 use crate::entity_selector::EntitySelector;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_chained_argument,
+    parse_optional_argument,
+};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EffectDuration {
     Duration(i32),
     Infinite,
@@ -18,11 +23,17 @@ impl Display for EffectDuration {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EffectCommand {
-    Clear(Option<EntitySelector>, Option<ResourceLocation>),
+    Clear(
+        Option<EntitySelector>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        Option<ResourceLocation>,
+    ),
     Give(
         EntitySelector,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
         Option<EffectDuration>,
         Option<i32>,
@@ -66,3 +77,41 @@ impl Display for EffectCommand {
         }
     }
 }
+
+impl Parse for EffectDuration {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("infinite") {
+            reader.read_literal()?;
+
+            return Ok(EffectDuration::Infinite);
+        }
+
+        reader.read_int().map(EffectDuration::Duration)
+    }
+}
+
+impl Parse for EffectCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "clear" => {
+                let selector = parse_optional_argument(reader)?;
+                let effect = parse_chained_argument(reader, &selector)?;
+
+                EffectCommand::Clear(selector, effect)
+            }
+            "give" => {
+                let selector = parse_argument(reader)?;
+                let effect = parse_argument(reader)?;
+                let duration = parse_optional_argument(reader)?;
+                let amplifier = parse_chained_argument(reader, &duration)?;
+                let hide_particles = parse_chained_argument(reader, &amplifier)?;
+
+                EffectCommand::Give(selector, effect, duration, amplifier, hide_particles)
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
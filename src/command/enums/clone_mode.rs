@@ -1,3 +1,10 @@
 use crate::create_enum;
 
-create_enum!(CloneMode, [], Normal, Force, Remove);
+create_enum!(
+    CloneMode,
+    [],
+    Normal,
+    Force,
+    #[strum(serialize = "move")]
+    Remove
+);
@@ -1,3 +1,9 @@
 use crate::create_enum;
 
-create_enum!(EntityAnchor, [], Eyes, Feet);
+create_enum!(
+    EntityAnchor,
+    [Default],
+    Eyes,
+    #[default]
+    Feet
+);
@@ -46,11 +46,13 @@ macro_rules! create_enum {
         $($(#[$variant_attr:meta])* $variant:ident),+
         $(,)?
     ) => {
-        #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, ::strum::Display, ::strum::EnumString, ::minecraft_command_types_derive::HasMacro, ::serde::Serialize, ::serde::Deserialize, $($custom_derives),*)]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, ::strum::Display, ::strum::EnumString, ::minecraft_command_types_derive::HasMacro, ::minecraft_command_types_derive::Visit, ::minecraft_command_types_derive::VisitMut, ::minecraft_command_types_derive::Fold, ::serde::Serialize, ::serde::Deserialize, $($custom_derives),*)]
         #[strum(serialize_all = "snake_case")]
         pub enum $name {
             $($(#[$variant_attr])* $variant,)*
         }
+
+        $crate::impl_parse_enum!($name);
     };
 
     (
@@ -60,10 +62,25 @@ macro_rules! create_enum {
         $($(#[$variant_attr:meta])* $variant:ident),+
         $(,)?
     ) => {
-        #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, ::strum::Display, ::strum::EnumString, ::minecraft_command_types_derive::HasMacro, ::serde::Serialize, ::serde::Deserialize, $($custom_derives),*)]
+        #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, ::strum::Display, ::strum::EnumString, ::minecraft_command_types_derive::HasMacro, ::minecraft_command_types_derive::Visit, ::minecraft_command_types_derive::VisitMut, ::minecraft_command_types_derive::Fold, ::serde::Serialize, ::serde::Deserialize, $($custom_derives),*)]
         #[strum(serialize_all = $serialize_all)]
         pub enum $name {
             $($(#[$variant_attr])* $variant,)*
         }
+
+        $crate::impl_parse_enum!($name);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_parse_enum {
+    ($name:ident) => {
+        impl $crate::parser::Parse for $name {
+            fn parse(
+                reader: &mut $crate::parser::StringReader<'_>,
+            ) -> Result<Self, $crate::parser::ParseError> {
+                reader.read_enum(stringify!($name))
+            }
+        }
     };
 }
@@ -29,5 +29,6 @@ create_enum!(
     Player,
     Ambient,
     Voice,
+    #[strum(serialize = "*")]
     All
 );
@@ -15,16 +15,20 @@ use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
 use crate::item::ItemPredicate;
 use crate::nbt_path::NbtPath;
+use crate::parser::{
+    Parse, ParseError, ParseErrorKind, StringReader, parse_argument, parse_optional, parse_word,
+};
 use crate::range::{FloatRange, IntegerRange};
 use crate::resource_location::ResourceLocation;
-use crate::rotation::Rotation;
-use minecraft_command_types_derive::HasMacro;
+use crate::rotation::RelativeRotation;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
-use strum::Display;
+use strum::{Display, EnumString};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Facing {
     Position(Coordinates),
     Entity(EntitySelector, EntityAnchor),
@@ -39,7 +43,8 @@ impl Display for Facing {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Positioned {
     Position(Coordinates),
     As(EntitySelector),
@@ -56,9 +61,10 @@ impl Display for Positioned {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rotated {
-    Rotation(Rotation),
+    Rotation(RelativeRotation),
     As(EntitySelector),
 }
 
@@ -71,7 +77,22 @@ impl Display for Rotated {
     }
 }
 
-#[derive(Display, Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(
+    Display,
+    EnumString,
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    HasMacro,
+    Visit,
+    VisitMut,
+    Fold,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreComparisonOperator {
     #[strum(serialize = "<")]
     LessThan,
@@ -85,7 +106,8 @@ pub enum ScoreComparisonOperator {
     GreaterThanOrEqualTo,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreComparison {
     Range(IntegerRange),
     Score(ScoreComparisonOperator, PlayerScore),
@@ -102,10 +124,12 @@ impl Display for ScoreComparison {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExecuteIfSubcommand {
     Biome(
         Coordinates,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
         Option<Box<ExecuteSubcommand>>,
     ),
@@ -118,9 +142,17 @@ pub enum ExecuteIfSubcommand {
         Option<Box<ExecuteSubcommand>>,
     ),
     Data(DataTarget, NbtPath, Option<Box<ExecuteSubcommand>>),
-    Dimension(ResourceLocation, Option<Box<ExecuteSubcommand>>),
+    Dimension(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<Box<ExecuteSubcommand>>,
+    ),
     Entity(EntitySelector, Option<Box<ExecuteSubcommand>>),
-    Function(ResourceLocation, Box<ExecuteSubcommand>),
+    Function(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Box<ExecuteSubcommand>,
+    ),
     Items(
         ItemSource,
         String,
@@ -128,9 +160,18 @@ pub enum ExecuteIfSubcommand {
         Option<Box<ExecuteSubcommand>>,
     ),
     Loaded(ColumnPosition, Option<Box<ExecuteSubcommand>>),
-    Predicate(ResourceLocation, Option<Box<ExecuteSubcommand>>),
+    Predicate(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<Box<ExecuteSubcommand>>,
+    ),
     Score(PlayerScore, ScoreComparison, Option<Box<ExecuteSubcommand>>),
-    Stopwatch(ResourceLocation, FloatRange, Option<Box<ExecuteSubcommand>>),
+    Stopwatch(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        FloatRange,
+        Option<Box<ExecuteSubcommand>>,
+    ),
 }
 
 impl Display for ExecuteIfSubcommand {
@@ -361,7 +402,8 @@ impl ExecuteIfSubcommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExecuteStoreSubcommand {
     Data(
         DataTarget,
@@ -370,7 +412,12 @@ pub enum ExecuteStoreSubcommand {
         NotNan<f32>,
         Box<ExecuteSubcommand>,
     ),
-    Bossbar(ResourceLocation, BossbarStoreType, Box<ExecuteSubcommand>),
+    Bossbar(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        BossbarStoreType,
+        Box<ExecuteSubcommand>,
+    ),
     Score(PlayerScore, Box<ExecuteSubcommand>),
 }
 
@@ -418,18 +465,27 @@ impl ExecuteStoreSubcommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExecuteSubcommand {
     Align(BTreeSet<Axis>, Box<ExecuteSubcommand>),
     Anchored(EntityAnchor, Box<ExecuteSubcommand>),
     As(EntitySelector, Box<ExecuteSubcommand>),
     At(EntitySelector, Box<ExecuteSubcommand>),
     Facing(Facing, Box<ExecuteSubcommand>),
-    In(ResourceLocation, Box<ExecuteSubcommand>),
+    In(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Box<ExecuteSubcommand>,
+    ),
     On(Relation, Box<ExecuteSubcommand>),
     Positioned(Positioned, Box<ExecuteSubcommand>),
     Rotated(Rotated, Box<ExecuteSubcommand>),
-    Summon(ResourceLocation, Box<ExecuteSubcommand>),
+    Summon(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Box<ExecuteSubcommand>,
+    ),
     If(bool, ExecuteIfSubcommand),
     Store(StoreType, ExecuteStoreSubcommand),
     Run(Box<Command>),
@@ -547,3 +603,273 @@ impl ExecuteSubcommand {
         }
     }
 }
+
+pub(crate) fn final_command(subcommand: &ExecuteSubcommand) -> Option<&Command> {
+    match subcommand {
+        ExecuteSubcommand::Align(_, next)
+        | ExecuteSubcommand::Anchored(_, next)
+        | ExecuteSubcommand::As(_, next)
+        | ExecuteSubcommand::At(_, next)
+        | ExecuteSubcommand::Facing(_, next)
+        | ExecuteSubcommand::In(_, next)
+        | ExecuteSubcommand::On(_, next)
+        | ExecuteSubcommand::Positioned(_, next)
+        | ExecuteSubcommand::Rotated(_, next)
+        | ExecuteSubcommand::Summon(_, next) => final_command(next),
+        ExecuteSubcommand::If(_, if_subcommand) => {
+            if_next(if_subcommand).and_then(|next| final_command(next))
+        }
+        ExecuteSubcommand::Store(_, store) => final_command(store_next(store)),
+        ExecuteSubcommand::Run(command) => Some(command),
+    }
+}
+
+pub(crate) fn if_next(subcommand: &ExecuteIfSubcommand) -> Option<&ExecuteSubcommand> {
+    match subcommand {
+        ExecuteIfSubcommand::Biome(.., next)
+        | ExecuteIfSubcommand::Block(.., next)
+        | ExecuteIfSubcommand::Blocks(.., next)
+        | ExecuteIfSubcommand::Data(.., next)
+        | ExecuteIfSubcommand::Dimension(.., next)
+        | ExecuteIfSubcommand::Entity(.., next)
+        | ExecuteIfSubcommand::Items(.., next)
+        | ExecuteIfSubcommand::Loaded(.., next)
+        | ExecuteIfSubcommand::Predicate(.., next)
+        | ExecuteIfSubcommand::Score(.., next)
+        | ExecuteIfSubcommand::Stopwatch(.., next) => next.as_deref(),
+        ExecuteIfSubcommand::Function(_, next) => Some(next),
+    }
+}
+
+pub(crate) fn store_next(subcommand: &ExecuteStoreSubcommand) -> &ExecuteSubcommand {
+    match subcommand {
+        ExecuteStoreSubcommand::Data(.., next)
+        | ExecuteStoreSubcommand::Bossbar(.., next)
+        | ExecuteStoreSubcommand::Score(_, next) => next,
+    }
+}
+
+crate::impl_parse_enum!(ScoreComparisonOperator);
+
+impl Parse for Facing {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("entity") {
+            reader.read_literal()?;
+
+            return Ok(Facing::Entity(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ));
+        }
+
+        Coordinates::parse(reader).map(Facing::Position)
+    }
+}
+
+impl Parse for Positioned {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("as") {
+            reader.read_literal()?;
+
+            return Ok(Positioned::As(parse_argument(reader)?));
+        }
+
+        if reader.peek_str("over") {
+            reader.read_literal()?;
+
+            return Ok(Positioned::Over(parse_argument(reader)?));
+        }
+
+        Coordinates::parse(reader).map(Positioned::Position)
+    }
+}
+
+impl Parse for Rotated {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("as") {
+            reader.read_literal()?;
+
+            return Ok(Rotated::As(parse_argument(reader)?));
+        }
+
+        RelativeRotation::parse(reader).map(Rotated::Rotation)
+    }
+}
+
+impl Parse for ScoreComparison {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("matches") {
+            reader.read_literal()?;
+
+            return Ok(ScoreComparison::Range(parse_argument(reader)?));
+        }
+
+        Ok(ScoreComparison::Score(
+            ScoreComparisonOperator::parse(reader)?,
+            parse_argument(reader)?,
+        ))
+    }
+}
+
+fn parse_next(reader: &mut StringReader<'_>) -> Result<Option<Box<ExecuteSubcommand>>, ParseError> {
+    parse_optional(reader, Box::<ExecuteSubcommand>::parse)
+}
+
+impl Parse for ExecuteIfSubcommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "biome" => ExecuteIfSubcommand::Biome(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            "block" => ExecuteIfSubcommand::Block(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            "blocks" => ExecuteIfSubcommand::Blocks(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            "data" => ExecuteIfSubcommand::Data(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            "dimension" => {
+                ExecuteIfSubcommand::Dimension(parse_argument(reader)?, parse_next(reader)?)
+            }
+            "entity" => ExecuteIfSubcommand::Entity(parse_argument(reader)?, parse_next(reader)?),
+            "function" => {
+                ExecuteIfSubcommand::Function(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "items" => {
+                let source = parse_argument(reader)?;
+                reader.expect_separator()?;
+                let slot = parse_word(reader)?;
+
+                ExecuteIfSubcommand::Items(
+                    source,
+                    slot,
+                    parse_argument(reader)?,
+                    parse_next(reader)?,
+                )
+            }
+            "loaded" => ExecuteIfSubcommand::Loaded(parse_argument(reader)?, parse_next(reader)?),
+            "predicate" => {
+                ExecuteIfSubcommand::Predicate(parse_argument(reader)?, parse_next(reader)?)
+            }
+            "score" => ExecuteIfSubcommand::Score(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            "stopwatch" => ExecuteIfSubcommand::Stopwatch(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_next(reader)?,
+            ),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for ExecuteStoreSubcommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("bossbar") {
+            reader.read_literal()?;
+
+            return Ok(ExecuteStoreSubcommand::Bossbar(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ));
+        }
+
+        if reader.peek_str("score") {
+            reader.read_literal()?;
+
+            return Ok(ExecuteStoreSubcommand::Score(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ));
+        }
+
+        Ok(ExecuteStoreSubcommand::Data(
+            DataTarget::parse(reader)?,
+            parse_argument(reader)?,
+            parse_argument(reader)?,
+            parse_argument(reader)?,
+            parse_argument(reader)?,
+        ))
+    }
+}
+
+fn parse_axes(reader: &mut StringReader<'_>) -> Result<BTreeSet<Axis>, ParseError> {
+    let start = reader.cursor();
+    let word = reader.read_required_word("axes")?;
+    let invalid = || {
+        ParseError::new(
+            start,
+            ParseErrorKind::InvalidValue("axes", word.to_string()),
+        )
+    };
+    let mut axes = BTreeSet::new();
+
+    for c in word.chars() {
+        let axis = match c {
+            'x' => Axis::X,
+            'y' => Axis::Y,
+            'z' => Axis::Z,
+            _ => return Err(invalid()),
+        };
+
+        if !axes.insert(axis) {
+            return Err(invalid());
+        }
+    }
+
+    Ok(axes)
+}
+
+impl Parse for ExecuteSubcommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "align" => {
+                reader.expect_separator()?;
+
+                ExecuteSubcommand::Align(parse_axes(reader)?, parse_argument(reader)?)
+            }
+            "anchored" => {
+                ExecuteSubcommand::Anchored(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "as" => ExecuteSubcommand::As(parse_argument(reader)?, parse_argument(reader)?),
+            "at" => ExecuteSubcommand::At(parse_argument(reader)?, parse_argument(reader)?),
+            "facing" => ExecuteSubcommand::Facing(parse_argument(reader)?, parse_argument(reader)?),
+            "in" => ExecuteSubcommand::In(parse_argument(reader)?, parse_argument(reader)?),
+            "on" => ExecuteSubcommand::On(parse_argument(reader)?, parse_argument(reader)?),
+            "positioned" => {
+                ExecuteSubcommand::Positioned(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "rotated" => {
+                ExecuteSubcommand::Rotated(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "summon" => ExecuteSubcommand::Summon(parse_argument(reader)?, parse_argument(reader)?),
+            "if" => ExecuteSubcommand::If(false, parse_argument(reader)?),
+            "unless" => ExecuteSubcommand::If(true, parse_argument(reader)?),
+            "store" => ExecuteSubcommand::Store(parse_argument(reader)?, parse_argument(reader)?),
+            "run" => ExecuteSubcommand::Run(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,9 +1,12 @@
 use crate::command::enums::experience_type::ExperienceType;
+use crate::command::enums::experience_type::ExperienceType::Points;
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExperienceCommand {
     Add(EntitySelector, i32, ExperienceType),
     Set(EntitySelector, i32, ExperienceType),
@@ -25,3 +28,25 @@ impl Display for ExperienceCommand {
         }
     }
 }
+
+impl Parse for ExperienceCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => ExperienceCommand::Add(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?.unwrap_or(Points),
+            ),
+            "set" => ExperienceCommand::Set(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?.unwrap_or(Points),
+            ),
+            "query" => ExperienceCommand::Query(parse_argument(reader)?, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
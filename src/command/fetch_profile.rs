@@ -1,7 +1,9 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_word};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FetchProfileCommand {
     Name(String),
     Id(String),
@@ -15,3 +17,17 @@ impl Display for FetchProfileCommand {
         }
     }
 }
+
+impl Parse for FetchProfileCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+        reader.expect_separator()?;
+
+        Ok(match literal {
+            "name" => FetchProfileCommand::Name(parse_word(reader)?),
+            "id" => FetchProfileCommand::Id(parse_word(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,10 +1,13 @@
 use crate::block::BlockState;
 use crate::command::enums::fill_mode::FillMode;
+use crate::command::enums::fill_mode::FillMode::Replace;
 use crate::command::enums::fill_replace_mode::FillReplaceMode;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillCommand {
     Mode(FillMode),
     Replace(BlockState, Option<FillReplaceMode>),
@@ -15,7 +18,7 @@ impl Display for FillCommand {
         match self {
             FillCommand::Mode(mode) => mode.fmt(f),
             FillCommand::Replace(predicate, replace_mode) => {
-                predicate.fmt(f)?;
+                write!(f, "replace {}", predicate)?;
 
                 if let Some(replace_mode) = replace_mode {
                     write!(f, " {}", replace_mode)?;
@@ -26,3 +29,18 @@ impl Display for FillCommand {
         }
     }
 }
+
+impl Parse for FillCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let mode = FillMode::parse(reader)?;
+
+        if mode == Replace && reader.has_argument() {
+            return Ok(FillCommand::Replace(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ));
+        }
+
+        Ok(FillCommand::Mode(mode))
+    }
+}
@@ -1,8 +1,10 @@
 use crate::column_position::ColumnPosition;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForceloadRemoveType {
     ColumnPosition(ColumnPosition, Option<ColumnPosition>),
     All,
@@ -25,7 +27,8 @@ impl Display for ForceloadRemoveType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForceloadCommand {
     Add(ColumnPosition, Option<ColumnPosition>),
     Remove(ForceloadRemoveType),
@@ -59,3 +62,34 @@ impl Display for ForceloadCommand {
         }
     }
 }
+
+impl Parse for ForceloadRemoveType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("all") {
+            reader.read_literal()?;
+
+            return Ok(ForceloadRemoveType::All);
+        }
+
+        Ok(ForceloadRemoveType::ColumnPosition(
+            ColumnPosition::parse(reader)?,
+            parse_optional_argument(reader)?,
+        ))
+    }
+}
+
+impl Parse for ForceloadCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => {
+                ForceloadCommand::Add(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "remove" => ForceloadCommand::Remove(parse_argument(reader)?),
+            "query" => ForceloadCommand::Query(parse_optional_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
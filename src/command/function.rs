@@ -1,12 +1,17 @@
 use crate::command::data::DataTarget;
 use crate::nbt_path::{NbtPath, SNBTCompound};
+use crate::parser::{
+    Parse, ParseError, StringReader, expect_literal, parse_argument, parse_optional_argument,
+};
 use crate::snbt::fmt_snbt_compound;
-use minecraft_command_types_derive::HasMacro;
+use crate::snbt::parse_snbt_compound;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FunctionCommandArguments {
-    Compound(SNBTCompound),
+    Compound(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBTCompound),
     DataTarget(DataTarget, Option<NbtPath>),
 }
 
@@ -26,3 +31,18 @@ impl Display for FunctionCommandArguments {
         }
     }
 }
+
+impl Parse for FunctionCommandArguments {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek() == Some('{') {
+            return parse_snbt_compound(reader).map(FunctionCommandArguments::Compound);
+        }
+
+        expect_literal(reader, "with")?;
+
+        Ok(FunctionCommandArguments::DataTarget(
+            parse_argument(reader)?,
+            parse_optional_argument(reader)?,
+        ))
+    }
+}
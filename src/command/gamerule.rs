@@ -1,7 +1,9 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameruleValue {
     Integer(i32),
     Boolean(bool),
@@ -15,3 +17,13 @@ impl Display for GameruleValue {
         }
     }
 }
+
+impl Parse for GameruleValue {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("true") || reader.peek_str("false") {
+            reader.read_bool().map(GameruleValue::Boolean)
+        } else {
+            reader.read_int().map(GameruleValue::Integer)
+        }
+    }
+}
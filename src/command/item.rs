@@ -1,20 +1,42 @@
 use crate::command::item_source::ItemSource;
 use crate::item::ItemStack;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional, parse_word};
+use crate::resource_location::ResourceLocation;
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+/// Item modifiers are either inline SNBT or the id of a modifier, which is kept as a plain string.
+fn fmt_modifier(f: &mut Formatter<'_>, modifier: &SNBT) -> std::fmt::Result {
+    match modifier {
+        SNBT::String(id) => f.write_str(&id.1),
+        modifier => modifier.fmt(f),
+    }
+}
+
+pub(crate) fn parse_modifier(reader: &mut StringReader<'_>) -> Result<SNBT, ParseError> {
+    match reader.peek() {
+        Some('{' | '[') => SNBT::parse(reader),
+        _ => ResourceLocation::parse(reader).map(SNBT::string),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemCommand {
-    Modifier(SNBT),
+    Modifier(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
     With(ItemStack, Option<i32>),
-    From(ItemSource, String, Option<SNBT>),
+    From(
+        ItemSource,
+        String,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>,
+    ),
 }
 
 impl Display for ItemCommand {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ItemCommand::Modifier(item) => item.fmt(f),
+            ItemCommand::Modifier(modifier) => fmt_modifier(f, modifier),
             ItemCommand::With(item, count) => {
                 write!(f, "with {}", item)?;
 
@@ -28,7 +50,8 @@ impl Display for ItemCommand {
                 write!(f, "from {} {}", source, slot)?;
 
                 if let Some(modifier) = modifier {
-                    write!(f, " {}", modifier)?;
+                    f.write_str(" ")?;
+                    fmt_modifier(f, modifier)?;
                 }
 
                 Ok(())
@@ -36,3 +59,24 @@ impl Display for ItemCommand {
         }
     }
 }
+
+impl Parse for ItemCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "with" => {
+                ItemCommand::With(parse_argument(reader)?, parse_optional(reader, i32::parse)?)
+            }
+            "from" => {
+                let source = parse_argument(reader)?;
+                reader.expect_separator()?;
+                let slot = parse_word(reader)?;
+
+                ItemCommand::From(source, slot, parse_optional(reader, parse_modifier)?)
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
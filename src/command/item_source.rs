@@ -1,9 +1,11 @@
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemSource {
     Block(Coordinates),
     Entity(EntitySelector),
@@ -17,3 +19,16 @@ impl Display for ItemSource {
         }
     }
 }
+
+impl Parse for ItemSource {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "block" => ItemSource::Block(parse_argument(reader)?),
+            "entity" => ItemSource::Entity(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
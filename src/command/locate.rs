@@ -1,7 +1,9 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LocateType {
     Structure,
     Biome,
@@ -17,3 +19,17 @@ impl Display for LocateType {
         }
     }
 }
+
+impl Parse for LocateType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "structure" => LocateType::Structure,
+            "biome" => LocateType::Biome,
+            "poi" => LocateType::POI,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -2,11 +2,16 @@ use crate::command::item_source::ItemSource;
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
 use crate::item::ItemStack;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_optional, parse_optional_argument,
+    parse_word,
+};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LootTarget {
     Give(EntitySelector),
     Insert(Coordinates),
@@ -33,7 +38,8 @@ impl Display for LootTarget {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LootItemSource {
     Tool(ItemStack),
     Mainhand,
@@ -50,10 +56,19 @@ impl Display for LootItemSource {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LootSource {
-    Fish(ResourceLocation, Coordinates, Option<LootItemSource>),
-    Loot(ResourceLocation),
+    Fish(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Coordinates,
+        Option<LootItemSource>,
+    ),
+    Loot(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Kill(EntitySelector),
     Mine(Coordinates, Option<LootItemSource>),
 }
@@ -88,3 +103,59 @@ impl Display for LootSource {
         }
     }
 }
+
+impl Parse for LootTarget {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "give" => LootTarget::Give(parse_argument(reader)?),
+            "insert" => LootTarget::Insert(parse_argument(reader)?),
+            "spawn" => LootTarget::Spawn(parse_argument(reader)?),
+            "replace" => {
+                let item_source = parse_argument(reader)?;
+                reader.expect_separator()?;
+                let slot = parse_word(reader)?;
+
+                LootTarget::Replace(item_source, slot, parse_optional(reader, i32::parse)?)
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for LootItemSource {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+
+        match reader.read_word() {
+            "mainhand" => Ok(LootItemSource::Mainhand),
+            "offhand" => Ok(LootItemSource::Offhand),
+            _ => {
+                reader.set_cursor(start);
+
+                ItemStack::parse(reader).map(LootItemSource::Tool)
+            }
+        }
+    }
+}
+
+impl Parse for LootSource {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "fish" => LootSource::Fish(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "loot" => LootSource::Loot(parse_argument(reader)?),
+            "kill" => LootSource::Kill(parse_argument(reader)?),
+            "mine" => LootSource::Mine(parse_argument(reader)?, parse_optional_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
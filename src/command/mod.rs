@@ -91,9 +91,13 @@ use crate::command::trigger::TriggerAction;
 use crate::command::waypoint::WaypointCommand;
 use crate::command::whitelist::WhitelistCommand;
 use crate::command::worldborder::WorldborderCommand;
-use crate::coordinate::{Coordinates, WorldCoordinate};
+use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
 use crate::item::{ItemPredicate, ItemStack};
+use crate::parser::{
+    Parse, ParseError, ParseErrorKind, StringReader, expect_literal, parse_all, parse_argument,
+    parse_chained_argument, parse_greedy, parse_optional, parse_optional_argument, parse_word,
+};
 use crate::resource_location::ResourceLocation;
 use crate::snbt::SNBT;
 use crate::time::Time;
@@ -102,11 +106,14 @@ use enums::banlist_type::BanlistType;
 use enums::clone_mode::CloneMode;
 use enums::difficulty::Difficulty;
 use enums::gamemode::Gamemode;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[visit(hook = player_score)]
 pub struct PlayerScore {
     pub selector: EntitySelector,
     pub objective: String,
@@ -129,19 +136,37 @@ impl PlayerScore {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+impl Parse for PlayerScore {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let selector = EntitySelector::parse(reader)?;
+        reader.expect_separator()?;
+
+        Ok(PlayerScore::new(selector, parse_word(reader)?))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[visit(hook = command)]
 pub enum Command {
     Advancement(AdvancementType, EntitySelector, AdvancementCommand),
-    Attribute(EntitySelector, ResourceLocation, AttributeCommand),
+    Attribute(
+        EntitySelector,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        AttributeCommand,
+    ),
     Ban(EntitySelector, Option<String>),
     BanIP(String, Option<String>),
     Banlist(Option<BanlistType>),
     Bossbar(BossbarCommand),
     Clear(Option<EntitySelector>, Option<ItemPredicate>, Option<i32>),
     Clone {
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         source_dimension: Option<ResourceLocation>,
         begin: Coordinates,
         end: Coordinates,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         target_dimension: Option<ResourceLocation>,
         destination: Coordinates,
         strict: bool,
@@ -151,6 +176,7 @@ pub enum Command {
     Damage(
         EntitySelector,
         NotNan<f32>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         Option<ResourceLocation>,
         Option<DamageType>,
     ),
@@ -162,7 +188,12 @@ pub enum Command {
     Dialog(DialogCommand),
     Difficulty(Option<Difficulty>),
     Effect(EffectCommand),
-    Enchant(EntitySelector, ResourceLocation, Option<i32>),
+    Enchant(
+        EntitySelector,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<i32>,
+    ),
     Execute(ExecuteSubcommand),
     Experience(ExperienceCommand),
     FetchProfile(FetchProfileCommand),
@@ -170,11 +201,17 @@ pub enum Command {
     FillBiome(
         Coordinates,
         Coordinates,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         Option<ResourceLocation>,
     ),
     Forceload(ForceloadCommand),
-    Function(ResourceLocation, Option<FunctionCommandArguments>),
+    Function(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<FunctionCommandArguments>,
+    ),
     Gamemode(Gamemode, Option<EntitySelector>),
     Gamerule(String, Option<GameruleValue>),
     Give(EntitySelector, ItemStack, Option<i32>),
@@ -184,7 +221,11 @@ pub enum Command {
     Kick(EntitySelector, Option<String>),
     Kill(Option<EntitySelector>),
     List(bool),
-    Locate(LocateType, ResourceLocation),
+    Locate(
+        LocateType,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Loot(LootTarget, LootSource),
     Me(String),
     Message(EntitySelector, String),
@@ -195,10 +236,11 @@ pub enum Command {
     Perf(bool),
     Place(PlaceCommand),
     Playsound(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
         Option<SoundSource>,
         Option<EntitySelector>,
-        Option<WorldCoordinate>,
+        Option<Coordinates>,
         Option<NotNan<f32>>,
         Option<NotNan<f32>>,
         Option<NotNan<f32>>,
@@ -238,15 +280,24 @@ pub enum Command {
     StopSound(
         EntitySelector,
         Option<StopSoundSource>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         Option<ResourceLocation>,
     ),
     Stopwatch(StopwatchCommand),
-    Summon(ResourceLocation, Option<Coordinates>, Option<SNBT>),
+    Summon(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<Coordinates>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>,
+    ),
     Tag(EntitySelector, TagCommand),
     Team(TeamCommand),
     TeamMessage(String),
     Teleport(TeleportCommand),
-    Tellraw(EntitySelector, SNBT),
+    Tellraw(
+        EntitySelector,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
     Test(TestCommand),
     Tick(TickCommand),
     Time(TimeCommand),
@@ -476,7 +527,7 @@ impl Display for Command {
                 Ok(())
             }
             Command::Data(data_command) => write!(f, "data {}", data_command),
-            Command::Datapack(datapack_command) => write!(f, "datapack {},", datapack_command),
+            Command::Datapack(datapack_command) => write!(f, "datapack {}", datapack_command),
             Command::Debug(debug_type) => write!(f, "debug {}", debug_type),
             Command::DefaultGamemode(gamemode) => write!(f, "defaultgamemode {}", gamemode),
             Command::Deop(selector) => write!(f, "deop {}", selector),
@@ -516,7 +567,7 @@ impl Display for Command {
                 write!(f, "fillbiome {} {} {}", from, to, biome)?;
 
                 if let Some(filter) = filter {
-                    write!(f, " {}", filter)?;
+                    write!(f, " replace {}", filter)?;
                 }
 
                 Ok(())
@@ -567,9 +618,12 @@ impl Display for Command {
 
                 Ok(())
             }
-            Command::Item(source, slot, command) => {
-                write!(f, "item {} {} {}", source, slot, command)
-            }
+            Command::Item(source, slot, command) => match command {
+                ItemCommand::Modifier(_) => {
+                    write!(f, "item modify {} {} {}", source, slot, command)
+                }
+                _ => write!(f, "item replace {} {} {}", source, slot, command),
+            },
             Command::JFR(start) => {
                 f.write_str("jfr ")?;
 
@@ -672,7 +726,7 @@ impl Display for Command {
                 Ok(())
             }
             Command::Publish(allow_commands, gamemode, port) => {
-                f.write_str("playsound")?;
+                f.write_str("publish")?;
 
                 if let Some(allow_commands) = allow_commands {
                     write!(f, " {}", allow_commands)?;
@@ -896,3 +950,519 @@ impl Display for Command {
         }
     }
 }
+
+fn parse_start_stop(reader: &mut StringReader<'_>) -> Result<bool, ParseError> {
+    let start = reader.cursor();
+    let literal = reader.read_literal()?;
+
+    match literal {
+        "start" => Ok(true),
+        "stop" => Ok(false),
+        _ => Err(reader.unknown_argument(start, literal)),
+    }
+}
+
+fn parse_flag(reader: &mut StringReader<'_>, flag: &str) -> Result<bool, ParseError> {
+    parse_optional(reader, |reader| expect_literal(reader, flag)).map(|flag| flag.is_some())
+}
+
+fn parse_clone(reader: &mut StringReader<'_>) -> Result<Command, ParseError> {
+    let source_dimension = parse_dimension_prefix(reader, "from")?;
+    let begin = Coordinates::parse(reader)?;
+    let end = parse_argument(reader)?;
+    reader.expect_separator()?;
+    let target_dimension = parse_dimension_prefix(reader, "to")?;
+    let destination = Coordinates::parse(reader)?;
+    let strict = reader.peek_str(" strict") && parse_flag(reader, "strict")?;
+    let mask_mode = parse_optional_argument(reader)?;
+    let clone_mode = parse_chained_argument(reader, &mask_mode)?;
+
+    Ok(Command::Clone {
+        source_dimension,
+        begin,
+        end,
+        target_dimension,
+        destination,
+        strict,
+        mask_mode: mask_mode.unwrap_or(CloneMaskMode::Replace),
+        clone_mode: clone_mode.unwrap_or(CloneMode::Normal),
+    })
+}
+
+/// Parses the `from <dimension> ` and `to <dimension> ` prefixes of `clone`.
+fn parse_dimension_prefix(
+    reader: &mut StringReader<'_>,
+    keyword: &str,
+) -> Result<Option<ResourceLocation>, ParseError> {
+    if !reader.peek_str(keyword) {
+        return Ok(None);
+    }
+
+    expect_literal(reader, keyword)?;
+    let dimension = parse_argument(reader)?;
+    reader.expect_separator()?;
+
+    Ok(Some(dimension))
+}
+
+fn parse_item(reader: &mut StringReader<'_>) -> Result<Command, ParseError> {
+    let start = reader.cursor();
+    let literal = reader.read_literal()?;
+
+    let source = parse_argument(reader)?;
+    reader.expect_separator()?;
+    let slot = parse_word(reader)?;
+
+    let command = match literal {
+        "modify" => {
+            reader.expect_separator()?;
+
+            ItemCommand::Modifier(item::parse_modifier(reader)?)
+        }
+        "replace" => parse_argument(reader)?,
+        _ => return Err(reader.unknown_argument(start, literal)),
+    };
+
+    Ok(Command::Item(source, slot, command))
+}
+
+fn parse_spreadplayers(reader: &mut StringReader<'_>) -> Result<Command, ParseError> {
+    let center = parse_argument(reader)?;
+    let spread_distance = parse_argument(reader)?;
+    let max_range = parse_argument(reader)?;
+    reader.expect_separator()?;
+
+    let max_height = if reader.peek_str("under") {
+        reader.read_literal()?;
+        let max_height = parse_argument(reader)?;
+        reader.expect_separator()?;
+
+        Some(max_height)
+    } else {
+        None
+    };
+
+    Ok(Command::SpreadPlayers(
+        center,
+        spread_distance,
+        max_range,
+        max_height,
+        bool::parse(reader)?,
+        parse_argument(reader)?,
+    ))
+}
+
+impl Parse for Command {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let name = reader.read_literal()?;
+
+        Ok(match name {
+            "advancement" => Command::Advancement(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            "attribute" => Command::Attribute(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            "ban" => Command::Ban(
+                parse_argument(reader)?,
+                parse_optional(reader, parse_greedy)?,
+            ),
+            "ban-ip" => {
+                reader.expect_separator()?;
+
+                Command::BanIP(parse_word(reader)?, parse_optional(reader, parse_greedy)?)
+            }
+            "banlist" => Command::Banlist(parse_optional_argument(reader)?),
+            "bossbar" => Command::Bossbar(parse_argument(reader)?),
+            "clear" => {
+                let selector = parse_optional_argument(reader)?;
+                let item = parse_chained_argument(reader, &selector)?;
+                let max_count = parse_chained_argument(reader, &item)?;
+
+                Command::Clear(selector, item, max_count)
+            }
+            "clone" => {
+                reader.expect_separator()?;
+
+                parse_clone(reader)?
+            }
+            "damage" => {
+                let target = parse_argument(reader)?;
+                let amount = parse_argument(reader)?;
+                let type_ = parse_optional_argument(reader)?;
+                let command_type = parse_chained_argument(reader, &type_)?;
+
+                Command::Damage(target, amount, type_, command_type)
+            }
+            "data" => Command::Data(parse_argument(reader)?),
+            "datapack" => Command::Datapack(parse_argument(reader)?),
+            "debug" => Command::Debug(parse_argument(reader)?),
+            "defaultgamemode" => Command::DefaultGamemode(parse_argument(reader)?),
+            "deop" => Command::Deop(parse_argument(reader)?),
+            "dialog" => Command::Dialog(parse_argument(reader)?),
+            "difficulty" => Command::Difficulty(parse_optional_argument(reader)?),
+            "effect" => Command::Effect(parse_argument(reader)?),
+            "enchant" => Command::Enchant(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "execute" => Command::Execute(parse_argument(reader)?),
+            "experience" | "xp" => Command::Experience(parse_argument(reader)?),
+            "fetchprofile" => Command::FetchProfile(parse_argument(reader)?),
+            "fill" => Command::Fill(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "fillbiome" => Command::FillBiome(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional(reader, |reader| {
+                    expect_literal(reader, "replace")?;
+                    parse_argument(reader)
+                })?,
+            ),
+            "forceload" => Command::Forceload(parse_argument(reader)?),
+            "function" => {
+                Command::Function(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "gamemode" => {
+                Command::Gamemode(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "gamerule" => {
+                reader.expect_separator()?;
+
+                Command::Gamerule(parse_word(reader)?, parse_optional_argument(reader)?)
+            }
+            "give" => Command::Give(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "help" => Command::Help(parse_optional(reader, parse_greedy)?),
+            "item" => {
+                reader.expect_separator()?;
+
+                parse_item(reader)?
+            }
+            "jfr" => {
+                reader.expect_separator()?;
+
+                Command::JFR(parse_start_stop(reader)?)
+            }
+            "kick" => Command::Kick(
+                parse_argument(reader)?,
+                parse_optional(reader, parse_greedy)?,
+            ),
+            "kill" => Command::Kill(parse_optional_argument(reader)?),
+            "list" => Command::List(parse_flag(reader, "uuids")?),
+            "locate" => Command::Locate(parse_argument(reader)?, parse_argument(reader)?),
+            "loot" => Command::Loot(parse_argument(reader)?, parse_argument(reader)?),
+            "me" => {
+                reader.expect_separator()?;
+
+                Command::Me(parse_greedy(reader)?)
+            }
+            "msg" | "tell" | "w" => {
+                let selector = parse_argument(reader)?;
+                reader.expect_separator()?;
+
+                Command::Message(selector, parse_greedy(reader)?)
+            }
+            "op" => Command::Op(parse_argument(reader)?),
+            "pardon" => Command::Pardon(parse_argument(reader)?),
+            "pardon-ip" => {
+                reader.expect_separator()?;
+
+                Command::PardonIp(parse_word(reader)?)
+            }
+            "particle" => Command::Particle(parse_argument(reader)?),
+            "perf" => {
+                reader.expect_separator()?;
+
+                Command::Perf(parse_start_stop(reader)?)
+            }
+            "place" => Command::Place(parse_argument(reader)?),
+            "playsound" => {
+                let sound = parse_argument(reader)?;
+                let source = parse_optional_argument(reader)?;
+                let selector = parse_chained_argument(reader, &source)?;
+                let pos = parse_chained_argument(reader, &selector)?;
+                let volume = parse_chained_argument(reader, &pos)?;
+                let pitch = parse_chained_argument(reader, &volume)?;
+                let minimum_volume = parse_chained_argument(reader, &pitch)?;
+
+                Command::Playsound(sound, source, selector, pos, volume, pitch, minimum_volume)
+            }
+            "publish" => {
+                let allow_commands = parse_optional_argument(reader)?;
+                let gamemode = parse_chained_argument(reader, &allow_commands)?;
+                let port = parse_chained_argument(reader, &gamemode)?;
+
+                Command::Publish(allow_commands, gamemode, port)
+            }
+            "random" => Command::Random(parse_argument(reader)?),
+            "recipe" => {
+                reader.expect_separator()?;
+                let literal_start = reader.cursor();
+                let give = match reader.read_literal()? {
+                    "give" => true,
+                    "take" => false,
+                    literal => return Err(reader.unknown_argument(literal_start, literal)),
+                };
+
+                Command::Recipe(give, parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "reload" => Command::Reload,
+            "return" => Command::Return(parse_argument(reader)?),
+            "ride" => Command::Ride(parse_argument(reader)?, parse_argument(reader)?),
+            "rotate" => Command::Rotate(parse_argument(reader)?, parse_argument(reader)?),
+            "save-all" => Command::SaveAll(parse_flag(reader, "flush")?),
+            "save-off" => Command::SaveOff,
+            "save-on" => Command::SaveOn,
+            "say" => {
+                reader.expect_separator()?;
+
+                Command::Say(parse_greedy(reader)?)
+            }
+            "schedule" => Command::Schedule(parse_argument(reader)?),
+            "scoreboard" => Command::Scoreboard(parse_argument(reader)?),
+            "seed" => Command::Seed,
+            "setblock" => Command::Setblock(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "setidletimeout" => Command::SetIdleTimeout(parse_argument(reader)?),
+            "setworldspawn" => {
+                let coordinates = parse_optional_argument(reader)?;
+                let angle = parse_chained_argument(reader, &coordinates)?;
+
+                Command::SetWorldSpawn(coordinates, angle)
+            }
+            "spawnpoint" => {
+                let selector = parse_optional_argument(reader)?;
+                let coordinates = parse_chained_argument(reader, &selector)?;
+                let angle = parse_chained_argument(reader, &coordinates)?;
+
+                Command::Spawnpoint(selector, coordinates, angle)
+            }
+            "spectate" => {
+                let target = parse_optional_argument(reader)?;
+                let player = parse_chained_argument(reader, &target)?;
+
+                Command::Spectate(target, player)
+            }
+            "spreadplayers" => parse_spreadplayers(reader)?,
+            "stop" => Command::Stop,
+            "stopsound" => {
+                let selector = parse_argument(reader)?;
+                let source = parse_optional_argument(reader)?;
+                let sound = parse_chained_argument(reader, &source)?;
+
+                Command::StopSound(selector, source, sound)
+            }
+            "stopwatch" => Command::Stopwatch(parse_argument(reader)?),
+            "summon" => {
+                let entity = parse_argument(reader)?;
+                let coordinates = parse_optional_argument(reader)?;
+                let nbt = parse_chained_argument(reader, &coordinates)?;
+
+                Command::Summon(entity, coordinates, nbt)
+            }
+            "tag" => Command::Tag(parse_argument(reader)?, parse_argument(reader)?),
+            "team" => Command::Team(parse_argument(reader)?),
+            "teammsg" | "tm" => {
+                reader.expect_separator()?;
+
+                Command::TeamMessage(parse_greedy(reader)?)
+            }
+            "teleport" | "tp" => Command::Teleport(parse_argument(reader)?),
+            "tellraw" => Command::Tellraw(parse_argument(reader)?, parse_argument(reader)?),
+            "test" => Command::Test(parse_argument(reader)?),
+            "tick" => Command::Tick(parse_argument(reader)?),
+            "time" => Command::Time(parse_argument(reader)?),
+            "title" => Command::Title(parse_argument(reader)?, parse_argument(reader)?),
+            "transfer" => {
+                reader.expect_separator()?;
+                let hostname = parse_word(reader)?;
+                let port = parse_optional_argument(reader)?;
+                let selector = parse_chained_argument(reader, &port)?;
+
+                Command::Transfer(hostname, port, selector)
+            }
+            "trigger" => {
+                reader.expect_separator()?;
+
+                Command::Trigger(parse_word(reader)?, parse_optional_argument(reader)?)
+            }
+            "version" => Command::Version,
+            "waypoint" => Command::Waypoint(parse_argument(reader)?),
+            "weather" => {
+                Command::Weather(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "whitelist" => Command::Whitelist(parse_argument(reader)?),
+            "worldborder" => Command::Worldborder(parse_argument(reader)?),
+            _ => {
+                return Err(ParseError::new(
+                    start,
+                    ParseErrorKind::UnknownCommand(name.to_string()),
+                ));
+            }
+        })
+    }
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseErrorKind;
+
+    fn assert_round_trip(input: &str) {
+        let command: Command = input
+            .parse()
+            .unwrap_or_else(|error| panic!("failed to parse {input:?}: {error}"));
+
+        assert_eq!(command.to_string(), input);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for input in [
+            "say Hello, world!",
+            "msg @a[tag=foo] hi there",
+            "give @s diamond_sword[damage=5] 2",
+            "teleport @s ~ ~1 ~",
+            "teleport @s @p",
+            "teleport @s ~ ~ ~ ~90 ~",
+            "execute rotated ~ 0 run rotate @s 90 ~-10",
+            "teleport 1 2 3",
+            "effect give @a speed infinite 1 true",
+            "scoreboard players operation @s foo += @a bar",
+            "scoreboard objectives add foo dummy",
+            "data modify storage foo:bar path.to[0] set value 1b",
+            "function foo:bar with storage foo:args",
+            "execute as @a at @s if score @s foo matches 1.. run say hi",
+            "execute store result score @s foo run data get entity @s Health",
+            "execute align xz positioned ~0.5 ~ ~0.5 unless block ~ ~-1 ~ air run kill @s",
+            "fill 0 0 0 10 10 10 stone replace dirt",
+            "clone 0 0 0 1 1 1 5 5 5 filtered stone move",
+            "particle dust{color:[1.0d,0.0d,0.0d],scale:1.0d} ~ ~ ~ 0 0 0 1 10 force",
+            "item replace entity @s weapon.mainhand with stick",
+            "item modify entity @s armor.head foo:bar",
+            "datapack enable foo",
+            "publish true survival 25565",
+            "weather rain 10s",
+            "return run function foo:bar",
+        ] {
+            assert_round_trip(input);
+        }
+    }
+
+    #[test]
+    fn test_reparse() {
+        for input in [
+            "advancement grant @s only minecraft:story/root",
+            "attribute @s minecraft:generic.armor base set 2",
+            "ban Steve griefing",
+            "bossbar add foo:bar \"Bar\"",
+            "clear @s minecraft:stone 1",
+            "clone 0 0 0 1 1 1 2 2 2 masked",
+            "damage @e[limit=1] 5 minecraft:magic by @s",
+            "data get block ~ ~ ~ Items[0]",
+            "datapack disable \"file/foo.zip\"",
+            "defaultgamemode creative",
+            "difficulty hard",
+            "effect clear @s minecraft:speed",
+            "enchant @s minecraft:sharpness 2",
+            "execute in minecraft:the_nether run say hi",
+            "execute summon minecraft:armor_stand run tag @s add new",
+            "execute if entity @e[type=minecraft:pig,distance=..5] run kill @s",
+            "experience add @s 10 levels",
+            "fill ~ ~ ~ ~1 ~1 ~1 minecraft:oak_planks hollow",
+            "fillbiome 0 0 0 15 15 15 minecraft:plains",
+            "forceload add 0 0 16 16",
+            "function #minecraft:tick",
+            "gamemode spectator @a",
+            "gamerule doDaylightCycle false",
+            "give @s minecraft:stone 64",
+            "help",
+            "item replace block ~ ~ ~ container.0 from entity @s weapon.mainhand",
+            "kick @a[name=Alex]",
+            "kill @e[type=!minecraft:player]",
+            "list uuids",
+            "locate structure minecraft:village_plains",
+            "loot give @s loot minecraft:chests/simple_dungeon",
+            "me waves",
+            "op Steve",
+            "particle minecraft:flame ~ ~1 ~",
+            "place feature minecraft:oak ~ ~ ~",
+            "playsound minecraft:entity.pig.ambient master @a ~ ~ ~ 1 1",
+            "publish",
+            "random value 1..6",
+            "recipe give @s *",
+            "reload",
+            "return 1",
+            "ride @s mount @e[type=minecraft:horse,limit=1,sort=nearest]",
+            "schedule function foo:bar 10t append",
+            "scoreboard players add @s foo 1",
+            "setblock ~ ~ ~ minecraft:chest[facing=north]{Lock:\"key\"} replace",
+            "setworldspawn 0 64 0",
+            "spawnpoint @s 0 64 0",
+            "spectate @e[limit=1] @s",
+            "spreadplayers 0 0 5 20 false @a",
+            "stopsound @a master minecraft:music.game",
+            "summon minecraft:armor_stand ~ ~ ~ {Invisible:1b}",
+            "tag @s remove foo",
+            "team join red @s",
+            "teleport @s 0 64 0 facing entity @p eyes",
+            "tellraw @a {\"text\":\"hi\"}",
+            "time set day",
+            "title @a title \"Hello\"",
+            "trigger foo set 1",
+            "weather clear",
+            "whitelist add Steve",
+            "worldborder set 100 10",
+        ] {
+            let command: Command = input
+                .parse()
+                .unwrap_or_else(|error| panic!("failed to parse {input:?}: {error}"));
+            let reparsed: Command = command
+                .to_string()
+                .parse()
+                .unwrap_or_else(|error| panic!("failed to reparse {command}: {error}"));
+
+            assert_eq!(reparsed, command, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "frobnicate @s".parse::<Command>().unwrap_err();
+        assert_eq!(error.cursor, 0);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::UnknownCommand("frobnicate".to_string())
+        );
+
+        let error = "kill @s extra".parse::<Command>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::TrailingData);
+        assert_eq!(error.cursor, 7);
+    }
+}
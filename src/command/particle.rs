@@ -1,11 +1,17 @@
 use crate::command::enums::particle_display_type::ParticleDisplayType;
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{
+    Parse, ParseError, StringReader, is_allowed_in_resource_location, parse_argument,
+    parse_chained_argument, parse_optional_argument,
+};
+use crate::snbt::parse_snbt_compound;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParticleCommand {
     Regular(String, Option<Coordinates>),
     Extra(
@@ -47,3 +53,50 @@ impl Display for ParticleCommand {
         }
     }
 }
+
+/// Reads a particle id along with its inline options, e.g. `dust{color:[1,0,0],scale:1}`.
+fn parse_particle_name(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    let start = reader.cursor();
+
+    if reader
+        .read_while(is_allowed_in_resource_location)
+        .is_empty()
+    {
+        return Err(reader.expected("particle"));
+    }
+
+    if reader.peek() == Some('{') {
+        parse_snbt_compound(reader)?;
+    }
+
+    Ok(reader.input()[start..reader.cursor()].to_string())
+}
+
+impl Parse for ParticleCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let name = parse_particle_name(reader)?;
+        let Some(pos) = parse_optional_argument(reader)? else {
+            return Ok(ParticleCommand::Regular(name, None));
+        };
+
+        if !reader.has_argument() {
+            return Ok(ParticleCommand::Regular(name, Some(pos)));
+        }
+
+        let delta = parse_argument(reader)?;
+        let speed = parse_argument(reader)?;
+        let count = parse_argument(reader)?;
+        let display_type = parse_optional_argument(reader)?;
+        let viewers = parse_chained_argument(reader, &display_type)?;
+
+        Ok(ParticleCommand::Extra(
+            name,
+            pos,
+            delta,
+            speed,
+            count,
+            display_type,
+            viewers,
+        ))
+    }
+}
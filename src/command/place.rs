@@ -1,17 +1,38 @@
 use crate::command::enums::template_mirror::TemplateMirror;
 use crate::command::enums::template_rotation::TemplateRotation;
 use crate::coordinate::Coordinates;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_chained_argument, parse_optional,
+    parse_optional_argument,
+};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlaceCommand {
-    Feature(ResourceLocation, Option<Coordinates>),
-    Jigsaw(ResourceLocation, ResourceLocation, i32, Option<Coordinates>),
-    Structure(ResourceLocation, Option<Coordinates>),
+    Feature(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<Coordinates>,
+    ),
+    Jigsaw(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        i32,
+        Option<Coordinates>,
+    ),
+    Structure(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<Coordinates>,
+    ),
     Template(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
         Option<Coordinates>,
         Option<TemplateRotation>,
@@ -70,8 +91,8 @@ impl Display for PlaceCommand {
                                 if let Some(seed) = seed {
                                     write!(f, " {}", seed)?;
 
-                                    if let Some(strict) = strict {
-                                        write!(f, " {}", strict)?;
+                                    if *strict == Some(true) {
+                                        f.write_str(" strict")?;
                                     }
                                 }
                             }
@@ -84,3 +105,50 @@ impl Display for PlaceCommand {
         }
     }
 }
+
+fn parse_strict(reader: &mut StringReader<'_>) -> Result<bool, ParseError> {
+    if reader.peek_str("strict") {
+        reader.read_literal()?;
+
+        return Ok(true);
+    }
+
+    reader.read_bool()
+}
+
+impl Parse for PlaceCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "feature" => {
+                PlaceCommand::Feature(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "jigsaw" => PlaceCommand::Jigsaw(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "structure" => {
+                PlaceCommand::Structure(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "template" => {
+                let template = parse_argument(reader)?;
+                let pos = parse_optional_argument(reader)?;
+                let rotation = parse_chained_argument(reader, &pos)?;
+                let mirror = parse_chained_argument(reader, &rotation)?;
+                let integrity = parse_chained_argument(reader, &mirror)?;
+                let seed = parse_chained_argument(reader, &integrity)?;
+                let strict = match seed {
+                    Some(_) => parse_optional(reader, parse_strict)?,
+                    None => None,
+                };
+
+                PlaceCommand::Template(template, pos, rotation, mirror, integrity, seed, strict)
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,13 +1,21 @@
 use crate::command::enums::random_type::RandomType;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_chained_argument,
+    parse_optional_argument,
+};
 use crate::range::IntegerRange;
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RandomResetType {
     All,
-    Sequence(ResourceLocation),
+    Sequence(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for RandomResetType {
@@ -19,9 +27,15 @@ impl Display for RandomResetType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RandomCommand {
-    ValueRoll(RandomType, IntegerRange, Option<ResourceLocation>),
+    ValueRoll(
+        RandomType,
+        IntegerRange,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        Option<ResourceLocation>,
+    ),
     Reset(RandomResetType, Option<i32>, Option<bool>, Option<bool>),
 }
 
@@ -57,3 +71,39 @@ impl Display for RandomCommand {
         }
     }
 }
+
+impl Parse for RandomResetType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.eat('*') {
+            return Ok(RandomResetType::All);
+        }
+
+        ResourceLocation::parse(reader).map(RandomResetType::Sequence)
+    }
+}
+
+impl Parse for RandomCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if !reader.peek_str("reset") {
+            return Ok(RandomCommand::ValueRoll(
+                RandomType::parse(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ));
+        }
+
+        reader.read_literal()?;
+
+        let reset_type = parse_argument(reader)?;
+        let seed = parse_optional_argument(reader)?;
+        let include_world_seed = parse_chained_argument(reader, &seed)?;
+        let include_sequence_id = parse_chained_argument(reader, &include_world_seed)?;
+
+        Ok(RandomCommand::Reset(
+            reset_type,
+            seed,
+            include_world_seed,
+            include_sequence_id,
+        ))
+    }
+}
@@ -1,11 +1,16 @@
+use crate::parser::{Parse, ParseError, StringReader};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RecipeType {
     All,
-    Recipe(ResourceLocation),
+    Recipe(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for RecipeType {
@@ -16,3 +21,13 @@ impl Display for RecipeType {
         }
     }
 }
+
+impl Parse for RecipeType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.eat('*') {
+            return Ok(RecipeType::All);
+        }
+
+        ResourceLocation::parse(reader).map(RecipeType::Recipe)
+    }
+}
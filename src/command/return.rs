@@ -1,8 +1,10 @@
 use crate::command::Command;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReturnCommand {
     Value(i32),
     Fail,
@@ -18,3 +20,21 @@ impl Display for ReturnCommand {
         }
     }
 }
+
+impl Parse for ReturnCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("fail") {
+            reader.read_literal()?;
+
+            return Ok(ReturnCommand::Fail);
+        }
+
+        if reader.peek_str("run") {
+            reader.read_literal()?;
+
+            return Ok(ReturnCommand::Run(parse_argument(reader)?));
+        }
+
+        reader.read_int().map(ReturnCommand::Value)
+    }
+}
@@ -1,8 +1,10 @@
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RideCommand {
     Mount(EntitySelector),
     Dismount,
@@ -16,3 +18,16 @@ impl Display for RideCommand {
         }
     }
 }
+
+impl Parse for RideCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "mount" => RideCommand::Mount(parse_argument(reader)?),
+            "dismount" => RideCommand::Dismount,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
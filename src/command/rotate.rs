@@ -1,11 +1,13 @@
 use crate::command::enums::entity_anchor::EntityAnchor;
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
-use crate::rotation::Rotation;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
+use crate::rotation::RelativeRotation;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FacingRotateCommand {
     Coordinates(Coordinates),
     Entity(EntitySelector, Option<EntityAnchor>),
@@ -28,9 +30,10 @@ impl Display for FacingRotateCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RotateCommand {
-    Rotation(Rotation),
+    Rotation(RelativeRotation),
     Facing(FacingRotateCommand),
 }
 
@@ -44,3 +47,30 @@ impl Display for RotateCommand {
         }
     }
 }
+
+impl Parse for FacingRotateCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("entity") {
+            reader.read_literal()?;
+
+            return Ok(FacingRotateCommand::Entity(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ));
+        }
+
+        Coordinates::parse(reader).map(FacingRotateCommand::Coordinates)
+    }
+}
+
+impl Parse for RotateCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("facing") {
+            reader.read_literal()?;
+
+            return Ok(RotateCommand::Facing(parse_argument(reader)?));
+        }
+
+        RelativeRotation::parse(reader).map(RotateCommand::Rotation)
+    }
+}
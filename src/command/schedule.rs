@@ -1,13 +1,23 @@
 use crate::command::enums::schedule_mode::ScheduleMode;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::resource_location::ResourceLocation;
 use crate::time::Time;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScheduleCommand {
-    Function(ResourceLocation, Time, Option<ScheduleMode>),
-    Clear(ResourceLocation),
+    Function(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Time,
+        Option<ScheduleMode>,
+    ),
+    Clear(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for ScheduleCommand {
@@ -26,3 +36,20 @@ impl Display for ScheduleCommand {
         }
     }
 }
+
+impl Parse for ScheduleCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "function" => ScheduleCommand::Function(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "clear" => ScheduleCommand::Clear(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -2,15 +2,20 @@ use crate::command::PlayerScore;
 use crate::command::enums::score_operation_operator::ScoreOperationOperator;
 use crate::command::enums::scoreboard_render_type::ScoreboardRenderType;
 use crate::entity_selector::EntitySelector;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_optional, parse_optional_argument,
+    parse_word,
+};
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreboardNumberFormat {
     Blank,
-    Fixed(SNBT),
-    Styled(SNBT),
+    Fixed(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
+    Styled(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
 }
 
 impl Display for ScoreboardNumberFormat {
@@ -23,10 +28,11 @@ impl Display for ScoreboardNumberFormat {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreboardModification {
     DisplayAutoUpdate(bool),
-    DisplayName(SNBT),
+    DisplayName(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
     NumberFormat(Option<ScoreboardNumberFormat>),
     RenderType(ScoreboardRenderType),
 }
@@ -56,10 +62,15 @@ impl Display for ScoreboardModification {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectivesScoreboardCommand {
     List,
-    Add(String, String, Option<SNBT>),
+    Add(
+        String,
+        String,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>,
+    ),
     Remove(String),
     SetDisplay(String, Option<String>),
     Modify(String, ScoreboardModification),
@@ -97,9 +108,13 @@ impl Display for ObjectivesScoreboardCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlayersDisplayScoreboardCommand {
-    Name(PlayerScore, Option<SNBT>),
+    Name(
+        PlayerScore,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>,
+    ),
     NumberFormat(PlayerScore, Option<ScoreboardNumberFormat>),
 }
 
@@ -128,7 +143,8 @@ impl Display for PlayersDisplayScoreboardCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlayersScoreboardCommand {
     List(Option<EntitySelector>),
     Get(PlayerScore),
@@ -187,7 +203,8 @@ impl Display for PlayersScoreboardCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScoreboardCommand {
     Objectives(ObjectivesScoreboardCommand),
     Players(PlayersScoreboardCommand),
@@ -201,3 +218,138 @@ impl Display for ScoreboardCommand {
         }
     }
 }
+
+impl Parse for ScoreboardNumberFormat {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "blank" => ScoreboardNumberFormat::Blank,
+            "fixed" => ScoreboardNumberFormat::Fixed(parse_argument(reader)?),
+            "styled" => ScoreboardNumberFormat::Styled(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for ScoreboardModification {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "displayautoupdate" => {
+                ScoreboardModification::DisplayAutoUpdate(parse_argument(reader)?)
+            }
+            "displayname" => ScoreboardModification::DisplayName(parse_argument(reader)?),
+            "numberformat" => {
+                ScoreboardModification::NumberFormat(parse_optional_argument(reader)?)
+            }
+            "rendertype" => ScoreboardModification::RenderType(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for ObjectivesScoreboardCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "list" => ObjectivesScoreboardCommand::List,
+            "add" => {
+                reader.expect_separator()?;
+                let name = parse_word(reader)?;
+                reader.expect_separator()?;
+                let criterion = parse_word(reader)?;
+
+                ObjectivesScoreboardCommand::Add(name, criterion, parse_optional_argument(reader)?)
+            }
+            "remove" => {
+                reader.expect_separator()?;
+
+                ObjectivesScoreboardCommand::Remove(parse_word(reader)?)
+            }
+            "setdisplay" => {
+                reader.expect_separator()?;
+                let slot = parse_word(reader)?;
+
+                ObjectivesScoreboardCommand::SetDisplay(slot, parse_optional(reader, parse_word)?)
+            }
+            "modify" => {
+                reader.expect_separator()?;
+                let name = parse_word(reader)?;
+
+                ObjectivesScoreboardCommand::Modify(name, parse_argument(reader)?)
+            }
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for PlayersDisplayScoreboardCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "name" => PlayersDisplayScoreboardCommand::Name(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            "numberformat" => PlayersDisplayScoreboardCommand::NumberFormat(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for PlayersScoreboardCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "list" => PlayersScoreboardCommand::List(parse_optional_argument(reader)?),
+            "get" => PlayersScoreboardCommand::Get(parse_argument(reader)?),
+            "set" => {
+                PlayersScoreboardCommand::Set(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "add" => {
+                PlayersScoreboardCommand::Add(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "remove" => {
+                PlayersScoreboardCommand::Remove(parse_argument(reader)?, parse_argument(reader)?)
+            }
+            "reset" => PlayersScoreboardCommand::Reset(
+                parse_argument(reader)?,
+                parse_optional(reader, parse_word)?,
+            ),
+            "enable" => PlayersScoreboardCommand::Enable(parse_argument(reader)?),
+            "operation" => PlayersScoreboardCommand::Operation(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            "display" => PlayersScoreboardCommand::Display(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for ScoreboardCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "objectives" => ScoreboardCommand::Objectives(parse_argument(reader)?),
+            "players" => ScoreboardCommand::Players(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
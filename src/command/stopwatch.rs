@@ -1,14 +1,29 @@
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StopwatchCommand {
-    Create(ResourceLocation),
-    Query(ResourceLocation, Option<NotNan<f32>>),
-    Restart(ResourceLocation),
-    Remove(ResourceLocation),
+    Create(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Query(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<NotNan<f32>>,
+    ),
+    Restart(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Remove(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
 }
 
 impl Display for StopwatchCommand {
@@ -35,3 +50,20 @@ impl Display for StopwatchCommand {
         }
     }
 }
+
+impl Parse for StopwatchCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "create" => StopwatchCommand::Create(parse_argument(reader)?),
+            "query" => {
+                StopwatchCommand::Query(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "restart" => StopwatchCommand::Restart(parse_argument(reader)?),
+            "remove" => StopwatchCommand::Remove(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
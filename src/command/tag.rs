@@ -1,7 +1,9 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_word};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TagCommand {
     Add(String),
     Remove(String),
@@ -17,3 +19,25 @@ impl Display for TagCommand {
         }
     }
 }
+
+impl Parse for TagCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => {
+                reader.expect_separator()?;
+
+                TagCommand::Add(parse_word(reader)?)
+            }
+            "remove" => {
+                reader.expect_separator()?;
+
+                TagCommand::Remove(parse_word(reader)?)
+            }
+            "list" => TagCommand::List,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -2,21 +2,26 @@ use crate::command::enums::team_collision_rule::TeamCollisionRule;
 use crate::command::enums::team_color::TeamColor;
 use crate::command::enums::team_visibility::TeamVisibility;
 use crate::entity_selector::EntitySelector;
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_optional, parse_optional_argument,
+    parse_word,
+};
 use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeamOption {
-    DisplayName(SNBT),
+    DisplayName(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
     Color(TeamColor),
     FriendlyFire(bool),
     SeeFriendlyInvisibles(bool),
     NametagVisibility(TeamVisibility),
     DeathMessageVisibility(TeamVisibility),
     CollisionRule(TeamCollisionRule),
-    Prefix(SNBT),
-    Suffix(SNBT),
+    Prefix(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
+    Suffix(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT),
 }
 
 impl Display for TeamOption {
@@ -53,10 +58,14 @@ impl Display for TeamOption {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeamCommand {
     List(Option<String>),
-    Add(String, Option<SNBT>),
+    Add(
+        String,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>,
+    ),
     Remove(String),
     Empty(String),
     Join(String, Option<EntitySelector>),
@@ -109,3 +118,50 @@ impl Display for TeamCommand {
         }
     }
 }
+
+impl Parse for TeamOption {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "displayName" => TeamOption::DisplayName(parse_argument(reader)?),
+            "color" => TeamOption::Color(parse_argument(reader)?),
+            "friendlyFire" => TeamOption::FriendlyFire(parse_argument(reader)?),
+            "seeFriendlyInvisibles" => TeamOption::SeeFriendlyInvisibles(parse_argument(reader)?),
+            "nametagVisibility" => TeamOption::NametagVisibility(parse_argument(reader)?),
+            "deathMessageVisibility" => TeamOption::DeathMessageVisibility(parse_argument(reader)?),
+            "collisionRule" => TeamOption::CollisionRule(parse_argument(reader)?),
+            "prefix" => TeamOption::Prefix(parse_argument(reader)?),
+            "suffix" => TeamOption::Suffix(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for TeamCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        if literal == "list" {
+            return Ok(TeamCommand::List(parse_optional(reader, parse_word)?));
+        }
+
+        if literal == "leave" {
+            return Ok(TeamCommand::Leave(parse_argument(reader)?));
+        }
+
+        reader.expect_separator()?;
+        let name = parse_word(reader)?;
+
+        Ok(match literal {
+            "add" => TeamCommand::Add(name, parse_optional_argument(reader)?),
+            "remove" => TeamCommand::Remove(name),
+            "empty" => TeamCommand::Empty(name),
+            "join" => TeamCommand::Join(name, parse_optional_argument(reader)?),
+            "modify" => TeamCommand::Modify(name, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,11 +1,13 @@
 use crate::command::enums::entity_anchor::EntityAnchor;
 use crate::coordinate::Coordinates;
 use crate::entity_selector::EntitySelector;
-use crate::rotation::Rotation;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
+use crate::rotation::RelativeRotation;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeleportFacing {
     Position(Coordinates),
     Entity(EntitySelector, Option<EntityAnchor>),
@@ -28,9 +30,10 @@ impl Display for TeleportFacing {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeleportCoordinatesType {
-    Rotation(Rotation),
+    Rotation(RelativeRotation),
     Facing(TeleportFacing),
 }
 
@@ -45,7 +48,8 @@ impl Display for TeleportCoordinatesType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TargetTeleportCommand {
     Coordinates(Coordinates, Option<TeleportCoordinatesType>),
     Entity(EntitySelector),
@@ -68,7 +72,8 @@ impl Display for TargetTeleportCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TeleportCommand {
     Coordinates(Coordinates),
     Entity(EntitySelector, Option<TargetTeleportCommand>),
@@ -90,3 +95,63 @@ impl Display for TeleportCommand {
         }
     }
 }
+
+/// Returns `true` if the next argument starts like a position rather than an entity.
+fn is_coordinates_next(reader: &StringReader<'_>) -> bool {
+    reader
+        .peek()
+        .is_some_and(|c| matches!(c, '~' | '^' | '-' | '+' | '.') || c.is_ascii_digit())
+}
+
+impl Parse for TeleportFacing {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("entity") {
+            reader.read_literal()?;
+
+            return Ok(TeleportFacing::Entity(
+                parse_argument(reader)?,
+                parse_optional_argument(reader)?,
+            ));
+        }
+
+        Coordinates::parse(reader).map(TeleportFacing::Position)
+    }
+}
+
+impl Parse for TeleportCoordinatesType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("facing") {
+            reader.read_literal()?;
+
+            return Ok(TeleportCoordinatesType::Facing(parse_argument(reader)?));
+        }
+
+        RelativeRotation::parse(reader).map(TeleportCoordinatesType::Rotation)
+    }
+}
+
+impl Parse for TargetTeleportCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if is_coordinates_next(reader) {
+            return Ok(TargetTeleportCommand::Coordinates(
+                Coordinates::parse(reader)?,
+                parse_optional_argument(reader)?,
+            ));
+        }
+
+        EntitySelector::parse(reader).map(TargetTeleportCommand::Entity)
+    }
+}
+
+impl Parse for TeleportCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if is_coordinates_next(reader) {
+            return Coordinates::parse(reader).map(TeleportCommand::Coordinates);
+        }
+
+        Ok(TeleportCommand::Entity(
+            EntitySelector::parse(reader)?,
+            parse_optional_argument(reader)?,
+        ))
+    }
+}
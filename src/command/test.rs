@@ -1,8 +1,13 @@
+use crate::parser::{
+    Parse, ParseError, StringReader, parse_argument, parse_chained_argument, parse_optional,
+    parse_optional_argument, parse_word,
+};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RunfailedTestCommand {
     NumberOfTimes(Option<i32>, Option<bool>, Option<i32>, Option<i32>),
     OnlyRequiredTest(Option<bool>, Option<i32>),
@@ -50,18 +55,28 @@ impl Display for RunfailedTestCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TestCommand {
     ClearAll(Option<i32>),
     ClearThat,
     ClearThese,
-    Create(ResourceLocation, Option<i32>, Option<(i32, i32)>),
-    Locate(ResourceLocation),
+    Create(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<i32>,
+        Option<(i32, i32)>,
+    ),
+    Locate(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Pos(Option<String>),
     ResetClosest,
     ResetThat,
     ResetThese,
     Run(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
         ResourceLocation,
         Option<i32>,
         Option<bool>,
@@ -71,11 +86,21 @@ pub enum TestCommand {
     RunClosest(Option<i32>, Option<bool>),
     RunThat(Option<i32>, Option<bool>),
     RunThese(Option<i32>, Option<bool>),
-    RunMultiple(ResourceLocation, Option<i32>),
+    RunMultiple(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        Option<i32>,
+    ),
     RunFailed(RunfailedTestCommand),
     Stop,
-    Verify(ResourceLocation),
-    Export(ResourceLocation),
+    Verify(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Export(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     ExportClosest,
     ExportThat,
     ExportThese,
@@ -196,7 +221,7 @@ impl Display for TestCommand {
 
                 Ok(())
             }
-            TestCommand::RunFailed(command) => write!(f, "runfailed {}", command),
+            TestCommand::RunFailed(command) => write!(f, "runfailed{}", command),
             TestCommand::Stop => f.write_str("stop"),
             TestCommand::Verify(location) => write!(f, "verify {}", location),
             TestCommand::Export(location) => write!(f, "export {}", location),
@@ -206,3 +231,114 @@ impl Display for TestCommand {
         }
     }
 }
+
+/// Parses `[a] [b]`, where `b` is only read when `a` is present.
+fn parse_optional_pair<A: Parse, B: Parse>(
+    reader: &mut StringReader<'_>,
+) -> Result<(Option<A>, Option<B>), ParseError> {
+    let a = parse_optional_argument(reader)?;
+    let b = parse_chained_argument(reader, &a)?;
+
+    Ok((a, b))
+}
+
+impl Parse for RunfailedTestCommand {
+    /// An empty argument list is ambiguous and parses as [`RunfailedTestCommand::NumberOfTimes`].
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let remaining = reader.remaining().trim_start_matches(' ');
+
+        if remaining.starts_with("true") || remaining.starts_with("false") {
+            let (only_required_tests, number_of_times) = parse_optional_pair(reader)?;
+
+            return Ok(RunfailedTestCommand::OnlyRequiredTest(
+                only_required_tests,
+                number_of_times,
+            ));
+        }
+
+        let (number_of_times, until_failed) = parse_optional_pair(reader)?;
+        let (rotation_steps, tests_per_row) = match until_failed {
+            Some(_) => parse_optional_pair(reader)?,
+            None => (None, None),
+        };
+
+        Ok(RunfailedTestCommand::NumberOfTimes(
+            number_of_times,
+            until_failed,
+            rotation_steps,
+            tests_per_row,
+        ))
+    }
+}
+
+impl Parse for TestCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "clearall" => TestCommand::ClearAll(parse_optional_argument(reader)?),
+            "clearthat" => TestCommand::ClearThat,
+            "clearthese" => TestCommand::ClearThese,
+            "create" => {
+                let location = parse_argument(reader)?;
+                let width = parse_optional_argument(reader)?;
+                let height_depth = match width {
+                    Some(_) => parse_optional(reader, |reader| {
+                        Ok((i32::parse(reader)?, parse_argument(reader)?))
+                    })?,
+                    None => None,
+                };
+
+                TestCommand::Create(location, width, height_depth)
+            }
+            "locate" => TestCommand::Locate(parse_argument(reader)?),
+            "pos" => TestCommand::Pos(parse_optional(reader, parse_word)?),
+            "resetclosest" => TestCommand::ResetClosest,
+            "resetthat" => TestCommand::ResetThat,
+            "resetthese" => TestCommand::ResetThese,
+            "run" => {
+                let location = parse_argument(reader)?;
+                let (number_of_times, until_failed) = parse_optional_pair(reader)?;
+                let (rotation_steps, tests_per_row) = match until_failed {
+                    Some(_) => parse_optional_pair(reader)?,
+                    None => (None, None),
+                };
+
+                TestCommand::Run(
+                    location,
+                    number_of_times,
+                    until_failed,
+                    rotation_steps,
+                    tests_per_row,
+                )
+            }
+            "runclosest" => {
+                let (number_of_times, until_failed) = parse_optional_pair(reader)?;
+
+                TestCommand::RunClosest(number_of_times, until_failed)
+            }
+            "runthat" => {
+                let (number_of_times, until_failed) = parse_optional_pair(reader)?;
+
+                TestCommand::RunThat(number_of_times, until_failed)
+            }
+            "runthese" => {
+                let (number_of_times, until_failed) = parse_optional_pair(reader)?;
+
+                TestCommand::RunThese(number_of_times, until_failed)
+            }
+            "runmultiple" => {
+                TestCommand::RunMultiple(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "runfailed" => TestCommand::RunFailed(RunfailedTestCommand::parse(reader)?),
+            "stop" => TestCommand::Stop,
+            "verify" => TestCommand::Verify(parse_argument(reader)?),
+            "export" => TestCommand::Export(parse_argument(reader)?),
+            "exportclosest" => TestCommand::ExportClosest,
+            "exportthat" => TestCommand::ExportThat,
+            "exportthese" => TestCommand::ExportThese,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
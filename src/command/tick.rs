@@ -1,9 +1,11 @@
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::time::Time;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdvanceTimeTickCommand {
     Time(Option<Time>),
     Stop,
@@ -24,7 +26,8 @@ impl Display for AdvanceTimeTickCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TickCommand {
     Query,
     Rate(NotNan<f32>),
@@ -46,3 +49,33 @@ impl Display for TickCommand {
         }
     }
 }
+
+impl Parse for AdvanceTimeTickCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str(" stop") {
+            reader.skip();
+            reader.read_literal()?;
+
+            return Ok(AdvanceTimeTickCommand::Stop);
+        }
+
+        parse_optional_argument(reader).map(AdvanceTimeTickCommand::Time)
+    }
+}
+
+impl Parse for TickCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "query" => TickCommand::Query,
+            "rate" => TickCommand::Rate(parse_argument(reader)?),
+            "freeze" => TickCommand::Freeze,
+            "unfreeze" => TickCommand::Unfreeze,
+            "step" => TickCommand::Step(AdvanceTimeTickCommand::parse(reader)?),
+            "sprint" => TickCommand::Sprint(AdvanceTimeTickCommand::parse(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,10 +1,12 @@
 use crate::command::enums::time_of_day::TimeOfDay;
 use crate::command::enums::time_query_type::TimeQueryType;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
 use crate::time::Time;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeSetType {
     Time(Time),
     TimeOfDay(TimeOfDay),
@@ -19,7 +21,8 @@ impl Display for TimeSetType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeCommand {
     Add(Time),
     Query(TimeQueryType),
@@ -35,3 +38,27 @@ impl Display for TimeCommand {
         }
     }
 }
+
+impl Parse for TimeSetType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            return TimeOfDay::parse(reader).map(TimeSetType::TimeOfDay);
+        }
+
+        Time::parse(reader).map(TimeSetType::Time)
+    }
+}
+
+impl Parse for TimeCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => TimeCommand::Add(parse_argument(reader)?),
+            "query" => TimeCommand::Query(parse_argument(reader)?),
+            "set" => TimeCommand::Set(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
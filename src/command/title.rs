@@ -1,14 +1,19 @@
 use crate::command::enums::title_type::TitleType;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
 use crate::snbt::SNBT;
 use crate::time::Time;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TitleCommand {
     Clear,
     Reset,
-    Title(TitleType, SNBT),
+    Title(
+        TitleType,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
     Times(Time, Time, Time),
 }
 
@@ -24,3 +29,25 @@ impl Display for TitleCommand {
         }
     }
 }
+
+impl Parse for TitleCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "clear" => TitleCommand::Clear,
+            "reset" => TitleCommand::Reset,
+            "times" => TitleCommand::Times(
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+                parse_argument(reader)?,
+            ),
+            _ => {
+                reader.set_cursor(start);
+
+                TitleCommand::Title(TitleType::parse(reader)?, parse_argument(reader)?)
+            }
+        })
+    }
+}
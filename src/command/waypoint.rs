@@ -1,10 +1,12 @@
 use crate::command::enums::team_color_with_reset::TeamColorWithReset;
 use crate::entity_selector::EntitySelector;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_word};
 use crate::resource_location::ResourceLocation;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WaypointColor {
     Color(TeamColorWithReset),
     Hex(String),
@@ -21,9 +23,13 @@ impl Display for WaypointColor {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WaypointStyleModification {
-    Set(ResourceLocation),
+    Set(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Reset,
 }
 
@@ -36,7 +42,8 @@ impl Display for WaypointStyleModification {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WaypointModification {
     Color(WaypointColor),
     Style(WaypointStyleModification),
@@ -51,7 +58,8 @@ impl Display for WaypointModification {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WaypointCommand {
     List,
     Modify(EntitySelector, WaypointModification),
@@ -67,3 +75,58 @@ impl Display for WaypointCommand {
         }
     }
 }
+
+impl Parse for WaypointColor {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek_str("hex") {
+            reader.read_literal()?;
+            reader.expect_separator()?;
+
+            return parse_word(reader).map(WaypointColor::Hex);
+        }
+
+        Ok(match TeamColorWithReset::parse(reader)? {
+            TeamColorWithReset::Reset => WaypointColor::Reset,
+            color => WaypointColor::Color(color),
+        })
+    }
+}
+
+impl Parse for WaypointStyleModification {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "set" => WaypointStyleModification::Set(parse_argument(reader)?),
+            "reset" => WaypointStyleModification::Reset,
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for WaypointModification {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "color" => WaypointModification::Color(parse_argument(reader)?),
+            "style" => WaypointModification::Style(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for WaypointCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "list" => WaypointCommand::List,
+            "modify" => WaypointCommand::Modify(parse_argument(reader)?, parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
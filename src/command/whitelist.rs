@@ -1,8 +1,10 @@
 use crate::entity_selector::EntitySelector;
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WhitelistCommand {
     Add(EntitySelector),
     List,
@@ -24,3 +26,20 @@ impl Display for WhitelistCommand {
         }
     }
 }
+
+impl Parse for WhitelistCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => WhitelistCommand::Add(parse_argument(reader)?),
+            "list" => WhitelistCommand::List,
+            "off" => WhitelistCommand::Off,
+            "on" => WhitelistCommand::On,
+            "reload" => WhitelistCommand::Reload,
+            "remove" => WhitelistCommand::Remove(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
@@ -1,10 +1,12 @@
 use crate::column_position::ColumnPosition;
+use crate::parser::{Parse, ParseError, StringReader, parse_argument, parse_optional_argument};
 use crate::time::Time;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DamageWorldborderCommand {
     Amount(NotNan<f32>),
     Buffer(NotNan<f32>),
@@ -19,7 +21,8 @@ impl Display for DamageWorldborderCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WarningWorldborderCommand {
     Distance(i32),
     Time(Time),
@@ -34,7 +37,8 @@ impl Display for WarningWorldborderCommand {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WorldborderCommand {
     Add(NotNan<f64>, Option<Time>),
     Center(ColumnPosition),
@@ -74,3 +78,50 @@ impl Display for WorldborderCommand {
         }
     }
 }
+
+impl Parse for DamageWorldborderCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "amount" => DamageWorldborderCommand::Amount(parse_argument(reader)?),
+            "buffer" => DamageWorldborderCommand::Buffer(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for WarningWorldborderCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "distance" => WarningWorldborderCommand::Distance(parse_argument(reader)?),
+            "time" => WarningWorldborderCommand::Time(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
+
+impl Parse for WorldborderCommand {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let literal = reader.read_literal()?;
+
+        Ok(match literal {
+            "add" => {
+                WorldborderCommand::Add(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "center" => WorldborderCommand::Center(parse_argument(reader)?),
+            "damage" => WorldborderCommand::Damage(parse_argument(reader)?),
+            "get" => WorldborderCommand::Get,
+            "set" => {
+                WorldborderCommand::Set(parse_argument(reader)?, parse_optional_argument(reader)?)
+            }
+            "warning" => WorldborderCommand::Warning(parse_argument(reader)?),
+            _ => return Err(reader.unknown_argument(start, literal)),
+        })
+    }
+}
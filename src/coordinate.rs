@@ -1,8 +1,13 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorldCoordinate {
     pub relative: bool,
     pub value: Option<NotNan<f32>>,
@@ -12,11 +17,18 @@ impl WorldCoordinate {
     #[inline]
     #[must_use]
     pub fn new(relative: bool, value: Option<NotNan<f32>>) -> Self {
+        Self::try_new(relative, value)
+            .expect("A world coordinate must have a relative coordinate and/or have a value")
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn try_new(relative: bool, value: Option<NotNan<f32>>) -> Option<Self> {
         if !relative && value.is_none() {
-            panic!("A world coordinate must have a relative coordinate and/or have a value");
+            return None;
         }
 
-        Self { relative, value }
+        Some(Self { relative, value })
     }
 
     #[inline]
@@ -70,7 +82,11 @@ impl Display for WorldCoordinate {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[visit(hook = coordinates)]
 pub enum Coordinates {
     World(WorldCoordinate, WorldCoordinate, WorldCoordinate),
     Local(
@@ -144,3 +160,101 @@ impl Display for Coordinates {
         }
     }
 }
+
+fn parse_optional_float(reader: &mut StringReader<'_>) -> Result<Option<NotNan<f32>>, ParseError> {
+    if reader.peek().is_some_and(|c| !c.is_whitespace()) {
+        reader.read_float().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+impl Parse for WorldCoordinate {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        match reader.peek() {
+            Some('~') => {
+                reader.skip();
+
+                Ok(WorldCoordinate::relative_optional(parse_optional_float(
+                    reader,
+                )?))
+            }
+            Some('^') => Err(reader.error(ParseErrorKind::MixedCoordinates)),
+            _ => Ok(WorldCoordinate::absolute(reader.read_float()?)),
+        }
+    }
+}
+
+fn parse_local_coordinate(
+    reader: &mut StringReader<'_>,
+) -> Result<Option<NotNan<f32>>, ParseError> {
+    match reader.peek() {
+        Some('^') => reader.skip(),
+        Some(_) => return Err(reader.error(ParseErrorKind::MixedCoordinates)),
+        None => return Err(reader.error(ParseErrorKind::UnexpectedEndOfInput)),
+    }
+
+    parse_optional_float(reader)
+}
+
+impl Parse for Coordinates {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek() == Some('^') {
+            let x = parse_local_coordinate(reader)?;
+            reader.expect_separator()?;
+            let y = parse_local_coordinate(reader)?;
+            reader.expect_separator()?;
+            let z = parse_local_coordinate(reader)?;
+
+            return Ok(Coordinates::Local(x, y, z));
+        }
+
+        let x = WorldCoordinate::parse(reader)?;
+        reader.expect_separator()?;
+        let y = WorldCoordinate::parse(reader)?;
+        reader.expect_separator()?;
+        let z = WorldCoordinate::parse(reader)?;
+
+        Ok(Coordinates::World(x, y, z))
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let coordinates: Coordinates = "1 ~ ~-2.5".parse().unwrap();
+        assert_eq!(
+            coordinates,
+            Coordinates::new_world(
+                WorldCoordinate::absolute(NotNan::new(1.0).unwrap()),
+                WorldCoordinate::relative_zero(),
+                WorldCoordinate::relative(NotNan::new(-2.5).unwrap()),
+            )
+        );
+
+        let coordinates: Coordinates = "^ ^1 ^".parse().unwrap();
+        assert_eq!(coordinates.to_string(), "^ ^1 ^");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for input in ["~ ^ ~", "^ ~ ^", "^ ^ 1"] {
+            let error = input.parse::<Coordinates>().unwrap_err();
+            assert_eq!(error.kind, ParseErrorKind::MixedCoordinates, "{}", input);
+        }
+
+        assert!("1 2".parse::<Coordinates>().is_err());
+        assert!(WorldCoordinate::try_new(false, None).is_none());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::datapack::Datapack;
+use crate::datapack::output::MemoryOutput;
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::io::{self, Cursor, Read, Seek, Write};
+use std::path::{Component, Path};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+fn entry_name(path: &Path) -> io::Result<String> {
+    let segments = path
+        .components()
+        .map(|component| match component {
+            Component::Normal(segment) => segment.to_str().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid file name {:?}", segment),
+                )
+            }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid path {}", path.display()),
+            )),
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(segments.join("/"))
+}
+
+impl Datapack {
+    /// Writes the datapack as a zip archive.
+    ///
+    /// Entries are sorted by name and use a fixed timestamp, so the same datapack always
+    /// produces the same bytes.
+    pub fn write_zip(&self, writer: impl Write + Seek) -> io::Result<()> {
+        let mut output = MemoryOutput::default();
+        self.write_to(&mut output)?;
+
+        let entries = output
+            .files
+            .into_iter()
+            .map(|(path, content)| Ok((entry_name(&path)?, content)))
+            .collect::<io::Result<BTreeMap<_, _>>>()?;
+
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .unix_permissions(0o644);
+
+        let mut zip = ZipWriter::new(writer);
+
+        for (name, content) in entries {
+            zip.start_file(name, options)?;
+            zip.write_all(&content)?;
+        }
+
+        zip.finish()?;
+
+        Ok(())
+    }
+
+    pub fn read_zip(reader: impl Read + Seek) -> io::Result<Datapack> {
+        let mut zip = ZipArchive::new(reader)?;
+        let mut files = Vec::new();
+
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i)?;
+
+            if file.is_dir() {
+                continue;
+            }
+
+            let Some(path) = file.enclosed_name() else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid zip entry {}", file.name()),
+                ));
+            };
+
+            let segments = entry_name(&path)?.split('/').map(str::to_string).collect();
+
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+
+            files.push((segments, content));
+        }
+
+        files.sort();
+
+        Datapack::from_files(files)
+    }
+
+    pub fn to_zip(&self) -> io::Result<Vec<u8>> {
+        let mut cursor = Cursor::new(Vec::new());
+        self.write_zip(&mut cursor)?;
+
+        Ok(cursor.into_inner())
+    }
+
+    /// Returns the lowercase hex SHA-1 of [`Datapack::to_zip`], the form `server.properties`
+    /// expects.
+    pub fn zip_sha1(&self) -> io::Result<String> {
+        let digest = Sha1::digest(self.to_zip()?);
+
+        Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nonempty::nonempty;
+    use serde_json::Value;
+
+    fn datapack() -> Datapack {
+        let mut datapack = Datapack::new(48, Value::from("test"));
+
+        let namespace = datapack.get_namespace_mut("foo");
+        namespace.add_function(&nonempty!["main".to_string()], "say hi");
+        namespace.add_function(&nonempty!["a".to_string(), "b".to_string()], "say bye");
+
+        datapack
+    }
+
+    #[test]
+    fn test_zip() {
+        let bytes = datapack().to_zip().unwrap();
+        assert_eq!(bytes, datapack().to_zip().unwrap());
+        assert_eq!(datapack().zip_sha1().unwrap().len(), 40);
+
+        let read = Datapack::read_zip(Cursor::new(bytes)).unwrap();
+        assert_eq!(read.pack.pack.pack_format, Some(48));
+        assert_eq!(
+            read.namespaces["foo"].functions[&nonempty!["a".to_string(), "b".to_string()]]
+                .to_string(),
+            "say bye"
+        );
+    }
+
+    #[test]
+    fn test_zip_insertion_order() {
+        let mut reversed = Datapack::new(48, Value::from("test"));
+        reversed
+            .other_files
+            .insert(nonempty!["pack.png".to_string()], vec![0]);
+
+        let namespace = reversed.get_namespace_mut("foo");
+        namespace.add_function(&nonempty!["a".to_string(), "b".to_string()], "say bye");
+        namespace.add_function(&nonempty!["main".to_string()], "say hi");
+
+        let mut datapack = datapack();
+        datapack
+            .other_files
+            .insert(nonempty!["pack.png".to_string()], vec![0]);
+
+        assert_eq!(datapack.to_zip().unwrap(), reversed.to_zip().unwrap());
+    }
+
+    #[test]
+    fn test_read_zip_escaping_entry() {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut zip = ZipWriter::new(&mut cursor);
+        zip.start_file("pack.mcmeta", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(br#"{"pack":{"pack_format":48,"description":""}}"#)
+            .unwrap();
+        zip.start_file("../x", SimpleFileOptions::default())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let Err(error) = Datapack::read_zip(Cursor::new(cursor.into_inner())) else {
+            panic!("A zip entry outside of the datapack was read");
+        };
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Invalid zip entry ../x");
+    }
+}
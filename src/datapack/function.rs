@@ -0,0 +1,508 @@
+use crate::command::Command;
+use crate::has_macro::HasMacro;
+use crate::parser::{ParseError, parse_all};
+use crate::version::{GameVersion, VersionError};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const CONTINUATION_INDENT: &str = "    ";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum McFunctionParseErrorKind {
+    ContinuationAtEndOfFile,
+    LeadingSlash,
+    MacroWithoutVariables,
+    Command(ParseError),
+}
+
+impl Display for McFunctionParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McFunctionParseErrorKind::ContinuationAtEndOfFile => {
+                f.write_str("Line continuation at end of file")
+            }
+            McFunctionParseErrorKind::LeadingSlash => {
+                f.write_str("Leading slash is not allowed in functions")
+            }
+            McFunctionParseErrorKind::MacroWithoutVariables => {
+                f.write_str("Macro line doesn't use any variables")
+            }
+            McFunctionParseErrorKind::Command(error) => error.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct McFunctionParseError {
+    pub line: usize,
+    pub kind: McFunctionParseErrorKind,
+}
+
+impl Display for McFunctionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} on line {}", self.kind, self.line)
+    }
+}
+
+impl std::error::Error for McFunctionParseError {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum McFunctionLine {
+    Command(Box<Command>),
+    Comment(String),
+    Blank,
+    /// Written verbatim, including any `$` prefix.
+    Raw(String),
+}
+
+impl Display for McFunctionLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McFunctionLine::Command(command) => {
+                if command.has_macro() {
+                    f.write_str("$")?;
+                }
+
+                command.fmt(f)
+            }
+            McFunctionLine::Comment(comment) => write!(f, "#{}", comment),
+            McFunctionLine::Blank => Ok(()),
+            McFunctionLine::Raw(raw) => f.write_str(raw),
+        }
+    }
+}
+
+impl From<Command> for McFunctionLine {
+    fn from(command: Command) -> Self {
+        McFunctionLine::Command(Box::new(command))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct McFunction {
+    pub lines: Vec<McFunctionLine>,
+    /// The physical lines of lines that were read with backslash continuations, by index. They
+    /// are written back while the line still joins to the same text.
+    continuations: BTreeMap<usize, Vec<String>>,
+}
+
+/// Layout isn't part of a function's meaning.
+impl PartialEq for McFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.lines == other.lines
+    }
+}
+
+impl Eq for McFunction {}
+
+impl McFunction {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps every non-blank line as [`McFunctionLine::Raw`] without parsing it.
+    #[must_use]
+    pub fn raw(text: &str) -> Self {
+        let lines: Vec<_> = text
+            .lines()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    McFunctionLine::Blank
+                } else {
+                    McFunctionLine::Raw(line.to_string())
+                }
+            })
+            .collect();
+
+        McFunction::from(lines)
+    }
+
+    #[inline]
+    pub fn push(&mut self, line: impl Into<McFunctionLine>) {
+        self.lines.push(line.into());
+    }
+
+    #[inline]
+    pub fn push_command(&mut self, command: Command) {
+        self.lines.push(McFunctionLine::Command(Box::new(command)));
+    }
+
+    #[inline]
+    pub fn push_comment<T: ToString>(&mut self, comment: T) {
+        self.lines
+            .push(McFunctionLine::Comment(comment.to_string()));
+    }
+
+    #[inline]
+    pub fn push_blank(&mut self) {
+        self.lines.push(McFunctionLine::Blank);
+    }
+
+    #[inline]
+    pub fn push_raw<T: ToString>(&mut self, raw: T) {
+        self.lines.push(McFunctionLine::Raw(raw.to_string()));
+    }
+
+    pub fn extend(&mut self, other: McFunction) {
+        self.lines.extend(other.lines);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.lines.iter().filter_map(|line| match line {
+            McFunctionLine::Command(command) => Some(command.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn check_version(&self, target: GameVersion) -> Result<(), VersionError> {
+        self.commands()
+            .try_for_each(|command| command.check_version(target))
+    }
+
+    /// Renders every command line for `target`, see [`Command::render`].
+    pub fn render(&self, target: GameVersion) -> Result<String, VersionError> {
+        let mut result = String::new();
+
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                result.push('\n');
+            }
+
+            match line {
+                McFunctionLine::Command(command) => {
+                    if command.has_macro() {
+                        result.push('$');
+                    }
+
+                    result.push_str(&command.render(target)?);
+                }
+                line => result.push_str(&line.to_string()),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Like [`Display`], but splits command lines longer than `max_width` with backslash
+    /// continuations.
+    #[must_use]
+    pub fn to_string_wrapped(&self, max_width: usize) -> String {
+        let mut result = String::new();
+
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                result.push('\n');
+            }
+
+            let text = line.to_string();
+
+            if matches!(line, McFunctionLine::Command(_)) {
+                wrap_line(&mut result, &text, max_width);
+            } else {
+                result.push_str(&text);
+            }
+        }
+
+        result
+    }
+}
+
+fn wrap_line(result: &mut String, text: &str, max_width: usize) {
+    // The game trims every physical line before joining, so a piece may only end after a
+    // space (kept in front of the backslash) and the next piece may not start with one.
+    let breaks = text
+        .char_indices()
+        .zip(text.chars().skip(1))
+        .filter(|&((_, c), next)| c == ' ' && next != ' ')
+        .map(|((i, _), _)| i + 1)
+        .collect::<Vec<_>>();
+
+    let mut start = 0;
+    let mut indent = "";
+
+    while text[start..].chars().count() + indent.len() > max_width {
+        let fits = |end: usize| indent.len() + text[start..end].chars().count() < max_width;
+
+        let Some(end) = breaks
+            .iter()
+            .copied()
+            .filter(|&end| end > start)
+            .take_while(|&end| fits(end))
+            .last()
+            .or_else(|| breaks.iter().copied().find(|&end| end > start))
+        else {
+            break;
+        };
+
+        result.push_str(indent);
+        result.push_str(&text[start..end]);
+        result.push_str("\\\n");
+
+        start = end;
+        indent = CONTINUATION_INDENT;
+    }
+
+    result.push_str(indent);
+    result.push_str(&text[start..]);
+}
+
+fn parse_line(line: &str) -> Result<McFunctionLine, McFunctionParseErrorKind> {
+    if line.is_empty() {
+        return Ok(McFunctionLine::Blank);
+    }
+
+    if let Some(comment) = line.strip_prefix('#') {
+        return Ok(McFunctionLine::Comment(comment.to_string()));
+    }
+
+    if line.starts_with('/') {
+        return Err(McFunctionParseErrorKind::LeadingSlash);
+    }
+
+    if let Some(macro_line) = line.strip_prefix('$') {
+        if !macro_line.contains("$(") {
+            return Err(McFunctionParseErrorKind::MacroWithoutVariables);
+        }
+
+        // Most slots stand in for numbers, coordinates or whole ids, which only parse once the
+        // macro is expanded. Some arguments (e.g. `say` messages) are kept as plain text, so the
+        // macro wouldn't be detected and the `$` prefix would be lost.
+        return Ok(match parse_all::<Command>(macro_line) {
+            Ok(command) if command.has_macro() => McFunctionLine::Command(Box::new(command)),
+            _ => McFunctionLine::Raw(line.to_string()),
+        });
+    }
+
+    parse_all::<Command>(line)
+        .map(McFunctionLine::from)
+        .map_err(McFunctionParseErrorKind::Command)
+}
+
+/// Joins physical lines like the game: each is trimmed, and a trailing backslash continues the
+/// line.
+fn join_continuation<T: AsRef<str>>(physical_lines: &[T]) -> String {
+    let mut line = String::new();
+
+    for physical_line in physical_lines {
+        if line.ends_with('\\') {
+            line.pop();
+        }
+
+        line.push_str(physical_line.as_ref().trim());
+    }
+
+    line
+}
+
+impl FromStr for McFunction {
+    type Err = McFunctionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let physical_lines = s.lines().collect::<Vec<_>>();
+        let mut function = McFunction::new();
+        let mut i = 0;
+
+        while i < physical_lines.len() {
+            let line_number = i + 1;
+            let start = i;
+
+            while physical_lines[i].trim().ends_with('\\') {
+                i += 1;
+
+                if i == physical_lines.len() {
+                    return Err(McFunctionParseError {
+                        line: line_number,
+                        kind: McFunctionParseErrorKind::ContinuationAtEndOfFile,
+                    });
+                }
+            }
+
+            let continued = &physical_lines[start..=i];
+            let line = join_continuation(continued);
+
+            if continued.len() > 1 {
+                function.continuations.insert(
+                    function.lines.len(),
+                    continued.iter().map(ToString::to_string).collect(),
+                );
+            }
+
+            function
+                .lines
+                .push(parse_line(&line).map_err(|kind| McFunctionParseError {
+                    line: line_number,
+                    kind,
+                })?);
+
+            i += 1;
+        }
+
+        Ok(function)
+    }
+}
+
+impl Display for McFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+
+            let text = line.to_string();
+
+            match self.continuations.get(&i) {
+                Some(physical_lines) if join_continuation(physical_lines) == text => {
+                    f.write_str(&physical_lines.join("\n"))?
+                }
+                _ => f.write_str(&text)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for McFunction {
+    fn from(text: &str) -> Self {
+        McFunction::raw(text)
+    }
+}
+
+impl From<Vec<McFunctionLine>> for McFunction {
+    fn from(lines: Vec<McFunctionLine>) -> Self {
+        McFunction {
+            lines,
+            continuations: BTreeMap::new(),
+        }
+    }
+}
+
+impl FromIterator<Command> for McFunction {
+    fn from_iter<I: IntoIterator<Item = Command>>(iter: I) -> Self {
+        McFunction::from(
+            iter.into_iter()
+                .map(McFunctionLine::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let function: McFunction = "# setup\n\nscoreboard objectives add foo dummy\nexecute as @a \\\n    run say hi\n$say $(name)"
+            .parse()
+            .unwrap();
+
+        assert_eq!(function.lines.len(), 5);
+        assert_eq!(
+            function.lines[0],
+            McFunctionLine::Comment(" setup".to_string())
+        );
+        assert_eq!(function.lines[1], McFunctionLine::Blank);
+        assert_eq!(
+            function.lines[3].to_string(),
+            "execute as @a run say hi".to_string()
+        );
+        assert_eq!(
+            function.lines[4],
+            McFunctionLine::Raw("$say $(name)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_macro_prefix() {
+        let command: Command = "function foo:bar".parse().unwrap();
+        let macro_command: Command = "data modify storage foo:bar $(key) set value 1b"
+            .parse()
+            .unwrap();
+
+        let function: McFunction = [command, macro_command].into_iter().collect();
+        assert_eq!(
+            function.to_string(),
+            "function foo:bar\n$data modify storage foo:bar $(key) set value 1b"
+        );
+
+        let parsed: McFunction = function.to_string().parse().unwrap();
+        assert_eq!(parsed, function);
+
+        let embedded: Command = "data remove storage foo:bar a.b$(x)".parse().unwrap();
+        let function: McFunction = [embedded].into_iter().collect();
+        assert_eq!(function.to_string(), "$data remove storage foo:bar a.b$(x)");
+    }
+
+    #[test]
+    fn test_unparsable_macro_lines() {
+        let lines = [
+            "$scoreboard players set @s foo $(value)",
+            "$tp @s $(x) $(y) $(z)",
+            "$function $(fn)",
+            "$give @s $(item)",
+        ];
+        let function: McFunction = lines.join("\n").parse().unwrap();
+
+        assert_eq!(
+            function.lines,
+            lines
+                .iter()
+                .map(|line| McFunctionLine::Raw(line.to_string()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(function.to_string(), lines.join("\n"));
+    }
+
+    #[test]
+    fn test_wrapped() {
+        let function: McFunction = "execute as @a at @s if score @s foo matches 1.. run say hi"
+            .parse()
+            .unwrap();
+
+        let wrapped = function.to_string_wrapped(24);
+        assert!(wrapped.lines().all(|line| line.len() <= 24));
+        assert_eq!(wrapped.parse::<McFunction>().unwrap(), function);
+    }
+
+    #[test]
+    fn test_continuation_layout() {
+        let text = "say start\nexecute as @a \\\n    at @s \\\n    run say hi\nsay end";
+        let mut function: McFunction = text.parse().unwrap();
+
+        assert_eq!(function.lines.len(), 3);
+        assert_eq!(
+            function.lines[1].to_string(),
+            "execute as @a at @s run say hi"
+        );
+        assert_eq!(function.to_string(), text);
+
+        function.lines[1] = McFunctionLine::from("say changed".parse::<Command>().unwrap());
+        assert_eq!(function.to_string(), "say start\nsay changed\nsay end");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "say hi\nsay \\".parse::<McFunction>().unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.kind,
+            McFunctionParseErrorKind::ContinuationAtEndOfFile
+        );
+
+        let error = "/say hi".parse::<McFunction>().unwrap_err();
+        assert_eq!(error.kind, McFunctionParseErrorKind::LeadingSlash);
+
+        let error = "$say hi".parse::<McFunction>().unwrap_err();
+        assert_eq!(error.kind, McFunctionParseErrorKind::MacroWithoutVariables);
+    }
+}
@@ -1,18 +1,27 @@
+#[cfg(feature = "zip")]
+mod archive;
+pub mod function;
+pub mod output;
 pub mod pack;
 pub mod tag;
 
+use crate::datapack::function::McFunction;
+use crate::datapack::output::{FsOutput, Output};
 use crate::datapack::pack::Pack;
 use crate::datapack::pack::feature::Features;
 use crate::datapack::pack::filter::Filter;
 use crate::datapack::pack::language::Language;
-use crate::datapack::pack::overlay::Overlays;
+use crate::datapack::pack::overlay::{Overlay, Overlays};
 use crate::datapack::tag::{Tag, TagType, Worldgen};
+use crate::version::{GameVersion, VersionError};
 use nonempty::NonEmpty;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
+use std::fmt::Display;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::{fs, io};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,7 +73,7 @@ impl<T> FilePathNode<T> {
 
 #[derive(Debug, Clone, Default)]
 pub struct Namespace {
-    pub functions: BTreeMap<NonEmpty<String>, String>,
+    pub functions: BTreeMap<NonEmpty<String>, McFunction>,
     pub tags: BTreeMap<TagType, BTreeMap<NonEmpty<String>, Tag>>,
 
     pub advancements: BTreeMap<NonEmpty<String>, Value>,
@@ -97,12 +106,18 @@ pub struct Namespace {
     pub wolf_sound_variants: BTreeMap<NonEmpty<String>, Value>,
     pub wolf_variants: BTreeMap<NonEmpty<String>, Value>,
     pub worldgen: Worldgen,
+
+    /// Files that aren't understood, keyed by their path (including the extension) relative to
+    /// the namespace directory.
+    pub other_files: BTreeMap<NonEmpty<String>, Vec<u8>>,
 }
+
 fn write_file_path_nodes<T>(
     base_path: &Path,
     nodes: &BTreeMap<NonEmpty<String>, T>,
     extension: &str,
     serializer: &impl Fn(&T) -> io::Result<String>,
+    output: &mut dyn Output,
 ) -> io::Result<()> {
     for (path, content) in nodes {
         let mut file_path = PathBuf::from(base_path);
@@ -118,17 +133,293 @@ fn write_file_path_nodes<T>(
             continue;
         }
 
-        if let Some(parent) = file_path.parent() {
-            fs::create_dir_all(parent)?;
+        output.write_file(&file_path, serialized_content.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+type Files = Vec<(Vec<String>, Vec<u8>)>;
+
+fn read_directory_files(directory: &Path) -> io::Result<Files> {
+    fn visit(directory: &Path, prefix: &[String], files: &mut Files) -> io::Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            let name = entry.file_name().into_string().map_err(|name| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid file name {:?}", name),
+                )
+            })?;
+
+            let mut segments = prefix.to_vec();
+            segments.push(name);
+
+            if entry.file_type()?.is_dir() {
+                visit(&path, &segments, files)?;
+            } else {
+                files.push((segments, fs::read(&path)?));
+            }
         }
 
-        fs::write(&file_path, serialized_content)?;
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    visit(directory, &[], &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+fn write_other_files(
+    base_path: &Path,
+    files: &BTreeMap<NonEmpty<String>, Vec<u8>>,
+    output: &mut dyn Output,
+) -> io::Result<()> {
+    for (path, content) in files {
+        let mut file_path = PathBuf::from(base_path);
+
+        for segment in path.iter() {
+            file_path.push(segment);
+        }
+
+        output.write_file(&file_path, content)?;
     }
 
     Ok(())
 }
 
+fn invalid_data(path: &Path, error: impl Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), error),
+    )
+}
+
+/// Strips `extension` from the last segment, returning `None` if the path doesn't end with it.
+fn strip_extension(segments: &[String], extension: &str) -> Option<NonEmpty<String>> {
+    let mut path = NonEmpty::from_vec(segments.to_vec())?;
+    let file_name = path.last().strip_suffix(extension)?;
+
+    if file_name.is_empty() {
+        return None;
+    }
+
+    *path.last_mut() = file_name.to_string();
+
+    Some(path)
+}
+
+/// Returns the folder name used by `target`, as some folders were plural before 1.21.
+fn folder_name(name: &'static str, target: Option<GameVersion>) -> &'static str {
+    if target.is_none_or(|target| target >= GameVersion::SINGULAR_FOLDERS) {
+        return name;
+    }
+
+    match name {
+        "advancement" => "advancements",
+        "function" => "functions",
+        "item_modifier" => "item_modifiers",
+        "loot_table" => "loot_tables",
+        "predicate" => "predicates",
+        "recipe" => "recipes",
+        name => name,
+    }
+}
+
+fn tag_folder_name(tag_type: &TagType, target: Option<GameVersion>) -> String {
+    if target.is_none_or(|target| target >= GameVersion::SINGULAR_FOLDERS) {
+        return tag_type.to_string();
+    }
+
+    match tag_type {
+        TagType::Block => "blocks".to_string(),
+        TagType::EntityType => "entity_types".to_string(),
+        TagType::Fluid => "fluids".to_string(),
+        TagType::Function => "functions".to_string(),
+        TagType::GameEvent => "game_events".to_string(),
+        TagType::Item => "items".to_string(),
+        tag_type => tag_type.to_string(),
+    }
+}
+
+/// Returns the folder name used since 1.21 for a folder of any version, the inverse of
+/// [`folder_name`].
+fn singular_folder_name(name: &str) -> &str {
+    match name {
+        "advancements" => "advancement",
+        "functions" => "function",
+        "item_modifiers" => "item_modifier",
+        "loot_tables" => "loot_table",
+        "predicates" => "predicate",
+        "recipes" => "recipe",
+        name => name,
+    }
+}
+
+/// Returns the tag type of a tag folder of any version, the inverse of [`tag_folder_name`].
+fn tag_type_from_folder(name: &str) -> Option<TagType> {
+    match name {
+        "blocks" => Some(TagType::Block),
+        "entity_types" => Some(TagType::EntityType),
+        "fluids" => Some(TagType::Fluid),
+        "functions" => Some(TagType::Function),
+        "game_events" => Some(TagType::GameEvent),
+        "items" => Some(TagType::Item),
+        name => TagType::from_str(name).ok(),
+    }
+}
+
 impl Namespace {
+    pub fn read(namespace_path: &Path) -> io::Result<Namespace> {
+        Self::from_files(namespace_path, read_directory_files(namespace_path)?)
+    }
+
+    /// Reads a namespace from its files. Errors name the file below `namespace_path`.
+    fn from_files(namespace_path: &Path, files: Files) -> io::Result<Namespace> {
+        let mut namespace = Namespace::default();
+
+        for (segments, content) in files {
+            let file_path = namespace_path.join(segments.iter().collect::<PathBuf>());
+
+            if !namespace.read_file(&segments, &file_path, &content)? {
+                let path = NonEmpty::from_vec(segments).expect("File paths are never empty");
+
+                namespace.other_files.insert(path, content);
+            }
+        }
+
+        Ok(namespace)
+    }
+
+    fn read_file(
+        &mut self,
+        segments: &[String],
+        file_path: &Path,
+        content: &[u8],
+    ) -> io::Result<bool> {
+        let Some((folder, rest)) = segments.split_first() else {
+            return Ok(false);
+        };
+        let folder = singular_folder_name(folder);
+
+        if folder == "function" {
+            let (Some(path), Ok(text)) = (
+                strip_extension(rest, ".mcfunction"),
+                std::str::from_utf8(content),
+            ) else {
+                return Ok(false);
+            };
+
+            self.functions.insert(path, McFunction::raw(text));
+
+            return Ok(true);
+        }
+
+        if folder == "tags" {
+            let (tag_type, rest) = match rest {
+                [worldgen, tag_type, rest @ ..] if worldgen == "worldgen" => {
+                    match TagType::from_str(tag_type) {
+                        Ok(tag_type) if tag_type.is_worldgen() => (tag_type, rest),
+                        _ => return Ok(false),
+                    }
+                }
+                [tag_type, rest @ ..] => match tag_type_from_folder(tag_type) {
+                    Some(tag_type) if !tag_type.is_worldgen() => (tag_type, rest),
+                    _ => return Ok(false),
+                },
+                [] => return Ok(false),
+            };
+
+            let Some(path) = strip_extension(rest, ".json") else {
+                return Ok(false);
+            };
+
+            let tag = serde_json::from_slice(content).map_err(|e| invalid_data(file_path, e))?;
+            self.tags.entry(tag_type).or_default().insert(path, tag);
+
+            return Ok(true);
+        }
+
+        if folder == "worldgen" {
+            let Some((worldgen_folder, rest)) = rest.split_first() else {
+                return Ok(false);
+            };
+
+            let (Some(registry), Some(path)) = (
+                self.worldgen.folder_mut(worldgen_folder),
+                strip_extension(rest, ".json"),
+            ) else {
+                return Ok(false);
+            };
+
+            let value = serde_json::from_slice(content).map_err(|e| invalid_data(file_path, e))?;
+            registry.insert(path, value);
+
+            return Ok(true);
+        }
+
+        let (Some(registry), Some(path)) = (
+            self.json_registry_mut(folder),
+            strip_extension(rest, ".json"),
+        ) else {
+            return Ok(false);
+        };
+
+        let value = serde_json::from_slice(content).map_err(|e| invalid_data(file_path, e))?;
+        registry.insert(path, value);
+
+        Ok(true)
+    }
+
+    fn json_registry_mut(
+        &mut self,
+        folder_name: &str,
+    ) -> Option<&mut BTreeMap<NonEmpty<String>, Value>> {
+        Some(match folder_name {
+            "advancement" => &mut self.advancements,
+            "banner_pattern" => &mut self.banner_patterns,
+            "cat_variant" => &mut self.cat_variants,
+            "chat_type" => &mut self.chat_types,
+            "chicken_variant" => &mut self.chicken_variants,
+            "cow_variant" => &mut self.cow_variants,
+            "damage_type" => &mut self.damage_types,
+            "dialog" => &mut self.dialogs,
+            "dimension" => &mut self.dimensions,
+            "dimension_type" => &mut self.dimension_types,
+            "enchantment" => &mut self.enchantments,
+            "enchantment_provider" => &mut self.enchantment_providers,
+            "frog_variant" => &mut self.frog_variants,
+            "instrument" => &mut self.instruments,
+            "item_modifier" => &mut self.item_modifiers,
+            "jukebox_song" => &mut self.jukebox_songs,
+            "loot_table" => &mut self.loot_tables,
+            "painting_variant" => &mut self.painting_variants,
+            "pig_variant" => &mut self.pig_variants,
+            "predicate" => &mut self.predicates,
+            "recipe" => &mut self.recipes,
+            "test_environment" => &mut self.test_environments,
+            "test_instance" => &mut self.test_instances,
+            "timeline" => &mut self.timelines,
+            "trial_spawner" => &mut self.trial_spawners,
+            "trim_material" => &mut self.trim_materials,
+            "trim_pattern" => &mut self.trim_patterns,
+            "wolf_sound_variant" => &mut self.wolf_sound_variants,
+            "wolf_variant" => &mut self.wolf_variants,
+            _ => return None,
+        })
+    }
+
+    /// Checks that every function command is available in `target`.
+    pub fn check_version(&self, target: GameVersion) -> Result<(), VersionError> {
+        self.functions
+            .values()
+            .try_for_each(|function| function.check_version(target))
+    }
+
     pub fn merge(&mut self, mut other: Namespace) {
         self.functions.append(&mut other.functions);
 
@@ -169,16 +460,46 @@ impl Namespace {
         self.wolf_variants.append(&mut other.wolf_variants);
 
         self.worldgen.merge(other.worldgen);
+
+        self.other_files.append(&mut other.other_files);
     }
 
     pub fn write(&self, namespace_path: &Path) -> io::Result<()> {
+        self.write_to(Path::new(""), &mut FsOutput::new(namespace_path))
+    }
+
+    pub fn write_to(&self, namespace_path: &Path, output: &mut dyn Output) -> io::Result<()> {
+        self.write_for(namespace_path, None, output)
+    }
+
+    /// Like [`Namespace::write_to`], but renders functions and folder names for `target`.
+    pub fn write_to_version(
+        &self,
+        namespace_path: &Path,
+        target: GameVersion,
+        output: &mut dyn Output,
+    ) -> io::Result<()> {
+        self.write_for(namespace_path, Some(target), output)
+    }
+
+    fn write_for(
+        &self,
+        namespace_path: &Path,
+        target: Option<GameVersion>,
+        output: &mut dyn Output,
+    ) -> io::Result<()> {
         let json_serializer = |v: &Value| serde_json::to_string_pretty(v).map_err(io::Error::other);
+        let folder = |name: &'static str| namespace_path.join(folder_name(name, target));
 
         write_file_path_nodes(
-            &namespace_path.join("function"),
+            &folder("function"),
             &self.functions,
             ".mcfunction",
-            &|content_str| Ok(content_str.clone()),
+            &|function| match target {
+                Some(target) => function.render(target).map_err(io::Error::other),
+                None => Ok(function.to_string()),
+            },
+            output,
         )?;
 
         let tags_root_path = namespace_path.join("tags");
@@ -186,21 +507,26 @@ impl Namespace {
             let type_path = if tag_type.is_worldgen() {
                 tags_root_path.join("worldgen").join(tag_type.to_string())
             } else {
-                tags_root_path.join(tag_type.to_string())
+                tags_root_path.join(tag_folder_name(tag_type, target))
             };
 
-            write_file_path_nodes(&type_path, nodes, ".json", &|tag| {
-                serde_json::to_string_pretty(tag).map_err(io::Error::other)
-            })?;
+            write_file_path_nodes(
+                &type_path,
+                nodes,
+                ".json",
+                &|tag| serde_json::to_string_pretty(tag).map_err(io::Error::other),
+                output,
+            )?;
         }
 
         macro_rules! generate_write_file_path_nodes {
             ($field_name:expr, $folder_name:expr) => {
                 write_file_path_nodes(
-                    &namespace_path.join($folder_name),
+                    &folder($folder_name),
                     &$field_name,
                     ".json",
                     &json_serializer,
+                    output,
                 )?;
             };
         }
@@ -235,6 +561,19 @@ impl Namespace {
         generate_write_file_path_nodes!(self.wolf_sound_variants, "wolf_sound_variant");
         generate_write_file_path_nodes!(self.wolf_variants, "wolf_variant");
 
+        let worldgen_path = namespace_path.join("worldgen");
+        for (folder_name, registry) in self.worldgen.folders() {
+            write_file_path_nodes(
+                &worldgen_path.join(folder_name),
+                registry,
+                ".json",
+                &json_serializer,
+                output,
+            )?;
+        }
+
+        write_other_files(namespace_path, &self.other_files, output)?;
+
         Ok(())
     }
 
@@ -251,20 +590,81 @@ impl Namespace {
         }
     }
 
-    pub fn add_function(&mut self, path: &NonEmpty<String>, new_function: &str) {
-        if let Some(functions) = self.functions.get_mut(path) {
-            functions.push('\n');
-            functions.push_str(new_function);
+    pub fn add_function(&mut self, path: &NonEmpty<String>, new_function: impl Into<McFunction>) {
+        let new_function = new_function.into();
+
+        if let Some(function) = self.functions.get_mut(path) {
+            function.extend(new_function);
         } else {
-            self.functions
-                .insert(path.clone(), new_function.to_string());
+            self.functions.insert(path.clone(), new_function);
         }
     }
 }
 
+/// The namespaces of an overlay directory, written to `<directory>/data/<namespace>`.
+#[derive(Debug, Clone, Default)]
+pub struct DatapackOverlay {
+    pub namespaces: BTreeMap<String, Namespace>,
+}
+
+impl DatapackOverlay {
+    pub fn get_namespace_mut(&mut self, name: &str) -> &mut Namespace {
+        self.namespaces.entry(name.to_string()).or_default()
+    }
+
+    pub fn add_namespace<T: ToString>(&mut self, name: T, namespace: Namespace) {
+        add_namespace(&mut self.namespaces, name.to_string(), namespace);
+    }
+}
+
+fn add_namespace(namespaces: &mut BTreeMap<String, Namespace>, name: String, namespace: Namespace) {
+    match namespaces.entry(name) {
+        Entry::Vacant(e) => {
+            e.insert(namespace);
+        }
+        Entry::Occupied(mut e) => {
+            e.get_mut().merge(namespace);
+        }
+    }
+}
+
+fn split_namespace_files(files: Files) -> BTreeMap<String, Files> {
+    let mut namespace_files = BTreeMap::<String, Files>::new();
+
+    for (mut segments, content) in files {
+        let rest = segments.split_off(1);
+
+        namespace_files
+            .entry(segments.remove(0))
+            .or_default()
+            .push((rest, content));
+    }
+
+    namespace_files
+}
+
+fn read_namespaces(data_path: &Path, files: Files) -> io::Result<BTreeMap<String, Namespace>> {
+    split_namespace_files(files)
+        .into_iter()
+        .map(|(name, files)| {
+            let namespace = Namespace::from_files(&data_path.join(&name), files)?;
+
+            Ok((name, namespace))
+        })
+        .collect()
+}
+
 pub struct Datapack {
     pub pack: PackMCMeta,
     pub namespaces: BTreeMap<String, Namespace>,
+
+    /// Content of the overlay directories, keyed by directory. Every directory must be declared
+    /// in [`PackMCMeta::overlays`].
+    pub overlays: BTreeMap<String, DatapackOverlay>,
+
+    /// Files outside of namespaces (such as `pack.png`), keyed by their path relative to the
+    /// datapack directory.
+    pub other_files: BTreeMap<NonEmpty<String>, Vec<u8>>,
 }
 
 impl Datapack {
@@ -286,22 +686,144 @@ impl Datapack {
                 language: None,
             },
             namespaces: BTreeMap::new(),
+            overlays: BTreeMap::new(),
+            other_files: BTreeMap::new(),
+        }
+    }
+
+    pub fn read(datapack_directory: &Path) -> io::Result<Datapack> {
+        Self::from_files(read_directory_files(datapack_directory)?)
+    }
+
+    fn from_files(files: Files) -> io::Result<Datapack> {
+        let Some(pack_content) = files
+            .iter()
+            .find(|(segments, _)| segments == &["pack.mcmeta"])
+            .map(|(_, content)| content)
+        else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Missing pack.mcmeta",
+            ));
+        };
+
+        let pack: PackMCMeta = serde_json::from_slice(pack_content)
+            .map_err(|e| invalid_data(Path::new("pack.mcmeta"), e))?;
+
+        let overlay_directories = pack
+            .overlays
+            .iter()
+            .flat_map(|overlays| &overlays.entries)
+            .map(|overlay| overlay.directory.clone())
+            .collect::<Vec<_>>();
+
+        let mut namespace_files = Files::new();
+        let mut overlay_files = BTreeMap::<String, Files>::new();
+        let mut other_files = BTreeMap::new();
+
+        for (mut segments, content) in files {
+            if segments == ["pack.mcmeta"] {
+                continue;
+            }
+
+            if segments.len() > 2 && segments[0] == "data" {
+                namespace_files.push((segments.split_off(1), content));
+
+                continue;
+            }
+
+            if segments.len() > 3
+                && segments[1] == "data"
+                && overlay_directories.contains(&segments[0])
+            {
+                let rest = segments.split_off(2);
+
+                overlay_files
+                    .entry(segments.remove(0))
+                    .or_default()
+                    .push((rest, content));
+
+                continue;
+            }
+
+            let path = NonEmpty::from_vec(segments).expect("File paths are never empty");
+            other_files.insert(path, content);
         }
+
+        let namespaces = read_namespaces(Path::new("data"), namespace_files)?;
+        let overlays = overlay_files
+            .into_iter()
+            .map(|(directory, files)| {
+                let namespaces = read_namespaces(&Path::new(&directory).join("data"), files)?;
+
+                Ok((directory, DatapackOverlay { namespaces }))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Datapack {
+            pack,
+            namespaces,
+            overlays,
+            other_files,
+        })
     }
+
     pub fn write(&self, datapack_directory: &Path) -> io::Result<()> {
         fs::create_dir_all(datapack_directory)?;
 
-        let mcmeta_path = datapack_directory.join("pack.mcmeta");
+        self.write_to(&mut FsOutput::new(datapack_directory))
+    }
+
+    /// Writes every file of the datapack to `output`, with paths relative to the datapack root.
+    pub fn write_to(&self, output: &mut dyn Output) -> io::Result<()> {
+        self.write_for(None, output)
+    }
+
+    /// Writes the datapack for `target`. Commands that don't exist in `target` are reported as
+    /// errors, and changed syntax and folder names are lowered where possible.
+    pub fn write_to_version(&self, target: GameVersion, output: &mut dyn Output) -> io::Result<()> {
+        self.write_for(Some(target), output)
+    }
+
+    fn write_for(&self, target: Option<GameVersion>, output: &mut dyn Output) -> io::Result<()> {
         let mcmeta_content = serde_json::to_string_pretty(&self.pack).map_err(io::Error::other)?;
-        fs::write(mcmeta_path, mcmeta_content)?;
+        output.write_file(Path::new("pack.mcmeta"), mcmeta_content.as_bytes())?;
 
-        let data_path = datapack_directory.join("data");
+        let data_path = Path::new("data");
 
         for (name, namespace) in &self.namespaces {
             let namespace_path = data_path.join(name);
-            namespace.write(&namespace_path)?;
+            namespace.write_for(&namespace_path, target, output)?;
+        }
+
+        for (directory, overlay) in &self.overlays {
+            // Overlay content only has to work in the releases the overlay applies to.
+            let earliest = self
+                .overlay_entry(directory)?
+                .supported_range()
+                .and_then(|(min, _)| GameVersion::earliest_for_data_pack_format(min));
+
+            let overlay_target =
+                target.map(|target| earliest.map_or(target, |earliest| target.max(earliest)));
+
+            let data_path = Path::new(directory).join("data");
+
+            for (name, namespace) in &overlay.namespaces {
+                if let Some(earliest) = earliest {
+                    namespace.check_version(earliest).map_err(|e| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("Overlay '{}': {}", directory, e),
+                        )
+                    })?;
+                }
+
+                namespace.write_for(&data_path.join(name), overlay_target, output)?;
+            }
         }
 
+        write_other_files(Path::new(""), &self.other_files, output)?;
+
         Ok(())
     }
 
@@ -310,13 +832,365 @@ impl Datapack {
     }
 
     pub fn add_namespace<T: ToString>(&mut self, name: T, namespace: Namespace) {
-        match self.namespaces.entry(name.to_string()) {
-            Entry::Vacant(e) => {
-                e.insert(namespace);
-            }
-            Entry::Occupied(mut e) => {
-                e.get_mut().merge(namespace);
-            }
-        }
+        add_namespace(&mut self.namespaces, name.to_string(), namespace);
+    }
+
+    /// Returns the content of the overlay `directory`, which should be declared in
+    /// [`PackMCMeta::overlays`] before writing.
+    pub fn overlay(&mut self, directory: &str) -> &mut DatapackOverlay {
+        self.overlays.entry(directory.to_string()).or_default()
+    }
+
+    fn overlay_entry(&self, directory: &str) -> io::Result<&Overlay> {
+        self.pack
+            .overlays
+            .iter()
+            .flat_map(|overlays| &overlays.entries)
+            .find(|overlay| overlay.directory == directory)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Overlay '{}' isn't declared in pack.mcmeta", directory),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datapack::output::MemoryOutput;
+    use crate::datapack::pack::format::Format;
+    use nonempty::nonempty;
+
+    #[test]
+    fn test_read() {
+        let directory = std::env::temp_dir().join(format!(
+            "minecraft_command_types_read_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&directory);
+
+        let mut datapack = Datapack::new(48, Value::from("test"));
+        datapack.other_files.insert(
+            nonempty!["pack.png".to_string()],
+            vec![0x89, b'P', b'N', b'G'],
+        );
+
+        let namespace = datapack.get_namespace_mut("foo");
+        namespace.add_function(&nonempty!["a".to_string(), "b".to_string()], "say hi");
+        namespace.add_tag(
+            TagType::Biome,
+            &nonempty!["hot".to_string()],
+            Tag {
+                replace: None,
+                values: vec![],
+            },
+        );
+        namespace
+            .loot_tables
+            .insert(nonempty!["chest".to_string()], serde_json::json!({}));
+        namespace
+            .other_files
+            .insert(nonempty!["notes.txt".to_string()], b"hello".to_vec());
+
+        datapack.write(&directory).unwrap();
+        fs::create_dir_all(directory.join("data/foo/worldgen/biome")).unwrap();
+        fs::write(directory.join("data/foo/worldgen/biome/hot.json"), "{}").unwrap();
+
+        let read = Datapack::read(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(read.pack.pack.pack_format, Some(48));
+        assert_eq!(read.other_files, datapack.other_files);
+
+        let namespace = &read.namespaces["foo"];
+        assert_eq!(
+            namespace.functions[&nonempty!["a".to_string(), "b".to_string()]].to_string(),
+            "say hi"
+        );
+        assert!(namespace.tags[&TagType::Biome].contains_key(&nonempty!["hot".to_string()]));
+        assert!(
+            namespace
+                .loot_tables
+                .contains_key(&nonempty!["chest".to_string()])
+        );
+        assert!(
+            namespace
+                .worldgen
+                .biome
+                .contains_key(&nonempty!["hot".to_string()])
+        );
+        assert_eq!(
+            namespace.other_files[&nonempty!["notes.txt".to_string()]],
+            b"hello"
+        );
+    }
+
+    #[test]
+    fn test_read_plural_folders() {
+        let file = |path: &str, content: &str| {
+            (
+                path.split('/').map(ToString::to_string).collect(),
+                content.as_bytes().to_vec(),
+            )
+        };
+
+        let namespace = Namespace::from_files(
+            Path::new(""),
+            vec![
+                file("functions/a/b.mcfunction", "say hi"),
+                file("tags/functions/load.json", r#"{"values":["foo:a/b"]}"#),
+                file("tags/blocks/ores.json", r#"{"values":["minecraft:stone"]}"#),
+                file("loot_tables/chest.json", "{}"),
+            ],
+        )
+        .unwrap();
+
+        assert!(
+            namespace
+                .functions
+                .contains_key(&nonempty!["a".to_string(), "b".to_string()])
+        );
+        assert!(namespace.tags[&TagType::Function].contains_key(&nonempty!["load".to_string()]));
+        assert!(namespace.tags[&TagType::Block].contains_key(&nonempty!["ores".to_string()]));
+        assert!(
+            namespace
+                .loot_tables
+                .contains_key(&nonempty!["chest".to_string()])
+        );
+        assert!(namespace.other_files.is_empty());
+    }
+
+    #[test]
+    fn test_read_missing_pack_mcmeta() {
+        let files = vec![(
+            vec!["data".to_string(), "foo".to_string(), "a.txt".to_string()],
+            Vec::new(),
+        )];
+
+        let Err(error) = Datapack::from_files(files) else {
+            panic!("A datapack without pack.mcmeta was read");
+        };
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert_eq!(error.to_string(), "Missing pack.mcmeta");
+    }
+
+    #[test]
+    fn test_read_malformed_registry() {
+        let file = |path: &str, content: &str| {
+            (
+                path.split('/').map(ToString::to_string).collect(),
+                content.as_bytes().to_vec(),
+            )
+        };
+
+        let Err(error) = Datapack::from_files(vec![
+            file(
+                "pack.mcmeta",
+                r#"{"pack":{"pack_format":48,"description":""}}"#,
+            ),
+            file("data/foo/loot_table/chests/broken.json", "{"),
+        ]) else {
+            panic!("A malformed loot table was read");
+        };
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with(&format!(
+            "{}: ",
+            Path::new("data/foo/loot_table/chests/broken.json").display()
+        )));
+    }
+
+    #[test]
+    fn test_write_worldgen() {
+        let mut namespace = Namespace::default();
+        namespace.worldgen.biome.insert(
+            nonempty!["overworld".to_string(), "hot".to_string()],
+            serde_json::json!({"temperature": 2.0}),
+        );
+
+        let mut other = Namespace::default();
+        other
+            .worldgen
+            .noise_settings
+            .insert(nonempty!["flat".to_string()], serde_json::json!({}));
+        namespace.merge(other);
+
+        let mut output = MemoryOutput::new();
+        namespace
+            .write_to(Path::new("data/foo"), &mut output)
+            .unwrap();
+
+        assert!(
+            output
+                .get("data/foo/worldgen/biome/overworld/hot.json")
+                .is_some()
+        );
+        assert!(
+            output
+                .get("data/foo/worldgen/noise_settings/flat.json")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_merge_worldgen_conflict() {
+        let path = nonempty!["hot".to_string()];
+
+        let mut namespace = Namespace::default();
+        namespace
+            .worldgen
+            .biome
+            .insert(path.clone(), serde_json::json!({"temperature": 1.0}));
+        namespace
+            .worldgen
+            .structure
+            .insert(path.clone(), serde_json::json!({}));
+
+        let mut other = Namespace::default();
+        other
+            .worldgen
+            .biome
+            .insert(path.clone(), serde_json::json!({"temperature": 2.0}));
+        namespace.merge(other);
+
+        assert_eq!(
+            namespace.worldgen.biome[&path],
+            serde_json::json!({"temperature": 2.0})
+        );
+        assert_eq!(namespace.worldgen.biome.len(), 1);
+        assert!(namespace.worldgen.structure.contains_key(&path));
+    }
+
+    #[test]
+    fn test_write_to_version() {
+        let mut datapack = Datapack::new(26, Value::from("test"));
+        let namespace = datapack.get_namespace_mut("foo");
+        namespace.add_function(
+            &nonempty!["main".to_string()],
+            McFunction::from_iter(["give @s stick[damage=1]".parse().unwrap()]),
+        );
+        namespace.add_tag(
+            TagType::Block,
+            &nonempty!["logs".to_string()],
+            Tag {
+                replace: None,
+                values: vec![],
+            },
+        );
+
+        let mut output = MemoryOutput::new();
+        datapack
+            .write_to_version(GameVersion::new(1, 20, 4), &mut output)
+            .unwrap();
+
+        assert_eq!(
+            output.get_str("data/foo/functions/main.mcfunction"),
+            Some("give @s stick{Damage:1}")
+        );
+        assert!(output.get("data/foo/tags/blocks/logs.json").is_some());
+
+        datapack.get_namespace_mut("foo").add_function(
+            &nonempty!["main".to_string()],
+            McFunction::from_iter(["tick freeze".parse().unwrap()]),
+        );
+
+        assert!(
+            datapack
+                .write_to_version(GameVersion::new(1, 20, 2), &mut MemoryOutput::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_overlays() {
+        let mut datapack = Datapack::new(48, Value::from("test"));
+        datapack.pack.overlays = Some(Overlays {
+            entries: vec![Overlay {
+                directory: "v1_21_2".to_string(),
+                formats: Some(Format::Integer(57)),
+                min_format: None,
+                max_format: None,
+            }],
+        });
+
+        let rotate = McFunction::from_iter(["rotate @s 90 0".parse().unwrap()]);
+        datapack
+            .overlay("v1_21_2")
+            .get_namespace_mut("foo")
+            .add_function(&nonempty!["main".to_string()], rotate.clone());
+
+        let mut output = MemoryOutput::new();
+        datapack
+            .write_to_version(GameVersion::new(1, 21, 0), &mut output)
+            .unwrap();
+        assert_eq!(
+            output.get_str("v1_21_2/data/foo/function/main.mcfunction"),
+            Some("rotate @s 90 0")
+        );
+
+        let files = output
+            .files
+            .into_iter()
+            .map(|(path, content)| {
+                let segments = path
+                    .iter()
+                    .map(|segment| segment.to_string_lossy().into_owned())
+                    .collect();
+
+                (segments, content)
+            })
+            .collect();
+        let read = Datapack::from_files(files).unwrap();
+        assert!(read.other_files.is_empty());
+        assert_eq!(
+            read.overlays["v1_21_2"].namespaces["foo"].functions[&nonempty!["main".to_string()]]
+                .to_string(),
+            "rotate @s 90 0"
+        );
+
+        datapack
+            .overlay("undeclared")
+            .get_namespace_mut("foo")
+            .add_function(&nonempty!["main".to_string()], rotate.clone());
+        assert!(datapack.write_to(&mut MemoryOutput::new()).is_err());
+
+        datapack.overlays.remove("undeclared");
+        datapack.pack.overlays.as_mut().unwrap().entries[0].formats = Some(Format::Integer(48));
+        assert!(datapack.write_to(&mut MemoryOutput::new()).is_err());
+    }
+
+    #[test]
+    fn test_read_undeclared_overlay() {
+        let file = |path: &str, content: &str| {
+            (
+                path.split('/').map(ToString::to_string).collect(),
+                content.as_bytes().to_vec(),
+            )
+        };
+
+        let read = Datapack::from_files(vec![
+            file(
+                "pack.mcmeta",
+                r#"{"pack":{"pack_format":48,"description":""}}"#,
+            ),
+            file(
+                "v1_21_2/data/foo/function/main.mcfunction",
+                "rotate @s 90 0",
+            ),
+        ])
+        .unwrap();
+
+        assert!(read.overlays.is_empty());
+        assert_eq!(
+            read.other_files[&nonempty![
+                "v1_21_2".to_string(),
+                "data".to_string(),
+                "foo".to_string(),
+                "function".to_string(),
+                "main.mcfunction".to_string()
+            ]],
+            b"rotate @s 90 0"
+        );
     }
 }
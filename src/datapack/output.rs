@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A destination for the files of a datapack.
+///
+/// Paths are relative and always use the datapack layout, e.g. `data/foo/function/main.mcfunction`.
+pub trait Output {
+    fn write_file(&mut self, path: &Path, content: &[u8]) -> io::Result<()>;
+}
+
+/// Writes files below a directory on the real filesystem, creating parent directories as needed.
+#[derive(Debug, Clone)]
+pub struct FsOutput {
+    pub root: PathBuf,
+}
+
+impl FsOutput {
+    #[inline]
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> FsOutput {
+        FsOutput { root: root.into() }
+    }
+}
+
+impl Output for FsOutput {
+    fn write_file(&mut self, path: &Path, content: &[u8]) -> io::Result<()> {
+        let file_path = self.root.join(path);
+
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(file_path, content)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryOutput {
+    pub files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryOutput {
+    #[inline]
+    #[must_use]
+    pub fn new() -> MemoryOutput {
+        MemoryOutput::default()
+    }
+
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&[u8]> {
+        self.files.get(path.as_ref()).map(Vec::as_slice)
+    }
+
+    pub fn get_str(&self, path: impl AsRef<Path>) -> Option<&str> {
+        self.get(path)
+            .and_then(|content| std::str::from_utf8(content).ok())
+    }
+}
+
+impl Output for MemoryOutput {
+    fn write_file(&mut self, path: &Path, content: &[u8]) -> io::Result<()> {
+        self.files.insert(path.to_path_buf(), content.to_vec());
+
+        Ok(())
+    }
+}
+
+/// Only records which files would be written and their sizes in bytes.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunOutput {
+    pub files: Vec<(PathBuf, usize)>,
+}
+
+impl DryRunOutput {
+    #[inline]
+    #[must_use]
+    pub fn new() -> DryRunOutput {
+        DryRunOutput::default()
+    }
+}
+
+impl Output for DryRunOutput {
+    fn write_file(&mut self, path: &Path, content: &[u8]) -> io::Result<()> {
+        self.files.push((path.to_path_buf(), content.len()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datapack::Datapack;
+    use nonempty::nonempty;
+    use serde_json::Value;
+
+    #[test]
+    fn test_memory_output() {
+        let mut datapack = Datapack::new(48, Value::from("test"));
+        datapack
+            .get_namespace_mut("foo")
+            .add_function(&nonempty!["main".to_string()], "say hi");
+
+        let mut output = MemoryOutput::new();
+        datapack.write_to(&mut output).unwrap();
+
+        assert!(output.get("pack.mcmeta").is_some());
+        assert_eq!(
+            output.get_str("data/foo/function/main.mcfunction"),
+            Some("say hi")
+        );
+
+        let mut dry_run = DryRunOutput::new();
+        datapack.write_to(&mut dry_run).unwrap();
+
+        let mut paths = dry_run
+            .files
+            .iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        assert_eq!(paths, output.files.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_fs_output() {
+        let directory = std::env::temp_dir().join(format!(
+            "minecraft_command_types_output_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&directory);
+
+        let mut output = FsOutput::new(&directory);
+        output
+            .write_file(Path::new("data/foo/function/a/b.mcfunction"), b"say hi")
+            .unwrap();
+        output
+            .write_file(Path::new("data/foo/function/a/b.mcfunction"), b"say bye")
+            .unwrap();
+
+        let content = fs::read(directory.join("data/foo/function/a/b.mcfunction"));
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(content.unwrap(), b"say bye");
+    }
+}
@@ -1,6 +1,35 @@
 use serde::ser::SerializeTuple;
 use serde::{Serialize, Serializer, de};
 use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A `major.minor` pack format. Before 1.21.9 pack formats only had a major version.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct PackFormat {
+    pub major: i32,
+    pub minor: i32,
+}
+
+impl PackFormat {
+    /// The first data pack format that uses `min_format` and `max_format`.
+    pub const MIN_MAX_FORMAT: i32 = 82;
+
+    #[inline]
+    #[must_use]
+    pub const fn new(major: i32, minor: i32) -> PackFormat {
+        PackFormat { major, minor }
+    }
+}
+
+impl Display for PackFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.minor == i32::MAX || (self.major < Self::MIN_MAX_FORMAT && self.minor == 0) {
+            return write!(f, "{}", self.major);
+        }
+
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum Format {
@@ -8,6 +37,32 @@ pub enum Format {
     Array(i32, i32),
 }
 
+impl Format {
+    /// Interprets this as `min_format`, where an integer means `[major, 0]`.
+    pub fn as_min_format(&self) -> PackFormat {
+        match self {
+            Format::Integer(major) => PackFormat::new(*major, 0),
+            Format::Array(major, minor) => PackFormat::new(*major, *minor),
+        }
+    }
+
+    /// Interprets this as `max_format`, where an integer allows any minor version.
+    pub fn as_max_format(&self) -> PackFormat {
+        match self {
+            Format::Integer(major) => PackFormat::new(*major, i32::MAX),
+            Format::Array(major, minor) => PackFormat::new(*major, *minor),
+        }
+    }
+
+    /// Interprets this as a legacy `supported_formats`/`formats` range of major versions.
+    pub fn as_range(&self) -> (i32, i32) {
+        match self {
+            Format::Integer(format) => (*format, *format),
+            Format::Array(min, max) => (*min, *max),
+        }
+    }
+}
+
 impl Serialize for Format {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -44,6 +99,24 @@ impl<'de> de::Visitor<'de> for FormatVisitor {
         Ok(Format::Integer(i))
     }
 
+    fn visit_i64<E>(self, i: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        i32::try_from(i)
+            .map(Format::Integer)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(i), &self))
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        i32::try_from(u)
+            .map(Format::Integer)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(u), &self))
+    }
+
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: de::SeqAccess<'de>,
@@ -3,6 +3,7 @@ pub mod filter;
 pub mod format;
 pub mod language;
 pub mod overlay;
+pub mod validation;
 
 use crate::datapack::pack::format::Format;
 use serde::{Deserialize, Serialize};
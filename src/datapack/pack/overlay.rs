@@ -5,7 +5,12 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "snake_case")]
 pub struct Overlay {
     pub directory: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub formats: Option<Format>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_format: Option<Format>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_format: Option<Format>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,375 @@
+use crate::datapack::PackMCMeta;
+use crate::datapack::pack::format::{Format, PackFormat};
+use crate::datapack::pack::overlay::Overlay;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PackValidationError {
+    MissingField(String),
+    InvalidRange {
+        field: String,
+        min: PackFormat,
+        max: PackFormat,
+    },
+    PackFormatOutOfRange {
+        pack_format: i32,
+        field: &'static str,
+    },
+    InvalidOverlayDirectory(String),
+    DuplicateOverlayDirectory(String),
+    OverlayOutsidePackRange(String),
+}
+
+impl Display for PackValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackValidationError::MissingField(field) => write!(f, "Missing field '{}'", field),
+            PackValidationError::InvalidRange { field, min, max } => {
+                write!(f, "Invalid range in '{}': {} is above {}", field, min, max)
+            }
+            PackValidationError::PackFormatOutOfRange { pack_format, field } => {
+                write!(f, "pack_format {} is outside of '{}'", pack_format, field)
+            }
+            PackValidationError::InvalidOverlayDirectory(directory) => {
+                write!(f, "Invalid overlay directory '{}'", directory)
+            }
+            PackValidationError::DuplicateOverlayDirectory(directory) => {
+                write!(f, "Duplicate overlay directory '{}'", directory)
+            }
+            PackValidationError::OverlayOutsidePackRange(directory) => {
+                write!(
+                    f,
+                    "Overlay '{}' doesn't apply to any format supported by the pack",
+                    directory
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackValidationError {}
+
+fn legacy_range(format: &Format) -> (PackFormat, PackFormat) {
+    let (min, max) = format.as_range();
+
+    (PackFormat::new(min, 0), PackFormat::new(max, i32::MAX))
+}
+
+/// Checks a `min_format`/`max_format` pair and a legacy `formats` range, returning the range in
+/// effect.
+fn check_range(
+    errors: &mut Vec<PackValidationError>,
+    prefix: &str,
+    min_format: Option<&Format>,
+    max_format: Option<&Format>,
+    legacy_field: &str,
+    legacy: Option<&Format>,
+) -> Option<(PackFormat, PackFormat)> {
+    let mut check = |field: String, min: PackFormat, max: PackFormat| {
+        if min > max {
+            errors.push(PackValidationError::InvalidRange { field, min, max });
+        }
+    };
+
+    let legacy_range = legacy.map(legacy_range);
+    if let Some((min, max)) = legacy_range {
+        check(format!("{}{}", prefix, legacy_field), min, max);
+    }
+
+    let range = match (min_format, max_format) {
+        (Some(min), Some(max)) => {
+            let range = (min.as_min_format(), max.as_max_format());
+            check(format!("{}min_format", prefix), range.0, range.1);
+
+            Some(range)
+        }
+        (Some(_), None) => {
+            errors.push(PackValidationError::MissingField(format!(
+                "{}max_format",
+                prefix
+            )));
+
+            None
+        }
+        (None, Some(_)) => {
+            errors.push(PackValidationError::MissingField(format!(
+                "{}min_format",
+                prefix
+            )));
+
+            None
+        }
+        (None, None) => None,
+    };
+
+    range.or(legacy_range)
+}
+
+impl Overlay {
+    /// Returns the range of pack formats this overlay applies to.
+    pub fn supported_range(&self) -> Option<(PackFormat, PackFormat)> {
+        if let (Some(min), Some(max)) = (&self.min_format, &self.max_format) {
+            return Some((min.as_min_format(), max.as_max_format()));
+        }
+
+        self.formats.as_ref().map(legacy_range)
+    }
+}
+
+fn is_valid_overlay_directory(directory: &str) -> bool {
+    !directory.is_empty()
+        && directory
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-'))
+}
+
+impl PackMCMeta {
+    /// Returns the range of pack formats this pack declares support for.
+    pub fn supported_range(&self) -> Option<(PackFormat, PackFormat)> {
+        let pack = &self.pack;
+
+        if let (Some(min), Some(max)) = (&pack.min_format, &pack.max_format) {
+            return Some((min.as_min_format(), max.as_max_format()));
+        }
+
+        if let Some(supported_formats) = &pack.supported_formats {
+            return Some(legacy_range(supported_formats));
+        }
+
+        pack.pack_format.map(|format| {
+            (
+                PackFormat::new(format, 0),
+                PackFormat::new(format, i32::MAX),
+            )
+        })
+    }
+
+    /// Checks that the declared formats are consistent, returning every problem found.
+    pub fn validate(&self) -> Result<(), Vec<PackValidationError>> {
+        let mut errors = Vec::new();
+        let pack = &self.pack;
+
+        let range = check_range(
+            &mut errors,
+            "pack.",
+            pack.min_format.as_ref(),
+            pack.max_format.as_ref(),
+            "supported_formats",
+            pack.supported_formats.as_ref(),
+        );
+
+        if pack.pack_format.is_none() && pack.min_format.is_none() {
+            errors.push(PackValidationError::MissingField(
+                "pack.pack_format".to_string(),
+            ));
+        }
+
+        // Versions since 1.21.9 only read `min_format` and `max_format`.
+        if pack.min_format.is_none()
+            && pack.max_format.is_none()
+            && let Some((_, max)) = self.supported_range()
+            && max.major >= PackFormat::MIN_MAX_FORMAT
+        {
+            errors.push(PackValidationError::MissingField(
+                "pack.min_format".to_string(),
+            ));
+        }
+
+        // Versions before 1.21.9 only read `pack_format`.
+        if pack.pack_format.is_none()
+            && let Some(min_format) = &pack.min_format
+            && min_format.as_min_format().major < PackFormat::MIN_MAX_FORMAT
+        {
+            errors.push(PackValidationError::MissingField(
+                "pack.pack_format".to_string(),
+            ));
+        }
+
+        if let Some(pack_format) = pack.pack_format {
+            let format = PackFormat::new(pack_format, 0);
+
+            let ranges = [
+                (
+                    "pack.min_format",
+                    pack.min_format
+                        .as_ref()
+                        .zip(pack.max_format.as_ref())
+                        .map(|(min, max)| (min.as_min_format(), max.as_max_format())),
+                ),
+                (
+                    "pack.supported_formats",
+                    pack.supported_formats.as_ref().map(legacy_range),
+                ),
+            ];
+
+            for (field, range) in ranges {
+                if let Some((min, max)) = range
+                    && min.major <= max.major
+                    && !(min.major..=max.major).contains(&format.major)
+                {
+                    errors.push(PackValidationError::PackFormatOutOfRange { pack_format, field });
+                }
+            }
+        }
+
+        if let Some(overlays) = &self.overlays {
+            let mut directories = BTreeSet::new();
+
+            for overlay in &overlays.entries {
+                let directory = &overlay.directory;
+
+                if !is_valid_overlay_directory(directory) {
+                    errors.push(PackValidationError::InvalidOverlayDirectory(
+                        directory.clone(),
+                    ));
+                }
+
+                if !directories.insert(directory) {
+                    errors.push(PackValidationError::DuplicateOverlayDirectory(
+                        directory.clone(),
+                    ));
+                }
+
+                let prefix = format!("overlays.entries[{}].", directory);
+                let overlay_range = check_range(
+                    &mut errors,
+                    &prefix,
+                    overlay.min_format.as_ref(),
+                    overlay.max_format.as_ref(),
+                    "formats",
+                    overlay.formats.as_ref(),
+                );
+
+                let Some((overlay_min, overlay_max)) = overlay_range else {
+                    errors.push(PackValidationError::MissingField(format!(
+                        "{}formats",
+                        prefix
+                    )));
+
+                    continue;
+                };
+
+                if overlay.min_format.is_none()
+                    && overlay.max_format.is_none()
+                    && overlay_max.major >= PackFormat::MIN_MAX_FORMAT
+                {
+                    errors.push(PackValidationError::MissingField(format!(
+                        "{}min_format",
+                        prefix
+                    )));
+                }
+
+                // Versions before 1.21.9 only read `formats`.
+                if overlay.formats.is_none() && overlay_min.major < PackFormat::MIN_MAX_FORMAT {
+                    errors.push(PackValidationError::MissingField(format!(
+                        "{}formats",
+                        prefix
+                    )));
+                }
+
+                if let Some((min, max)) = range
+                    && (overlay_max < min || overlay_min > max)
+                {
+                    errors.push(PackValidationError::OverlayOutsidePackRange(
+                        directory.clone(),
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datapack::Datapack;
+    use crate::datapack::pack::overlay::Overlays;
+    use serde_json::Value;
+
+    fn pack_mcmeta() -> PackMCMeta {
+        Datapack::new(48, Value::from("test")).pack
+    }
+
+    #[test]
+    fn test_valid() {
+        let mut mcmeta = pack_mcmeta();
+        assert_eq!(mcmeta.validate(), Ok(()));
+
+        mcmeta.pack.min_format = Some(Format::Integer(48));
+        mcmeta.pack.max_format = Some(Format::Array(88, 0));
+        mcmeta.pack.supported_formats = Some(Format::Array(48, 88));
+        mcmeta.overlays = Some(Overlays {
+            entries: vec![Overlay {
+                directory: "v1_21_9".to_string(),
+                formats: None,
+                min_format: Some(Format::Integer(88)),
+                max_format: Some(Format::Integer(88)),
+            }],
+        });
+        assert_eq!(mcmeta.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut mcmeta = pack_mcmeta();
+        mcmeta.pack.min_format = Some(Format::Integer(61));
+        mcmeta.pack.max_format = Some(Format::Integer(57));
+        mcmeta.overlays = Some(Overlays {
+            entries: vec![
+                Overlay {
+                    directory: "Old".to_string(),
+                    formats: Some(Format::Array(10, 15)),
+                    min_format: None,
+                    max_format: None,
+                },
+                Overlay {
+                    directory: "new".to_string(),
+                    formats: None,
+                    min_format: None,
+                    max_format: None,
+                },
+            ],
+        });
+
+        let errors = mcmeta.validate().unwrap_err();
+        assert!(errors.contains(&PackValidationError::InvalidRange {
+            field: "pack.min_format".to_string(),
+            min: PackFormat::new(61, 0),
+            max: PackFormat::new(57, i32::MAX),
+        }));
+        assert!(
+            errors.contains(&PackValidationError::InvalidOverlayDirectory(
+                "Old".to_string()
+            ))
+        );
+        assert!(errors.contains(&PackValidationError::MissingField(
+            "overlays.entries[new].formats".to_string()
+        )));
+
+        let mut mcmeta = pack_mcmeta();
+        mcmeta.pack.supported_formats = Some(Format::Array(57, 61));
+        assert_eq!(
+            mcmeta.validate(),
+            Err(vec![PackValidationError::PackFormatOutOfRange {
+                pack_format: 48,
+                field: "pack.supported_formats",
+            }])
+        );
+
+        let mut mcmeta = pack_mcmeta();
+        mcmeta.pack.pack_format = Some(88);
+        assert_eq!(
+            mcmeta.validate(),
+            Err(vec![PackValidationError::MissingField(
+                "pack.min_format".to_string()
+            )])
+        );
+    }
+}
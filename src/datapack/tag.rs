@@ -1,7 +1,8 @@
-use crate::datapack::FilePathNode;
 use crate::resource_location::ResourceLocation;
+use nonempty::NonEmpty;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use strum::{Display, EnumString};
 
 #[derive(
@@ -71,39 +72,98 @@ impl TagType {
 
 #[derive(Debug, Clone, Default)]
 pub struct Worldgen {
-    pub biome: Vec<FilePathNode<Value>>,
-    pub configured_carver: Vec<FilePathNode<Value>>,
-    pub configured_feature: Vec<FilePathNode<Value>>,
-    pub density_function: Vec<FilePathNode<Value>>,
-    pub noise: Vec<FilePathNode<Value>>,
-    pub noise_settings: Vec<FilePathNode<Value>>,
-    pub placed_feature: Vec<FilePathNode<Value>>,
-    pub processor_list: Vec<FilePathNode<Value>>,
-    pub structure: Vec<FilePathNode<Value>>,
-    pub structure_set: Vec<FilePathNode<Value>>,
-    pub template_pool: Vec<FilePathNode<Value>>,
-    pub world_preset: Vec<FilePathNode<Value>>,
-    pub flat_level_generator_preset: Vec<FilePathNode<Value>>,
-    pub multi_noise_biome_source_parameter_list: Vec<FilePathNode<Value>>,
+    pub biome: BTreeMap<NonEmpty<String>, Value>,
+    pub configured_carver: BTreeMap<NonEmpty<String>, Value>,
+    pub configured_feature: BTreeMap<NonEmpty<String>, Value>,
+    pub density_function: BTreeMap<NonEmpty<String>, Value>,
+    pub noise: BTreeMap<NonEmpty<String>, Value>,
+    pub noise_settings: BTreeMap<NonEmpty<String>, Value>,
+    pub placed_feature: BTreeMap<NonEmpty<String>, Value>,
+    pub processor_list: BTreeMap<NonEmpty<String>, Value>,
+    pub structure: BTreeMap<NonEmpty<String>, Value>,
+    pub structure_set: BTreeMap<NonEmpty<String>, Value>,
+    pub template_pool: BTreeMap<NonEmpty<String>, Value>,
+    pub world_preset: BTreeMap<NonEmpty<String>, Value>,
+    pub flat_level_generator_preset: BTreeMap<NonEmpty<String>, Value>,
+    pub multi_noise_biome_source_parameter_list: BTreeMap<NonEmpty<String>, Value>,
 }
 
 impl Worldgen {
-    pub fn merge(&mut self, other: Worldgen) {
-        self.biome.extend(other.biome);
-        self.configured_carver.extend(other.configured_carver);
-        self.configured_feature.extend(other.configured_feature);
-        self.density_function.extend(other.density_function);
-        self.noise.extend(other.noise);
-        self.noise_settings.extend(other.noise_settings);
-        self.placed_feature.extend(other.placed_feature);
-        self.processor_list.extend(other.processor_list);
-        self.structure.extend(other.structure);
-        self.structure_set.extend(other.structure_set);
-        self.template_pool.extend(other.template_pool);
-        self.world_preset.extend(other.world_preset);
+    /// Returns every registry together with its folder name below `worldgen/`.
+    pub fn folders(&self) -> [(&'static str, &BTreeMap<NonEmpty<String>, Value>); 14] {
+        [
+            ("biome", &self.biome),
+            ("configured_carver", &self.configured_carver),
+            ("configured_feature", &self.configured_feature),
+            ("density_function", &self.density_function),
+            ("noise", &self.noise),
+            ("noise_settings", &self.noise_settings),
+            ("placed_feature", &self.placed_feature),
+            ("processor_list", &self.processor_list),
+            ("structure", &self.structure),
+            ("structure_set", &self.structure_set),
+            ("template_pool", &self.template_pool),
+            ("world_preset", &self.world_preset),
+            (
+                "flat_level_generator_preset",
+                &self.flat_level_generator_preset,
+            ),
+            (
+                "multi_noise_biome_source_parameter_list",
+                &self.multi_noise_biome_source_parameter_list,
+            ),
+        ]
+    }
+
+    pub fn folder_mut(
+        &mut self,
+        folder_name: &str,
+    ) -> Option<&mut BTreeMap<NonEmpty<String>, Value>> {
+        Some(match folder_name {
+            "biome" => &mut self.biome,
+            "configured_carver" => &mut self.configured_carver,
+            "configured_feature" => &mut self.configured_feature,
+            "density_function" => &mut self.density_function,
+            "noise" => &mut self.noise,
+            "noise_settings" => &mut self.noise_settings,
+            "placed_feature" => &mut self.placed_feature,
+            "processor_list" => &mut self.processor_list,
+            "structure" => &mut self.structure,
+            "structure_set" => &mut self.structure_set,
+            "template_pool" => &mut self.template_pool,
+            "world_preset" => &mut self.world_preset,
+            "flat_level_generator_preset" => &mut self.flat_level_generator_preset,
+            "multi_noise_biome_source_parameter_list" => {
+                &mut self.multi_noise_biome_source_parameter_list
+            }
+            _ => return None,
+        })
+    }
+
+    pub fn merge(&mut self, mut other: Worldgen) {
+        self.biome.append(&mut other.biome);
+        self.configured_carver.append(&mut other.configured_carver);
+        self.configured_feature
+            .append(&mut other.configured_feature);
+        self.density_function.append(&mut other.density_function);
+        self.noise.append(&mut other.noise);
+        self.noise_settings.append(&mut other.noise_settings);
+        self.placed_feature.append(&mut other.placed_feature);
+        self.processor_list.append(&mut other.processor_list);
+        self.structure.append(&mut other.structure);
+        self.structure_set.append(&mut other.structure_set);
+        self.template_pool.append(&mut other.template_pool);
+        self.world_preset.append(&mut other.world_preset);
         self.flat_level_generator_preset
-            .extend(other.flat_level_generator_preset);
+            .append(&mut other.flat_level_generator_preset);
         self.multi_noise_biome_source_parameter_list
-            .extend(other.multi_noise_biome_source_parameter_list);
+            .append(&mut other.multi_noise_biome_source_parameter_list);
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.folders()
+            .iter()
+            .all(|(_, registry)| registry.is_empty())
     }
 }
@@ -1,14 +1,20 @@
 use crate::command::enums::gamemode::Gamemode;
 use crate::command::enums::sort::Sort;
+use crate::parser::{
+    Parse, ParseError, ParseErrorKind, StringReader, is_allowed_in_unquoted_string, parse_all,
+};
 use crate::range::{FloatRange, IntegerRange};
 use crate::resource_location::ResourceLocation;
-use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use crate::snbt::{SNBT, parse_snbt_compound};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::mem::discriminant;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EntitySelectorVariable {
     /// Selects the nearest player from the command's execution. If there are multiple nearest players, caused by them being precisely the same distance away, the player who most recently joined the server is selected.
     P,
@@ -58,7 +64,8 @@ fn fmt_hash_map<K: Display, V: Display>(
     f.write_str("}")
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdvancementChoiceType {
     Boolean(bool),
     Criterion(BTreeMap<String, bool>),
@@ -85,7 +92,8 @@ impl Display for AdvancementChoiceType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EntitySelectorOption {
     X(NotNan<f32>),
     Y(NotNan<f32>),
@@ -100,12 +108,26 @@ pub enum EntitySelectorOption {
     Tag(bool, String),
     Team(bool, String),
     Name(bool, String),
-    Type(bool, ResourceLocation),
-    Predicate(bool, ResourceLocation),
-    Nbt(bool, SNBT),
+    Type(
+        bool,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Predicate(
+        bool,
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    Nbt(
+        bool,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
     Gamemode(bool, Gamemode),
     Level(IntegerRange),
-    Advancements(BTreeMap<ResourceLocation, AdvancementChoiceType>),
+    Advancements(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        BTreeMap<ResourceLocation, AdvancementChoiceType>,
+    ),
     Limit(i32),
     Sort(Sort),
 }
@@ -167,7 +189,12 @@ impl Display for EntitySelectorOption {
                 write_entity_selector_option!(f, "team", inv, val)
             }
             EntitySelectorOption::Name(inv, val) => {
-                write_entity_selector_option!(f, "name", inv, val)
+                if !val.is_empty() && val.chars().all(is_allowed_in_unquoted_string) {
+                    write_entity_selector_option!(f, "name", inv, val)
+                } else {
+                    let quoted = format!("\"{}\"", val.replace('\\', "\\\\").replace('"', "\\\""));
+                    write_entity_selector_option!(f, "name", inv, quoted)
+                }
             }
             EntitySelectorOption::Type(inv, val) => {
                 write_entity_selector_option!(f, "type", inv, val)
@@ -194,7 +221,9 @@ impl Display for EntitySelectorOption {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[visit(hook = entity_selector)]
 pub enum EntitySelector {
     Variable(EntitySelectorVariable, Vec<EntitySelectorOption>),
     Name(String),
@@ -316,6 +345,218 @@ impl Display for EntitySelector {
     }
 }
 
+fn parse_map<K: Ord, V>(
+    reader: &mut StringReader<'_>,
+    parse_key: impl Fn(&mut StringReader<'_>) -> Result<K, ParseError>,
+    parse_value: impl Fn(&mut StringReader<'_>) -> Result<V, ParseError>,
+) -> Result<BTreeMap<K, V>, ParseError> {
+    let mut map = BTreeMap::new();
+
+    reader.expect('{')?;
+    reader.skip_whitespace();
+
+    if reader.eat('}') {
+        return Ok(map);
+    }
+
+    loop {
+        reader.skip_whitespace();
+        let key = parse_key(reader)?;
+        reader.skip_whitespace();
+        reader.expect('=')?;
+        reader.skip_whitespace();
+        let value = parse_value(reader)?;
+        map.insert(key, value);
+        reader.skip_whitespace();
+
+        if reader.eat('}') {
+            return Ok(map);
+        }
+
+        reader.expect(',')?;
+    }
+}
+
+fn parse_unquoted_key(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    let key = reader.read_unquoted_string();
+
+    if key.is_empty() {
+        return Err(reader.expected("key"));
+    }
+
+    Ok(key.to_string())
+}
+
+fn parse_unquoted_enum<T: FromStr>(
+    reader: &mut StringReader<'_>,
+    what: &'static str,
+) -> Result<T, ParseError> {
+    let start = reader.cursor();
+    let value = reader.read_unquoted_string();
+
+    value
+        .parse()
+        .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidValue(what, value.to_string())))
+}
+
+impl Parse for AdvancementChoiceType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.peek() == Some('{') {
+            parse_map(reader, parse_unquoted_key, bool::parse).map(AdvancementChoiceType::Criterion)
+        } else {
+            reader.read_bool().map(AdvancementChoiceType::Boolean)
+        }
+    }
+}
+
+impl Parse for EntitySelectorOption {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let key = reader.read_unquoted_string();
+
+        if key.is_empty() {
+            return Err(reader.expected("selector option"));
+        }
+
+        reader.skip_whitespace();
+        reader.expect('=')?;
+        reader.skip_whitespace();
+
+        let inverted = matches!(
+            key,
+            "tag" | "team" | "name" | "type" | "predicate" | "nbt" | "gamemode"
+        ) && reader.eat('!');
+
+        Ok(match key {
+            "x" => EntitySelectorOption::X(reader.read_float()?),
+            "y" => EntitySelectorOption::Y(reader.read_float()?),
+            "z" => EntitySelectorOption::Z(reader.read_float()?),
+            "distance" => EntitySelectorOption::Distance(FloatRange::parse(reader)?),
+            "dx" => EntitySelectorOption::DistanceX(reader.read_float()?),
+            "dy" => EntitySelectorOption::DistanceY(reader.read_float()?),
+            "dz" => EntitySelectorOption::DistanceZ(reader.read_float()?),
+            "x_rotation" => EntitySelectorOption::XRotation(FloatRange::parse(reader)?),
+            "y_rotation" => EntitySelectorOption::YRotation(FloatRange::parse(reader)?),
+            "scores" => EntitySelectorOption::Scores(parse_map(
+                reader,
+                parse_unquoted_key,
+                IntegerRange::parse,
+            )?),
+            "tag" => EntitySelectorOption::Tag(inverted, reader.read_unquoted_string().to_string()),
+            "team" => {
+                EntitySelectorOption::Team(inverted, reader.read_unquoted_string().to_string())
+            }
+            "name" => EntitySelectorOption::Name(inverted, reader.read_string()?),
+            "type" => EntitySelectorOption::Type(inverted, ResourceLocation::parse(reader)?),
+            "predicate" => {
+                EntitySelectorOption::Predicate(inverted, ResourceLocation::parse(reader)?)
+            }
+            "nbt" => {
+                EntitySelectorOption::Nbt(inverted, SNBT::Compound(parse_snbt_compound(reader)?))
+            }
+            "gamemode" => {
+                EntitySelectorOption::Gamemode(inverted, parse_unquoted_enum(reader, "gamemode")?)
+            }
+            "level" => EntitySelectorOption::Level(IntegerRange::parse(reader)?),
+            "advancements" => EntitySelectorOption::Advancements(parse_map(
+                reader,
+                ResourceLocation::parse,
+                AdvancementChoiceType::parse,
+            )?),
+            "limit" => EntitySelectorOption::Limit(reader.read_int()?),
+            "sort" => EntitySelectorOption::Sort(parse_unquoted_enum(reader, "sort")?),
+            _ => {
+                return Err(ParseError::new(
+                    start,
+                    ParseErrorKind::UnknownOption(key.to_string()),
+                ));
+            }
+        })
+    }
+}
+
+impl Parse for EntitySelectorVariable {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+
+        Ok(match reader.read() {
+            Some('p') => EntitySelectorVariable::P,
+            Some('r') => EntitySelectorVariable::R,
+            Some('a') => EntitySelectorVariable::A,
+            Some('e') => EntitySelectorVariable::E,
+            Some('s') => EntitySelectorVariable::S,
+            Some('n') => EntitySelectorVariable::N,
+            Some(c) => {
+                return Err(ParseError::new(
+                    start,
+                    ParseErrorKind::InvalidValue("selector type", c.to_string()),
+                ));
+            }
+            None => return Err(reader.error(ParseErrorKind::UnexpectedEndOfInput)),
+        })
+    }
+}
+
+impl Parse for EntitySelector {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if !reader.eat('@') {
+            let name = reader.read_required_word("entity")?;
+
+            return Ok(EntitySelector::Name(name.to_string()));
+        }
+
+        let variable = EntitySelectorVariable::parse(reader)?;
+        let mut options = Vec::new();
+
+        if reader.eat('[') {
+            reader.skip_whitespace();
+
+            if !reader.eat(']') {
+                loop {
+                    reader.skip_whitespace();
+
+                    let start = reader.cursor();
+                    let option = EntitySelectorOption::parse(reader)?;
+
+                    // An option may follow others of the same kind only if every earlier one is repeatable,
+                    // so `tag=a,tag=!b` and `team=!a,team=b` are fine but `team=a,team=!b` is not.
+                    if options.iter().any(|existing: &EntitySelectorOption| {
+                        discriminant(existing) == discriminant(&option)
+                            && !existing.can_be_repeated()
+                    }) {
+                        let key = &reader.input()[start..reader.cursor()];
+                        let key = key.split('=').next().unwrap_or(key).trim();
+
+                        return Err(ParseError::new(
+                            start,
+                            ParseErrorKind::DuplicateOption(key.to_string()),
+                        ));
+                    }
+
+                    options.push(option);
+                    reader.skip_whitespace();
+
+                    if reader.eat(']') {
+                        break;
+                    }
+
+                    reader.expect(',')?;
+                }
+            }
+        }
+
+        Ok(EntitySelector::Variable(variable, options))
+    }
+}
+
+impl FromStr for EntitySelector {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::snbt::SNBTString;
@@ -573,4 +814,80 @@ mod tests {
         assert_eq!(EntitySelector::default(), EntitySelector::s_no_options());
         assert_eq!(EntitySelector::default().to_string(), "@s");
     }
+
+    #[test]
+    fn test_parse_options() {
+        let selector: EntitySelector =
+            "@e[type=!minecraft:pig,scores={a=1..},advancements={x={c=true}},nbt={OnGround:1b},limit=1,sort=nearest]"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            selector.to_string(),
+            "@e[type=!pig, scores={a=1..}, advancements={x={c=true}}, nbt={OnGround:1b}, limit=1, sort=nearest]"
+        );
+        assert_eq!(
+            selector
+                .to_string()
+                .parse::<EntitySelector>()
+                .unwrap()
+                .to_string(),
+            selector.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_names() {
+        assert_eq!(
+            "Steve".parse::<EntitySelector>().unwrap(),
+            EntitySelector::Name("Steve".to_string())
+        );
+        assert_eq!(
+            "@a[name=!\"Foo Bar\"]".parse::<EntitySelector>().unwrap(),
+            EntitySelector::a(vec![EntitySelectorOption::Name(
+                true,
+                "Foo Bar".to_string()
+            )])
+        );
+        assert_eq!(
+            EntitySelector::a(vec![EntitySelectorOption::Name(
+                true,
+                "Foo Bar".to_string()
+            )])
+            .to_string(),
+            "@a[name=!\"Foo Bar\"]"
+        );
+    }
+
+    #[test]
+    fn test_parse_repeated_options() {
+        assert!(
+            "@e[tag=a,tag=!b,type=!pig,type=!cow]"
+                .parse::<EntitySelector>()
+                .is_ok()
+        );
+        assert!("@e[team=!a,team=b]".parse::<EntitySelector>().is_ok());
+
+        let error = "@e[limit=1,limit=2]".parse::<EntitySelector>().unwrap_err();
+        assert_eq!(error.cursor, 11);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::DuplicateOption("limit".to_string())
+        );
+
+        let error = "@e[type=pig,type=!cow]"
+            .parse::<EntitySelector>()
+            .unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::DuplicateOption("type".to_string())
+        );
+
+        let error = "@e[colour=red]".parse::<EntitySelector>().unwrap_err();
+        assert_eq!(error.cursor, 3);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::UnknownOption("colour".to_string())
+        );
+    }
 }
@@ -0,0 +1,706 @@
+//! Static analysis of `execute` chains: the context the final command runs in, and steps that
+//! have no effect or are likely mistakes.
+
+use crate::command::enums::axis::Axis;
+use crate::command::enums::entity_anchor::EntityAnchor;
+use crate::command::enums::relation::Relation;
+use crate::command::enums::sort::Sort;
+use crate::command::execute::{
+    ExecuteIfSubcommand, ExecuteStoreSubcommand, ExecuteSubcommand, Facing, Positioned, Rotated,
+};
+use crate::command::execute::{if_next, store_next};
+use crate::coordinate::Coordinates;
+use crate::entity_selector::{EntitySelector, EntitySelectorOption, EntitySelectorVariable};
+use crate::resource_location::ResourceLocation;
+use crate::visit::{Visit, Visitor};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// A part of the execution context.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum ContextComponent {
+    Executor,
+    Position,
+    Rotation,
+    Dimension,
+    Anchor,
+}
+
+impl ContextComponent {
+    const ALL: [ContextComponent; 5] = [
+        ContextComponent::Executor,
+        ContextComponent::Position,
+        ContextComponent::Rotation,
+        ContextComponent::Dimension,
+        ContextComponent::Anchor,
+    ];
+}
+
+impl Display for ContextComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextComponent::Executor => "executor",
+            ContextComponent::Position => "position",
+            ContextComponent::Rotation => "rotation",
+            ContextComponent::Dimension => "dimension",
+            ContextComponent::Anchor => "anchor",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ExecutorSource {
+    As(EntitySelector),
+    On(Relation),
+    Summon(ResourceLocation),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PositionSource {
+    At(EntitySelector),
+    Positioned(Positioned),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RotationSource {
+    At(EntitySelector),
+    Rotated(Rotated),
+    Facing(Facing),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DimensionSource {
+    At(EntitySelector),
+    In(ResourceLocation),
+}
+
+/// A subcommand of a chain, without the subcommands after it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExecuteStep {
+    /// The index of the subcommand in the chain.
+    pub index: usize,
+    pub subcommand: String,
+}
+
+impl Display for ExecuteStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.subcommand)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ExecuteIssue {
+    /// A step that doesn't change the context, like `positioned ~ ~ ~`.
+    Redundant(ExecuteStep),
+    /// Parts of the context set by `step` are set again by `by` before anything uses them.
+    Overridden {
+        step: ExecuteStep,
+        by: ExecuteStep,
+        components: BTreeSet<ContextComponent>,
+    },
+    /// `@s` is used after `step` moved to other entities, but no step changed the executor, so
+    /// `@s` is still the caller.
+    SelfAfterMove {
+        step: ExecuteStep,
+        usage: ExecuteStep,
+    },
+}
+
+impl Display for ExecuteIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecuteIssue::Redundant(step) => write!(f, "{} has no effect", step),
+            ExecuteIssue::Overridden {
+                step,
+                by,
+                components,
+            } => {
+                let components = components
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(
+                    f,
+                    "The {} set by {} is overridden by {} before it is used",
+                    components, step, by
+                )
+            }
+            ExecuteIssue::SelfAfterMove { step, usage } => write!(
+                f,
+                "`@s` in {} is the caller, not the entities of {}; use `as` to change the executor",
+                usage, step
+            ),
+        }
+    }
+}
+
+/// The context the final command of an `execute` chain runs in. Parts that are `None` are the
+/// ones of the caller.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ExecuteAnalysis {
+    pub executor: Option<ExecutorSource>,
+    pub position: Option<PositionSource>,
+    pub rotation: Option<RotationSource>,
+    pub dimension: Option<DimensionSource>,
+    pub anchor: Option<EntityAnchor>,
+    /// The axes the position was aligned on since it was last set.
+    pub alignment: BTreeSet<Axis>,
+    /// Whether a step may run the rest of the chain for several entities.
+    pub fans_out: bool,
+    pub issues: Vec<ExecuteIssue>,
+}
+
+/// The parts of the context a step depends on.
+#[derive(Debug, Default)]
+struct Reads {
+    components: BTreeSet<ContextComponent>,
+    uses_self: bool,
+}
+
+impl Reads {
+    fn all() -> Self {
+        Self {
+            components: ContextComponent::ALL.into(),
+            uses_self: false,
+        }
+    }
+
+    fn read(&mut self, components: &[ContextComponent]) {
+        self.components.extend(components);
+    }
+}
+
+impl Visitor for Reads {
+    fn visit_entity_selector(&mut self, selector: &EntitySelector) {
+        if let EntitySelector::Variable(variable, options) = selector {
+            if *variable == EntitySelectorVariable::S {
+                self.uses_self = true;
+                self.read(&[ContextComponent::Executor]);
+            }
+
+            if selector_uses_position(variable, options) {
+                self.read(&[ContextComponent::Position, ContextComponent::Dimension]);
+            }
+        }
+
+        selector.visit_children(self);
+    }
+
+    fn visit_coordinates(&mut self, coordinates: &Coordinates) {
+        match coordinates {
+            Coordinates::World(x, y, z) => {
+                if x.relative || y.relative || z.relative {
+                    self.read(&[ContextComponent::Position]);
+                }
+            }
+            Coordinates::Local(..) => self.read(&[
+                ContextComponent::Position,
+                ContextComponent::Rotation,
+                ContextComponent::Anchor,
+            ]),
+        }
+
+        self.read(&[ContextComponent::Dimension]);
+    }
+}
+
+fn selector_uses_position(
+    variable: &EntitySelectorVariable,
+    options: &[EntitySelectorOption],
+) -> bool {
+    matches!(
+        variable,
+        EntitySelectorVariable::P | EntitySelectorVariable::N
+    ) || options.iter().any(|option| {
+        matches!(
+            option,
+            EntitySelectorOption::Distance(_)
+                | EntitySelectorOption::DistanceX(_)
+                | EntitySelectorOption::DistanceY(_)
+                | EntitySelectorOption::DistanceZ(_)
+                | EntitySelectorOption::Sort(Sort::Nearest | Sort::Furthest)
+        )
+    })
+}
+
+/// Whether a selector may select more than one entity.
+fn selects_many(selector: &EntitySelector) -> bool {
+    match selector {
+        EntitySelector::Name(_) => false,
+        EntitySelector::Variable(
+            EntitySelectorVariable::S
+            | EntitySelectorVariable::P
+            | EntitySelectorVariable::R
+            | EntitySelectorVariable::N,
+            _,
+        ) => false,
+        EntitySelector::Variable(_, options) => !options
+            .iter()
+            .any(|option| matches!(option, EntitySelectorOption::Limit(limit) if *limit <= 1)),
+    }
+}
+
+fn is_self(selector: &EntitySelector) -> bool {
+    matches!(
+        selector,
+        EntitySelector::Variable(EntitySelectorVariable::S, _)
+    )
+}
+
+/// Whether a step selects entities, so it may run the rest of the chain several times or not at
+/// all. Such a step is never redundant or overridden.
+fn forks_or_fails(subcommand: &ExecuteSubcommand) -> bool {
+    matches!(
+        subcommand,
+        ExecuteSubcommand::As(..)
+            | ExecuteSubcommand::At(..)
+            | ExecuteSubcommand::On(..)
+            | ExecuteSubcommand::Positioned(Positioned::As(_), _)
+            | ExecuteSubcommand::Rotated(Rotated::As(_), _)
+            | ExecuteSubcommand::Facing(Facing::Entity(..), _)
+    )
+}
+
+fn next_step(subcommand: &ExecuteSubcommand) -> Option<&ExecuteSubcommand> {
+    match subcommand {
+        ExecuteSubcommand::Align(_, next)
+        | ExecuteSubcommand::Anchored(_, next)
+        | ExecuteSubcommand::As(_, next)
+        | ExecuteSubcommand::At(_, next)
+        | ExecuteSubcommand::Facing(_, next)
+        | ExecuteSubcommand::In(_, next)
+        | ExecuteSubcommand::On(_, next)
+        | ExecuteSubcommand::Positioned(_, next)
+        | ExecuteSubcommand::Rotated(_, next)
+        | ExecuteSubcommand::Summon(_, next) => Some(next),
+        ExecuteSubcommand::If(_, condition) => if_next(condition),
+        ExecuteSubcommand::Store(_, store) => Some(store_next(store)),
+        ExecuteSubcommand::Run(_) => None,
+    }
+}
+
+fn step(index: usize, subcommand: &ExecuteSubcommand) -> ExecuteStep {
+    let text = subcommand.to_string();
+    let text = match next_step(subcommand) {
+        Some(next) => text
+            .strip_suffix(&next.to_string())
+            .map_or(text.as_str(), str::trim_end)
+            .to_string(),
+        None => text,
+    };
+
+    ExecuteStep {
+        index,
+        subcommand: text,
+    }
+}
+
+/// What a step reads from the context, not counting the steps after it.
+fn step_reads(subcommand: &ExecuteSubcommand) -> Reads {
+    let mut reads = Reads::default();
+
+    match subcommand {
+        ExecuteSubcommand::Align(..) => reads.read(&[ContextComponent::Position]),
+        ExecuteSubcommand::Anchored(..) | ExecuteSubcommand::In(..) => {}
+        ExecuteSubcommand::As(selector, _)
+        | ExecuteSubcommand::At(selector, _)
+        | ExecuteSubcommand::Positioned(Positioned::As(selector), _)
+        | ExecuteSubcommand::Rotated(Rotated::As(selector), _) => selector.visit(&mut reads),
+        ExecuteSubcommand::Facing(facing, _) => {
+            facing.visit(&mut reads);
+            reads.read(&[ContextComponent::Position, ContextComponent::Anchor]);
+        }
+        ExecuteSubcommand::On(..) => reads.read(&[ContextComponent::Executor]),
+        ExecuteSubcommand::Positioned(Positioned::Position(coordinates), _) => {
+            coordinates.visit(&mut reads)
+        }
+        ExecuteSubcommand::Positioned(Positioned::Over(_), _) | ExecuteSubcommand::Summon(..) => {
+            reads.read(&[ContextComponent::Position, ContextComponent::Dimension])
+        }
+        ExecuteSubcommand::Rotated(Rotated::Rotation(rotation), _) => {
+            if rotation.0.relative || rotation.1.relative {
+                reads.read(&[ContextComponent::Rotation]);
+            }
+        }
+        ExecuteSubcommand::If(_, condition) => match condition {
+            ExecuteIfSubcommand::Function(..) | ExecuteIfSubcommand::Predicate(..) => {
+                reads = Reads::all()
+            }
+            ExecuteIfSubcommand::Dimension(..) => reads.read(&[ContextComponent::Dimension]),
+            ExecuteIfSubcommand::Loaded(..) => {
+                reads.read(&[ContextComponent::Position, ContextComponent::Dimension])
+            }
+            condition => without_next(condition).visit(&mut reads),
+        },
+        ExecuteSubcommand::Store(_, store) => match store {
+            ExecuteStoreSubcommand::Data(target, ..) => target.visit(&mut reads),
+            ExecuteStoreSubcommand::Score(score, _) => score.visit(&mut reads),
+            ExecuteStoreSubcommand::Bossbar(..) => {}
+        },
+        // The final command may use any part of the context.
+        ExecuteSubcommand::Run(command) => {
+            reads = Reads::all();
+            command.visit(&mut reads);
+        }
+    }
+
+    reads
+}
+
+fn without_next(condition: &ExecuteIfSubcommand) -> ExecuteIfSubcommand {
+    let mut condition = condition.clone();
+
+    match &mut condition {
+        ExecuteIfSubcommand::Biome(.., next)
+        | ExecuteIfSubcommand::Block(.., next)
+        | ExecuteIfSubcommand::Blocks(.., next)
+        | ExecuteIfSubcommand::Data(.., next)
+        | ExecuteIfSubcommand::Dimension(.., next)
+        | ExecuteIfSubcommand::Entity(.., next)
+        | ExecuteIfSubcommand::Items(.., next)
+        | ExecuteIfSubcommand::Loaded(.., next)
+        | ExecuteIfSubcommand::Predicate(.., next)
+        | ExecuteIfSubcommand::Score(.., next)
+        | ExecuteIfSubcommand::Stopwatch(.., next) => *next = None,
+        // Its `next` is required, but `step_reads` doesn't need to visit it.
+        ExecuteIfSubcommand::Function(..) => {}
+    }
+
+    condition
+}
+
+/// The parts of the context a step sets, or `None` if it doesn't change anything in `analysis`.
+fn step_sets(
+    subcommand: &ExecuteSubcommand,
+    analysis: &ExecuteAnalysis,
+) -> Option<&'static [ContextComponent]> {
+    use ContextComponent::*;
+
+    Some(match subcommand {
+        ExecuteSubcommand::As(EntitySelector::Variable(EntitySelectorVariable::S, options), _)
+            if options.is_empty() =>
+        {
+            return None;
+        }
+        ExecuteSubcommand::As(..) | ExecuteSubcommand::On(..) | ExecuteSubcommand::Summon(..) => {
+            &[Executor]
+        }
+        ExecuteSubcommand::At(..) => &[Position, Rotation, Dimension],
+        ExecuteSubcommand::Positioned(Positioned::Position(Coordinates::World(x, y, z)), _)
+            if [x, y, z].iter().all(|coordinate| {
+                coordinate.relative && coordinate.value.is_none_or(|v| v.into_inner() == 0.0)
+            }) && analysis.anchor == Some(EntityAnchor::Feet) =>
+        {
+            return None;
+        }
+        // `positioned` also resets the anchor to the feet.
+        ExecuteSubcommand::Positioned(Positioned::Position(_), _) => &[Position, Anchor],
+        ExecuteSubcommand::Positioned(..) => &[Position],
+        ExecuteSubcommand::Align(axes, _) if axes.is_subset(&analysis.alignment) => return None,
+        ExecuteSubcommand::Align(..) => &[Position],
+        ExecuteSubcommand::Rotated(Rotated::Rotation(rotation), _)
+            if [&rotation.0, &rotation.1].iter().all(|angle| {
+                angle.relative && angle.value.is_none_or(|v| v.into_inner() == 0.0)
+            }) =>
+        {
+            return None;
+        }
+        ExecuteSubcommand::Rotated(..) | ExecuteSubcommand::Facing(..) => &[Rotation],
+        ExecuteSubcommand::Anchored(anchor, _) if analysis.anchor == Some(*anchor) => return None,
+        ExecuteSubcommand::Anchored(..) => &[Anchor],
+        ExecuteSubcommand::In(dimension, _)
+            if analysis.dimension == Some(DimensionSource::In(dimension.clone())) =>
+        {
+            return None;
+        }
+        ExecuteSubcommand::In(..) => &[Dimension],
+        ExecuteSubcommand::If(..) | ExecuteSubcommand::Store(..) | ExecuteSubcommand::Run(_) => &[],
+    })
+}
+
+impl ExecuteAnalysis {
+    /// Records what `subcommand` changes in the context.
+    fn apply(&mut self, subcommand: &ExecuteSubcommand) {
+        match subcommand {
+            ExecuteSubcommand::As(selector, _) => {
+                self.executor = Some(ExecutorSource::As(selector.clone()));
+                self.fans_out |= selects_many(selector);
+            }
+            ExecuteSubcommand::On(relation, _) => {
+                self.executor = Some(ExecutorSource::On(*relation));
+                self.fans_out |= *relation == Relation::Passengers;
+            }
+            ExecuteSubcommand::Summon(entity, _) => {
+                self.executor = Some(ExecutorSource::Summon(entity.clone()));
+            }
+            ExecuteSubcommand::At(selector, _) => {
+                self.position = Some(PositionSource::At(selector.clone()));
+                self.rotation = Some(RotationSource::At(selector.clone()));
+                self.dimension = Some(DimensionSource::At(selector.clone()));
+                self.alignment.clear();
+                self.fans_out |= selects_many(selector);
+            }
+            ExecuteSubcommand::Positioned(positioned, _) => {
+                if let Positioned::As(selector) = positioned {
+                    self.fans_out |= selects_many(selector);
+                } else if let Positioned::Position(_) = positioned {
+                    self.anchor = Some(EntityAnchor::Feet);
+                }
+
+                self.position = Some(PositionSource::Positioned(positioned.clone()));
+                self.alignment.clear();
+            }
+            ExecuteSubcommand::Align(axes, _) => self.alignment.extend(axes),
+            ExecuteSubcommand::Rotated(rotated, _) => {
+                if let Rotated::As(selector) = rotated {
+                    self.fans_out |= selects_many(selector);
+                }
+
+                self.rotation = Some(RotationSource::Rotated(rotated.clone()));
+            }
+            ExecuteSubcommand::Facing(facing, _) => {
+                if let Facing::Entity(selector, _) = facing {
+                    self.fans_out |= selects_many(selector);
+                }
+
+                self.rotation = Some(RotationSource::Facing(facing.clone()));
+            }
+            ExecuteSubcommand::Anchored(anchor, _) => self.anchor = Some(*anchor),
+            ExecuteSubcommand::In(dimension, _) => {
+                self.dimension = Some(DimensionSource::In(dimension.clone()));
+            }
+            ExecuteSubcommand::If(..)
+            | ExecuteSubcommand::Store(..)
+            | ExecuteSubcommand::Run(_) => {}
+        }
+    }
+}
+
+impl ExecuteSubcommand {
+    /// Analyzes this chain: the context its final command runs in, and issues like steps that
+    /// are overridden before anything uses what they set.
+    ///
+    /// Only the context inside the chain is known, so a step is never reported as overridden when
+    /// a later step might read what it set. Steps that select entities are never reported either,
+    /// since they decide how many times the rest of the chain runs.
+    #[must_use]
+    pub fn analyze(&self) -> ExecuteAnalysis {
+        let mut analysis = ExecuteAnalysis::default();
+        // The step whose value of each component wasn't used yet.
+        let mut unused: [Option<ExecuteStep>; 5] = Default::default();
+        let mut moved_to: Option<ExecuteStep> = None;
+        let mut has_executor = false;
+
+        let mut current = Some(self);
+        let mut index = 0;
+
+        while let Some(subcommand) = current {
+            let this = step(index, subcommand);
+            let reads = step_reads(subcommand);
+
+            for component in &reads.components {
+                unused[*component as usize] = None;
+            }
+
+            if reads.uses_self
+                && !has_executor
+                && let Some(moved) = moved_to.take()
+            {
+                analysis.issues.push(ExecuteIssue::SelfAfterMove {
+                    step: moved,
+                    usage: this.clone(),
+                });
+            }
+
+            let conditional = forks_or_fails(subcommand);
+
+            match step_sets(subcommand, &analysis) {
+                None if conditional => {}
+                None => analysis.issues.push(ExecuteIssue::Redundant(this.clone())),
+                Some(components) => {
+                    for component in components {
+                        let unused = &mut unused[*component as usize];
+                        let previous = if conditional {
+                            unused.take()
+                        } else {
+                            unused.replace(this.clone())
+                        };
+
+                        if let Some(previous) = previous {
+                            add_overridden(
+                                &mut analysis.issues,
+                                previous,
+                                this.clone(),
+                                *component,
+                            );
+                        }
+                    }
+
+                    analysis.apply(subcommand);
+                }
+            }
+
+            match subcommand {
+                ExecuteSubcommand::As(..)
+                | ExecuteSubcommand::On(..)
+                | ExecuteSubcommand::Summon(..) => has_executor = true,
+                ExecuteSubcommand::At(selector, _)
+                | ExecuteSubcommand::Positioned(Positioned::As(selector), _)
+                    if !is_self(selector) =>
+                {
+                    moved_to = Some(this);
+                }
+                _ => {}
+            }
+
+            current = next_step(subcommand);
+            index += 1;
+        }
+
+        analysis
+    }
+}
+
+fn add_overridden(
+    issues: &mut Vec<ExecuteIssue>,
+    step: ExecuteStep,
+    by: ExecuteStep,
+    component: ContextComponent,
+) {
+    for issue in issues.iter_mut() {
+        if let ExecuteIssue::Overridden {
+            step: existing_step,
+            by: existing_by,
+            components,
+        } = issue
+            && existing_step.index == step.index
+            && existing_by.index == by.index
+        {
+            components.insert(component);
+            return;
+        }
+    }
+
+    issues.push(ExecuteIssue::Overridden {
+        step,
+        by,
+        components: [component].into(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+
+    fn analyze(command: &str) -> ExecuteAnalysis {
+        let Ok(Command::Execute(subcommand)) = command.parse() else {
+            panic!("not an execute command: {}", command);
+        };
+
+        subcommand.analyze()
+    }
+
+    fn issues(command: &str) -> Vec<String> {
+        analyze(command)
+            .issues
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_context() {
+        let analysis = analyze(
+            "execute in minecraft:the_nether as @e[type=zombie] positioned as @s anchored eyes \
+             align xz facing ^ ^ ^1 run say hi",
+        );
+
+        assert_eq!(
+            analysis.executor,
+            Some(ExecutorSource::As("@e[type=zombie]".parse().unwrap()))
+        );
+        assert_eq!(
+            analysis.position,
+            Some(PositionSource::Positioned(Positioned::As(
+                EntitySelector::s_no_options()
+            )))
+        );
+        assert!(matches!(analysis.rotation, Some(RotationSource::Facing(_))));
+        assert_eq!(
+            analysis.dimension,
+            Some(DimensionSource::In("minecraft:the_nether".parse().unwrap()))
+        );
+        assert_eq!(analysis.anchor, Some(EntityAnchor::Eyes));
+        assert_eq!(analysis.alignment, [Axis::X, Axis::Z].into());
+        assert!(analysis.fans_out);
+        assert!(analysis.issues.is_empty(), "{:?}", analysis.issues);
+
+        let analysis = analyze("execute as @p on vehicle positioned 0 64 0 run say hi");
+        assert_eq!(
+            analysis.executor,
+            Some(ExecutorSource::On(Relation::Vehicle))
+        );
+        assert_eq!(analysis.anchor, Some(EntityAnchor::Feet));
+        assert!(!analysis.fans_out);
+    }
+
+    #[test]
+    fn test_issues() {
+        assert_eq!(
+            issues("execute positioned ~ ~1 ~ at @s run say hi"),
+            ["The position set by `positioned ~ ~1 ~` is overridden by `at @s` before it is used"]
+        );
+        assert_eq!(
+            issues("execute positioned ~ ~1 ~ at @p run say hi"),
+            [] as [&str; 0]
+        );
+        assert_eq!(
+            issues("execute at @e[tag=a] at @e[tag=b] run say hi"),
+            [] as [&str; 0]
+        );
+        assert_eq!(
+            issues("execute as @e[tag=a] on vehicle as @e[tag=b] run say hi"),
+            [] as [&str; 0]
+        );
+        assert_eq!(
+            issues(
+                "execute as @s positioned ~ ~ ~ rotated ~ ~ anchored feet anchored feet run say hi"
+            ),
+            [
+                "`rotated ~ ~` has no effect",
+                "`anchored feet` has no effect",
+                "`anchored feet` has no effect",
+            ]
+        );
+        assert_eq!(
+            issues("execute anchored feet positioned ~ ~ ~ run say hi"),
+            ["`positioned ~ ~ ~` has no effect"]
+        );
+        assert_eq!(
+            issues("execute positioned ~ ~ ~ run say hi"),
+            [] as [&str; 0]
+        );
+        assert_eq!(
+            issues("execute anchored eyes positioned ~ ~ ~ run say hi"),
+            [
+                "The anchor set by `anchored eyes` is overridden by `positioned ~ ~ ~` before it \
+                 is used"
+            ]
+        );
+        assert_eq!(
+            issues("execute at @e[type=marker] run kill @s"),
+            [
+                "`@s` in `run kill @s` is the caller, not the entities of `at @e[type=marker]`; \
+                 use `as` to change the executor"
+            ]
+        );
+        assert_eq!(
+            issues("execute as @a at @s if entity @s[tag=x] run tp @s ~ ~1 ~"),
+            [] as [&str; 0]
+        );
+    }
+}
@@ -4,6 +4,16 @@ use std::collections::{BTreeMap, BTreeSet};
 pub trait HasMacro {
     fn has_macro(&self) -> bool;
     fn has_macro_conflict(&self) -> bool;
+
+    /// Adds the name of every `$(name)` slot to `variables`.
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>);
+
+    #[must_use]
+    fn macro_variables(&self) -> BTreeSet<String> {
+        let mut variables = BTreeSet::new();
+        self.collect_macro_variables(&mut variables);
+        variables
+    }
 }
 
 #[macro_export]
@@ -15,6 +25,8 @@ macro_rules! impl_has_macro_false {
                 fn has_macro(&self) -> bool { false }
 
                 fn has_macro_conflict(&self) -> bool { false }
+
+                fn collect_macro_variables(&self, _: &mut ::std::collections::BTreeSet<::std::string::String>) {}
             }
         )*
     };
@@ -39,6 +51,11 @@ impl<A: HasMacro, B: HasMacro> HasMacro for (A, B) {
     fn has_macro_conflict(&self) -> bool {
         self.0.has_macro_conflict() || self.1.has_macro_conflict()
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.0.collect_macro_variables(variables);
+        self.1.collect_macro_variables(variables);
+    }
 }
 
 impl<T: HasMacro> HasMacro for Option<T> {
@@ -51,6 +68,12 @@ impl<T: HasMacro> HasMacro for Option<T> {
             .map(|t| t.has_macro_conflict())
             .unwrap_or(false)
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        if let Some(t) = self {
+            t.collect_macro_variables(variables);
+        }
+    }
 }
 
 impl<T: HasMacro> HasMacro for Vec<T> {
@@ -61,6 +84,11 @@ impl<T: HasMacro> HasMacro for Vec<T> {
     fn has_macro_conflict(&self) -> bool {
         self.iter().any(|t| t.has_macro_conflict())
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.iter()
+            .for_each(|t| t.collect_macro_variables(variables));
+    }
 }
 
 impl<T: HasMacro> HasMacro for NonEmpty<T> {
@@ -71,6 +99,11 @@ impl<T: HasMacro> HasMacro for NonEmpty<T> {
     fn has_macro_conflict(&self) -> bool {
         self.iter().any(|t| t.has_macro_conflict())
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.iter()
+            .for_each(|t| t.collect_macro_variables(variables));
+    }
 }
 
 impl<T: HasMacro> HasMacro for Box<T> {
@@ -81,6 +114,10 @@ impl<T: HasMacro> HasMacro for Box<T> {
     fn has_macro_conflict(&self) -> bool {
         self.as_ref().has_macro_conflict()
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.as_ref().collect_macro_variables(variables);
+    }
 }
 
 impl<K, V: HasMacro> HasMacro for BTreeMap<K, V> {
@@ -91,6 +128,11 @@ impl<K, V: HasMacro> HasMacro for BTreeMap<K, V> {
     fn has_macro_conflict(&self) -> bool {
         self.values().any(|t| t.has_macro_conflict())
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.values()
+            .for_each(|t| t.collect_macro_variables(variables));
+    }
 }
 
 impl<T: HasMacro> HasMacro for BTreeSet<T> {
@@ -101,4 +143,9 @@ impl<T: HasMacro> HasMacro for BTreeSet<T> {
     fn has_macro_conflict(&self) -> bool {
         self.iter().any(|t| t.has_macro_conflict())
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        self.iter()
+            .for_each(|t| t.collect_macro_variables(variables));
+    }
 }
@@ -1,13 +1,31 @@
+use crate::has_macro::HasMacro;
+use crate::nbt_path::SNBTCompound;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
 use crate::resource_location::ResourceLocation;
-use crate::snbt::SNBT;
-use minecraft_command_types_derive::HasMacro;
+use crate::snbt::{SNBT, SNBTString};
+use crate::text::TextComponent;
+use crate::version::{GameVersion, VersionError};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemTest {
-    Component(ResourceLocation),
-    ComponentMatches(ResourceLocation, SNBT),
-    Predicate(ResourceLocation, SNBT),
+    Component(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+    ComponentMatches(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
+    Predicate(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
 }
 
 impl Display for ItemTest {
@@ -20,9 +38,13 @@ impl Display for ItemTest {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemType {
-    ResourceLocation(ResourceLocation),
+    ResourceLocation(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
     Wildcard,
 }
 
@@ -35,7 +57,8 @@ impl Display for ItemType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrGroup(pub Vec<(bool, ItemTest)>);
 
 impl Display for OrGroup {
@@ -55,7 +78,8 @@ impl Display for OrGroup {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemPredicate {
     pub id: ItemType,
     pub tests: Vec<OrGroup>,
@@ -95,10 +119,28 @@ impl ItemPredicate {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemComponent {
-    KeyValue(ResourceLocation, SNBT),
-    Remove(ResourceLocation),
+    KeyValue(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBT,
+    ),
+    Remove(
+        #[cfg_attr(feature = "serde", serde(with = "crate::resource_location_serde"))]
+        ResourceLocation,
+    ),
+}
+
+impl ItemComponent {
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> &ResourceLocation {
+        match self {
+            ItemComponent::KeyValue(id, _) | ItemComponent::Remove(id) => id,
+        }
+    }
 }
 
 impl Display for ItemComponent {
@@ -112,7 +154,8 @@ impl Display for ItemComponent {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemStack {
     pub id: ItemType,
     pub components: Vec<ItemComponent>,
@@ -142,3 +185,354 @@ impl Display for ItemStack {
         Ok(())
     }
 }
+
+impl Parse for ItemType {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.eat('*') {
+            Ok(ItemType::Wildcard)
+        } else {
+            ResourceLocation::parse(reader).map(ItemType::ResourceLocation)
+        }
+    }
+}
+
+impl Parse for ItemTest {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let id = ResourceLocation::parse(reader)?;
+
+        reader.skip_whitespace();
+
+        if reader.eat('=') {
+            reader.skip_whitespace();
+            Ok(ItemTest::ComponentMatches(id, SNBT::parse(reader)?))
+        } else if reader.eat('~') {
+            reader.skip_whitespace();
+            Ok(ItemTest::Predicate(id, SNBT::parse(reader)?))
+        } else {
+            Ok(ItemTest::Component(id))
+        }
+    }
+}
+
+impl Parse for OrGroup {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let mut tests = Vec::new();
+
+        loop {
+            reader.skip_whitespace();
+
+            let negated = reader.eat('!');
+            tests.push((negated, ItemTest::parse(reader)?));
+
+            reader.skip_whitespace();
+
+            if !reader.eat('|') {
+                return Ok(OrGroup(tests));
+            }
+        }
+    }
+}
+
+impl Parse for ItemPredicate {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let mut predicate = ItemPredicate::new(ItemType::parse(reader)?);
+
+        if !reader.eat('[') {
+            return Ok(predicate);
+        }
+
+        reader.skip_whitespace();
+
+        if reader.eat(']') {
+            return Ok(predicate);
+        }
+
+        loop {
+            predicate.tests.push(OrGroup::parse(reader)?);
+
+            reader.skip_whitespace();
+
+            if reader.eat(']') {
+                return Ok(predicate);
+            }
+
+            reader.expect(',')?;
+        }
+    }
+}
+
+impl Parse for ItemComponent {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        if reader.eat('!') {
+            return ResourceLocation::parse(reader).map(ItemComponent::Remove);
+        }
+
+        let id = ResourceLocation::parse(reader)?;
+
+        reader.skip_whitespace();
+        reader.expect('=')?;
+        reader.skip_whitespace();
+
+        Ok(ItemComponent::KeyValue(id, SNBT::parse(reader)?))
+    }
+}
+
+impl Parse for ItemStack {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let id = ItemType::parse(reader)?;
+
+        // Unlike predicates, a stack has to name one concrete item.
+        if matches!(&id, ItemType::Wildcard)
+            || matches!(&id, ItemType::ResourceLocation(id) if id.is_tag)
+        {
+            return Err(ParseError::new(
+                start,
+                ParseErrorKind::InvalidValue("item", id.to_string()),
+            ));
+        }
+        let mut components: Vec<ItemComponent> = Vec::new();
+
+        if reader.eat('[') {
+            reader.skip_whitespace();
+
+            if !reader.eat(']') {
+                loop {
+                    reader.skip_whitespace();
+
+                    let key_start = reader.cursor() + usize::from(reader.peek() == Some('!'));
+                    let component = ItemComponent::parse(reader)?;
+                    let id = component.id();
+
+                    if components.iter().any(|other| other.id() == id) {
+                        return Err(ParseError::new(
+                            key_start,
+                            ParseErrorKind::RepeatedKey("item component", id.to_string()),
+                        ));
+                    }
+
+                    components.push(component);
+                    reader.skip_whitespace();
+
+                    if reader.eat(']') {
+                        break;
+                    }
+
+                    reader.expect(',')?;
+                }
+            }
+        }
+
+        Ok(ItemStack { id, components })
+    }
+}
+
+fn legacy_key(key: &str) -> SNBTString {
+    SNBTString(false, key.to_string())
+}
+
+/// Item NBT stored text components as JSON strings.
+fn legacy_text(value: &SNBT, target: GameVersion) -> Option<SNBT> {
+    if value.has_macro() {
+        return None;
+    }
+
+    TextComponent::from_snbt(value).map(|text| SNBT::string(text.to_json_for(target)))
+}
+
+fn legacy_enchantments(value: &SNBT) -> Option<SNBT> {
+    let SNBT::Compound(compound) = value else {
+        return None;
+    };
+
+    // Before 1.21.5 the levels were nested in a `levels` field.
+    let levels = match compound.get(&legacy_key("levels")) {
+        Some(SNBT::Compound(levels)) => levels,
+        Some(_) => return None,
+        None => compound,
+    };
+
+    let enchantments = levels
+        .iter()
+        .map(|(id, level)| {
+            let SNBT::Integer(level) = level else {
+                return None;
+            };
+
+            let id = if id.1.contains(':') {
+                id.1.clone()
+            } else {
+                format!("minecraft:{}", id.1)
+            };
+
+            Some(SNBT::Compound(SNBTCompound::from([
+                (legacy_key("id"), SNBT::string(id)),
+                (legacy_key("lvl"), SNBT::Short(i16::try_from(*level).ok()?)),
+            ])))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(SNBT::List(enchantments))
+}
+
+impl ItemStack {
+    /// Renders this item for `target`, converting components to item NBT before 1.20.5.
+    pub fn render(&self, target: GameVersion) -> Result<String, VersionError> {
+        if target >= GameVersion::ITEM_COMPONENTS || self.components.is_empty() {
+            return Ok(self.to_string());
+        }
+
+        let mut tag = SNBTCompound::new();
+        let mut display = SNBTCompound::new();
+
+        for component in &self.components {
+            let cannot_lower = || VersionError::CannotLower {
+                feature: format!("item component '{}'", component),
+                target,
+            };
+
+            let ItemComponent::KeyValue(id, value) = component else {
+                return Err(cannot_lower());
+            };
+
+            if id.namespace() != "minecraft" || id.paths.len() != 1 {
+                return Err(cannot_lower());
+            }
+
+            let (compound, key, value) = match (id.paths.head.as_str(), value) {
+                ("custom_data", SNBT::Compound(data)) => {
+                    tag.extend(data.clone());
+                    continue;
+                }
+                ("damage", SNBT::Integer(_)) => (&mut tag, "Damage", value.clone()),
+                ("repair_cost", SNBT::Integer(_)) => (&mut tag, "RepairCost", value.clone()),
+                ("unbreakable", _) => (&mut tag, "Unbreakable", SNBT::Byte(1)),
+                ("custom_model_data", SNBT::Integer(_)) => {
+                    (&mut tag, "CustomModelData", value.clone())
+                }
+                ("dyed_color", SNBT::Integer(_)) => (&mut display, "color", value.clone()),
+                ("dyed_color", SNBT::Compound(color)) => match color.get(&legacy_key("rgb")) {
+                    Some(rgb @ SNBT::Integer(_)) => (&mut display, "color", rgb.clone()),
+                    _ => return Err(cannot_lower()),
+                },
+                ("custom_name", name) => {
+                    let name = legacy_text(name, target).ok_or_else(cannot_lower)?;
+
+                    (&mut display, "Name", name)
+                }
+                ("lore", SNBT::List(lines)) => {
+                    let lines = lines
+                        .iter()
+                        .map(|line| legacy_text(line, target))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(cannot_lower)?;
+
+                    (&mut display, "Lore", SNBT::List(lines))
+                }
+                ("enchantments", enchantments) => {
+                    let enchantments =
+                        legacy_enchantments(enchantments).ok_or_else(cannot_lower)?;
+
+                    (&mut tag, "Enchantments", enchantments)
+                }
+                _ => return Err(cannot_lower()),
+            };
+
+            compound.insert(legacy_key(key), value);
+        }
+
+        if !display.is_empty() {
+            tag.insert(legacy_key("display"), SNBT::Compound(display));
+        }
+
+        Ok(format!("{}{}", self.id, SNBT::Compound(tag)))
+    }
+}
+
+impl FromStr for ItemStack {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+impl FromStr for ItemPredicate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_stack() {
+        let stack: ItemStack = "minecraft:diamond_sword[damage=5,!enchantments]"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            stack.components,
+            vec![
+                ItemComponent::KeyValue(
+                    ResourceLocation::new_path::<String, _>("damage"),
+                    SNBT::Integer(5)
+                ),
+                ItemComponent::Remove(ResourceLocation::new_path::<String, _>("enchantments")),
+            ]
+        );
+        assert_eq!(stack.to_string(), "diamond_sword[damage=5, !enchantments]");
+
+        let error = "#minecraft:logs".parse::<ItemStack>().unwrap_err();
+        assert_eq!(error.cursor, 0);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::InvalidValue("item", "#logs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_item_predicate() {
+        let predicate: ItemPredicate = "*[count~{min:2}|!damage,custom_data={a:1b}]"
+            .parse()
+            .unwrap();
+
+        assert_eq!(predicate.id, ItemType::Wildcard);
+        assert_eq!(predicate.tests.len(), 2);
+        assert_eq!(predicate.tests[0].0.len(), 2);
+        assert!(predicate.tests[0].0[1].0);
+        assert_eq!(
+            predicate.to_string(),
+            "*[count~{min:2}|!damage,custom_data={a:1b}]"
+        );
+
+        let tag: ItemPredicate = "#minecraft:logs".parse().unwrap();
+        assert_eq!(tag.to_string(), "#logs");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "stick[damage=]".parse::<ItemStack>().unwrap_err();
+        assert_eq!(error.cursor, 13);
+
+        let error = "stick[damage".parse::<ItemPredicate>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedEndOfInput);
+
+        let repeated = |id: &str| ParseErrorKind::RepeatedKey("item component", id.to_string());
+
+        let error = "stick[count=1,count=2]".parse::<ItemStack>().unwrap_err();
+        assert_eq!((error.cursor, error.kind), (14, repeated("count")));
+
+        let error = "stick[!damage,damage=1]".parse::<ItemStack>().unwrap_err();
+        assert_eq!((error.cursor, error.kind), (14, repeated("damage")));
+
+        let error = "stick[damage=1,!minecraft:damage]"
+            .parse::<ItemStack>()
+            .unwrap_err();
+        assert_eq!((error.cursor, error.kind), (16, repeated("damage")));
+    }
+}
@@ -6,11 +6,23 @@ pub mod command;
 pub mod coordinate;
 pub mod datapack;
 pub mod entity_selector;
+pub mod execute_analysis;
 pub mod has_macro;
 pub mod item;
+pub mod macros;
+pub mod nbt_eval;
 pub mod nbt_path;
+pub mod parser;
 pub mod range;
 pub mod resource_location;
+#[cfg(feature = "serde")]
+pub mod resource_location_serde;
 pub mod rotation;
+pub mod simulator;
 pub mod snbt;
+#[cfg(feature = "serde")]
+pub mod snbt_serde;
+pub mod text;
 pub mod time;
+pub mod version;
+pub mod visit;
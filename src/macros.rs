@@ -0,0 +1,501 @@
+use crate::command::Command;
+use crate::command::execute::final_command;
+use crate::command::function::FunctionCommandArguments;
+use crate::command::r#return::ReturnCommand;
+use crate::datapack::Datapack;
+use crate::datapack::function::{McFunction, McFunctionLine};
+use crate::has_macro::HasMacro;
+use crate::nbt_path::SNBTCompound;
+use crate::parser::{ParseError, parse_all};
+use crate::resource_location::ResourceLocation;
+use crate::snbt::{SNBT, SNBTString, is_allowed_in_macro_name};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MissingArgs(pub Vec<String>);
+
+impl Display for MissingArgs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Missing macro arguments: {}", self.0.join(", "))
+    }
+}
+
+impl std::error::Error for MissingArgs {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MacroError {
+    MissingArgs(MissingArgs),
+    /// The substituted text isn't a valid command.
+    InvalidCommand(String, ParseError),
+}
+
+impl Display for MacroError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MacroError::MissingArgs(missing) => missing.fmt(f),
+            MacroError::InvalidCommand(command, error) => {
+                write!(
+                    f,
+                    "Invalid command '{}' after substitution: {}",
+                    command, error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MacroError {}
+
+impl From<MissingArgs> for MacroError {
+    fn from(missing: MissingArgs) -> Self {
+        MacroError::MissingArgs(missing)
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+fn segments(line: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut rest = line;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut search_from = 0;
+
+        while let Some(offset) = rest[search_from..].find("$(") {
+            let start = search_from + offset;
+            let name_start = start + 2;
+            let name_length = rest[name_start..]
+                .find(|c| !is_allowed_in_macro_name(c))
+                .unwrap_or(rest.len() - name_start);
+            let name_end = name_start + name_length;
+
+            if name_length > 0 && rest[name_end..].starts_with(')') {
+                if start > 0 {
+                    let text = &rest[..start];
+                    rest = &rest[start..];
+
+                    return Some(Segment::Text(text));
+                }
+
+                let name = &rest[name_start..name_end];
+                rest = &rest[name_end + 1..];
+
+                return Some(Segment::Variable(name));
+            }
+
+            search_from = name_start;
+        }
+
+        let text = rest;
+        rest = "";
+
+        Some(Segment::Text(text))
+    })
+}
+
+/// Returns the names of the `$(name)` slots in `line`.
+#[must_use]
+pub fn macro_variables(line: &str) -> BTreeSet<String> {
+    segments(line)
+        .filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.to_string()),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the keys of `args` that `line` doesn't use.
+#[must_use]
+pub fn unused_macro_args(line: &str, args: &SNBTCompound) -> Vec<String> {
+    let variables = macro_variables(line);
+
+    args.keys()
+        .map(|SNBTString(_, key)| key)
+        .filter(|key| !variables.contains(*key))
+        .cloned()
+        .collect()
+}
+
+/// Formats a double like Java's `DecimalFormat("#")` with 15 fraction digits.
+fn format_decimal(value: f64) -> String {
+    let formatted = format!("{:.15}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted.is_empty() {
+        "0".to_string()
+    } else {
+        formatted.to_string()
+    }
+}
+
+/// Turns a macro argument into the text that replaces its slot. Numbers lose their type
+/// suffixes and strings are inserted without quotes.
+#[must_use]
+pub fn stringify_macro_arg(value: &SNBT) -> String {
+    match value {
+        SNBT::Byte(v) => v.to_string(),
+        SNBT::Short(v) => v.to_string(),
+        SNBT::Integer(v) => v.to_string(),
+        SNBT::Long(v) => v.to_string(),
+        SNBT::Float(v) => format_decimal(f64::from(**v)),
+        SNBT::Double(v) => format_decimal(**v),
+        SNBT::String(SNBTString(_, v)) => v.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Replaces every `$(name)` slot in `line` with the matching argument, like the game does
+/// for a macro line without its `$` prefix.
+pub fn substitute_macro_line(line: &str, args: &SNBTCompound) -> Result<String, MissingArgs> {
+    let mut result = String::new();
+    let mut missing = BTreeSet::new();
+
+    for segment in segments(line) {
+        match segment {
+            Segment::Text(text) => result.push_str(text),
+            Segment::Variable(name) => match args.get(&SNBTString(false, name.to_string())) {
+                Some(value) => result.push_str(&stringify_macro_arg(value)),
+                None => {
+                    missing.insert(name.to_string());
+                }
+            },
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(result)
+    } else {
+        Err(MissingArgs(missing.into_iter().collect()))
+    }
+}
+
+fn parse_substituted(text: String) -> Result<Command, MacroError> {
+    parse_all(&text).map_err(|error| MacroError::InvalidCommand(text, error))
+}
+
+impl Command {
+    /// Fills in every macro slot with `args`, see [`substitute_macro_line`].
+    pub fn substitute_macros(&self, args: &SNBTCompound) -> Result<Command, MacroError> {
+        if !self.has_macro() {
+            return Ok(self.clone());
+        }
+
+        parse_substituted(substitute_macro_line(&self.to_string(), args)?)
+    }
+
+    /// Returns the keys of `args` that this command doesn't use.
+    #[must_use]
+    pub fn unused_macro_args(&self, args: &SNBTCompound) -> Vec<String> {
+        unused_macro_args(&self.to_string(), args)
+    }
+}
+
+/// Returns the text of a macro line without its `$` prefix.
+fn macro_line_text(line: &McFunctionLine) -> Option<String> {
+    match line {
+        McFunctionLine::Command(command) if command.has_macro() => Some(command.to_string()),
+        McFunctionLine::Raw(raw) => raw.strip_prefix('$').map(str::to_string),
+        _ => None,
+    }
+}
+
+impl McFunction {
+    /// Fills in every macro line with `args`. Missing arguments from all lines are reported
+    /// together.
+    pub fn substitute_macros(&self, args: &SNBTCompound) -> Result<McFunction, MacroError> {
+        let mut lines = Vec::with_capacity(self.lines.len());
+        let mut missing = BTreeSet::new();
+
+        for line in &self.lines {
+            let Some(text) = macro_line_text(line) else {
+                lines.push(line.clone());
+                continue;
+            };
+
+            match substitute_macro_line(&text, args) {
+                Ok(text) => lines.push(parse_substituted(text)?.into()),
+                Err(MissingArgs(names)) => missing.extend(names),
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(McFunction::from(lines))
+        } else {
+            Err(MissingArgs(missing.into_iter().collect()).into())
+        }
+    }
+
+    /// Returns the names of the macro variables used by any line.
+    #[must_use]
+    pub fn macro_variables(&self) -> BTreeSet<String> {
+        let mut variables = BTreeSet::new();
+
+        for line in &self.lines {
+            match line {
+                McFunctionLine::Command(command) => command.collect_macro_variables(&mut variables),
+                McFunctionLine::Raw(raw) => {
+                    if let Some(text) = raw.strip_prefix('$') {
+                        variables.extend(macro_variables(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        variables
+    }
+
+    /// Returns the keys of `args` that no macro line uses.
+    #[must_use]
+    pub fn unused_macro_args(&self, args: &SNBTCompound) -> Vec<String> {
+        let variables = self.macro_variables();
+
+        args.keys()
+            .map(|SNBTString(_, key)| key)
+            .filter(|key| !variables.contains(*key))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A `function` call whose arguments don't match the macro variables of the called function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MacroCallError {
+    pub caller: ResourceLocation,
+    /// The index of the calling line in the caller.
+    pub line: usize,
+    pub callee: ResourceLocation,
+    pub missing: Vec<String>,
+    pub unused: Vec<String>,
+}
+
+impl Display for MacroCallError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Call to {} in {} (line {})",
+            self.callee,
+            self.caller,
+            self.line + 1
+        )?;
+
+        if !self.missing.is_empty() {
+            write!(f, " is missing {}", self.missing.join(", "))?;
+        }
+
+        if !self.unused.is_empty() {
+            if !self.missing.is_empty() {
+                f.write_str(" and")?;
+            }
+
+            write!(f, " has unused {}", self.unused.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for MacroCallError {}
+
+fn called_function(
+    command: &Command,
+) -> Option<(&ResourceLocation, Option<&FunctionCommandArguments>)> {
+    match command {
+        Command::Function(function, arguments) => Some((function, arguments.as_ref())),
+        Command::Execute(subcommand) => final_command(subcommand).and_then(called_function),
+        Command::Return(ReturnCommand::Run(command)) => called_function(command),
+        _ => None,
+    }
+}
+
+impl Datapack {
+    /// Checks that every `function` call to a macro function of this datapack supplies exactly
+    /// the variables the function uses. Calls with `with` arguments are only known at runtime and
+    /// aren't checked.
+    #[must_use]
+    pub fn check_macro_calls(&self) -> Vec<MacroCallError> {
+        let mut errors = Vec::new();
+
+        for (namespace_name, namespace) in &self.namespaces {
+            for (path, function) in &namespace.functions {
+                for (line, function_line) in function.lines.iter().enumerate() {
+                    let McFunctionLine::Command(command) = function_line else {
+                        continue;
+                    };
+
+                    let Some((callee, arguments)) = called_function(command) else {
+                        continue;
+                    };
+
+                    if callee.is_tag {
+                        continue;
+                    }
+
+                    let Some(callee_function) = self
+                        .namespaces
+                        .get(callee.namespace())
+                        .and_then(|namespace| namespace.functions.get(&callee.paths))
+                    else {
+                        continue;
+                    };
+
+                    let keys = match arguments {
+                        None => BTreeSet::new(),
+                        Some(FunctionCommandArguments::Compound(compound)) => {
+                            // Keys filled in by the caller's own macro line can't be checked.
+                            if compound.keys().any(|SNBTString(is_macro, _)| *is_macro) {
+                                continue;
+                            }
+
+                            compound
+                                .keys()
+                                .map(|SNBTString(_, key)| key.clone())
+                                .collect()
+                        }
+                        Some(FunctionCommandArguments::DataTarget(..)) => continue,
+                    };
+
+                    let variables = callee_function.macro_variables();
+
+                    if keys != variables {
+                        errors.push(MacroCallError {
+                            caller: ResourceLocation::new_namespace_paths(
+                                namespace_name,
+                                path.clone(),
+                            ),
+                            line,
+                            callee: callee.clone(),
+                            missing: variables.difference(&keys).cloned().collect(),
+                            unused: keys.difference(&variables).cloned().collect(),
+                        });
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::NotNan;
+
+    fn args() -> SNBTCompound {
+        SNBTCompound::from([
+            (SNBTString(false, "key".to_string()), SNBT::string("foo")),
+            (SNBTString(false, "count".to_string()), SNBT::Byte(3)),
+            (
+                SNBTString(false, "scale".to_string()),
+                SNBT::Float(NotNan::new(0.5).unwrap()),
+            ),
+            (SNBTString(false, "unused".to_string()), SNBT::Integer(1)),
+        ])
+    }
+
+    #[test]
+    fn test_stringify() {
+        assert_eq!(stringify_macro_arg(&SNBT::Long(5)), "5");
+        assert_eq!(
+            stringify_macro_arg(&SNBT::Double(NotNan::new(1e20).unwrap())),
+            "100000000000000000000"
+        );
+        assert_eq!(
+            stringify_macro_arg(&SNBT::Float(NotNan::new(0.1).unwrap())),
+            "0.100000001490116"
+        );
+        assert_eq!(
+            stringify_macro_arg(&"{a:1b, b:\"x\"}".parse().unwrap()),
+            "{a:1b, b:\"x\"}"
+        );
+    }
+
+    #[test]
+    fn test_substitute_line() {
+        assert_eq!(
+            substitute_macro_line("say $(key) x$(count) $(scale) $( $(bad-name)", &args()),
+            Ok("say foo x3 0.5 $( $(bad-name)".to_string())
+        );
+        assert_eq!(
+            substitute_macro_line("say $(a) $(key) $(b)", &args()),
+            Err(MissingArgs(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(
+            unused_macro_args("say $(key) $(count) $(scale)", &args()),
+            vec!["unused".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_substitute_command() {
+        let command: Command = "data modify storage foo:bar $(key) set value 1b"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            command.substitute_macros(&args()).unwrap(),
+            "data modify storage foo:bar foo set value 1b"
+                .parse()
+                .unwrap()
+        );
+        assert!(matches!(
+            command.substitute_macros(&SNBTCompound::new()),
+            Err(MacroError::MissingArgs(_))
+        ));
+
+        let function: McFunction =
+            "say hi\n$say $(key)\n$data modify storage foo:bar $(key) set value $(count)"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            function.substitute_macros(&args()).unwrap().to_string(),
+            "say hi\nsay foo\ndata modify storage foo:bar foo set value 3"
+        );
+        assert_eq!(
+            function.unused_macro_args(&args()),
+            vec!["scale".to_string(), "unused".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_check_macro_calls() {
+        let mut datapack = Datapack::new(48, serde_json::Value::from("test"));
+        let namespace = datapack.get_namespace_mut("foo");
+        namespace.add_function(
+            &nonempty::nonempty!["greet".to_string()],
+            "$say hi $(name)\n$data modify storage foo:bar $(key) set value 1b"
+                .parse::<McFunction>()
+                .unwrap(),
+        );
+        namespace.add_function(
+            &nonempty::nonempty!["main".to_string()],
+            "function foo:greet {name:\"Steve\", key:\"a\"}\nexecute as @a run function foo:greet {name:\"Alex\", extra:1}\nfunction foo:greet with storage foo:bar\nfunction minecraft:unknown {a:1}"
+                .parse::<McFunction>()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            datapack.namespaces["foo"].functions[&nonempty::nonempty!["greet".to_string()]]
+                .macro_variables(),
+            BTreeSet::from(["key".to_string(), "name".to_string()])
+        );
+        assert_eq!(
+            datapack.check_macro_calls(),
+            vec![MacroCallError {
+                caller: ResourceLocation::new_namespace_path("foo", "main"),
+                line: 1,
+                callee: ResourceLocation::new_namespace_path("foo", "greet"),
+                missing: vec!["key".to_string()],
+                unused: vec!["extra".to_string()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,750 @@
+//! Evaluation of [`NbtPath`]s against [`SNBT`] values, with the semantics of the game's `data`
+//! command. Lists may hold mixed element types, like since 1.21.5.
+
+use crate::command::data::DataCommandModificationMode;
+use crate::has_macro::HasMacro;
+use crate::nbt_path::{NbtPath, NbtPathNode, SNBTCompound};
+use crate::snbt::{SNBT, SNBTString};
+use nonempty::NonEmpty;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum NbtPathError {
+    /// Nothing matched the path up to and including the contained one.
+    NotFound(NbtPath),
+    ExpectedList(SNBT),
+    ExpectedCompound(SNBT),
+    InvalidIndex(i32),
+    /// The path or a value still contains macros.
+    Macro,
+}
+
+impl Display for NbtPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NbtPathError::NotFound(path) => write!(f, "Found no elements matching {}", path),
+            NbtPathError::ExpectedList(value) => write!(f, "Expected list, got: {}", value),
+            NbtPathError::ExpectedCompound(value) => write!(f, "Expected object, got: {}", value),
+            NbtPathError::InvalidIndex(index) => write!(f, "Invalid index: {}", index),
+            NbtPathError::Macro => f.write_str("Macros must be substituted first"),
+        }
+    }
+}
+
+impl std::error::Error for NbtPathError {}
+
+impl SNBT {
+    /// Whether `self` matches `pattern` the way path filters and the `nbt` selector option do:
+    /// compounds only need the keys of the pattern, and every element of a non-empty list pattern
+    /// must match some element of the list.
+    #[must_use]
+    pub fn matches(&self, pattern: &SNBT) -> bool {
+        match (pattern, self) {
+            (SNBT::Compound(pattern), SNBT::Compound(compound)) => {
+                compound_matches(compound, pattern)
+            }
+            (SNBT::List(pattern), SNBT::List(list)) => {
+                if pattern.is_empty() {
+                    return list.is_empty();
+                }
+
+                pattern
+                    .iter()
+                    .all(|pattern| list.iter().any(|value| value.matches(pattern)))
+            }
+            (pattern, value) => pattern == value,
+        }
+    }
+}
+
+fn compound_matches(compound: &SNBTCompound, pattern: &SNBTCompound) -> bool {
+    pattern.iter().all(|(key, pattern)| {
+        compound
+            .get(key)
+            .is_some_and(|value| value.matches(pattern))
+    })
+}
+
+/// Merges `source` into `target` like `data merge`, recursing into compounds present in both.
+pub fn merge_compound(target: &mut SNBTCompound, source: &SNBTCompound) {
+    for (key, value) in source {
+        match (target.get_mut(key), value) {
+            (Some(SNBT::Compound(target)), SNBT::Compound(source)) => {
+                merge_compound(target, source)
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn key(name: &str) -> SNBTString {
+    SNBTString(false, name.to_string())
+}
+
+/// Resolves a negative index from the end, `None` if it's out of bounds.
+fn resolve_index(index: i32, length: usize) -> Option<usize> {
+    let index = if index < 0 {
+        length as i64 + index as i64
+    } else {
+        index as i64
+    };
+
+    (0..length as i64)
+        .contains(&index)
+        .then_some(index as usize)
+}
+
+fn array_length(value: &SNBT) -> Option<usize> {
+    match value {
+        SNBT::ByteArray(values) => Some(values.len()),
+        SNBT::IntegerArray(values) => Some(values.len()),
+        SNBT::LongArray(values) => Some(values.len()),
+        _ => None,
+    }
+}
+
+fn array_element(array: &SNBT, index: usize) -> Option<SNBT> {
+    match array {
+        SNBT::ByteArray(values) => values.get(index).map(|v| SNBT::Byte(*v)),
+        SNBT::IntegerArray(values) => values.get(index).map(|v| SNBT::Integer(*v)),
+        SNBT::LongArray(values) => values.get(index).map(|v| SNBT::Long(*v)),
+        _ => None,
+    }
+}
+
+fn as_byte(value: &SNBT) -> Option<i8> {
+    Some(match value {
+        SNBT::Byte(v) => *v,
+        SNBT::Short(v) => *v as i8,
+        SNBT::Integer(v) => *v as i8,
+        SNBT::Long(v) => *v as i8,
+        SNBT::Float(v) => v.floor() as i32 as i8,
+        SNBT::Double(v) => v.floor() as i32 as i8,
+        _ => return None,
+    })
+}
+
+fn as_int(value: &SNBT) -> Option<i32> {
+    Some(match value {
+        SNBT::Byte(v) => *v as i32,
+        SNBT::Short(v) => *v as i32,
+        SNBT::Integer(v) => *v,
+        SNBT::Long(v) => *v as i32,
+        SNBT::Float(v) => v.floor() as i32,
+        SNBT::Double(v) => v.floor() as i32,
+        _ => return None,
+    })
+}
+
+fn as_long(value: &SNBT) -> Option<i64> {
+    Some(match value {
+        SNBT::Byte(v) => *v as i64,
+        SNBT::Short(v) => *v as i64,
+        SNBT::Integer(v) => *v as i64,
+        SNBT::Long(v) => *v,
+        SNBT::Float(v) => v.into_inner() as i64,
+        SNBT::Double(v) => v.floor() as i64,
+        _ => return None,
+    })
+}
+
+/// Replaces the element at `index` of a list or array, converting numbers for arrays. Returns
+/// whether anything changed.
+fn set_element(collection: &mut SNBT, index: usize, value: &SNBT) -> bool {
+    fn replace<T: PartialEq>(values: &mut [T], index: usize, value: Option<T>) -> bool {
+        match value {
+            Some(value) if values[index] != value => {
+                values[index] = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    match collection {
+        SNBT::List(values) => replace(values, index, Some(value.clone())),
+        SNBT::ByteArray(values) => replace(values, index, as_byte(value)),
+        SNBT::IntegerArray(values) => replace(values, index, as_int(value)),
+        SNBT::LongArray(values) => replace(values, index, as_long(value)),
+        _ => false,
+    }
+}
+
+/// Inserts into a list or array, converting numbers for arrays. Returns whether the value could
+/// be inserted.
+fn insert_element(collection: &mut SNBT, index: usize, value: &SNBT) -> bool {
+    fn insert<T>(values: &mut Vec<T>, index: usize, value: Option<T>) -> bool {
+        value.map(|value| values.insert(index, value)).is_some()
+    }
+
+    match collection {
+        SNBT::List(values) => insert(values, index, Some(value.clone())),
+        SNBT::ByteArray(values) => insert(values, index, as_byte(value)),
+        SNBT::IntegerArray(values) => insert(values, index, as_int(value)),
+        SNBT::LongArray(values) => insert(values, index, as_long(value)),
+        _ => false,
+    }
+}
+
+fn collection_length(value: &SNBT) -> Option<usize> {
+    match value {
+        SNBT::List(values) => Some(values.len()),
+        value => array_length(value),
+    }
+}
+
+/// Returns the values `node` selects in `value`. Array elements are copied.
+fn children<'a>(value: &'a SNBT, node: &NbtPathNode) -> Vec<Cow<'a, SNBT>> {
+    match (node, value) {
+        (NbtPathNode::RootCompound(pattern), SNBT::Compound(compound)) => {
+            if compound_matches(compound, pattern) {
+                vec![Cow::Borrowed(value)]
+            } else {
+                vec![]
+            }
+        }
+        (NbtPathNode::Named(SNBTString(_, name), filter), SNBT::Compound(compound)) => compound
+            .get(&key(name))
+            .filter(|child| {
+                filter
+                    .as_ref()
+                    .is_none_or(|f| compound_matches_value(child, f))
+            })
+            .map(Cow::Borrowed)
+            .into_iter()
+            .collect(),
+        (NbtPathNode::Index(Some(SNBT::Integer(index))), SNBT::List(values)) => {
+            resolve_index(*index, values.len())
+                .map(|index| Cow::Borrowed(&values[index]))
+                .into_iter()
+                .collect()
+        }
+        (NbtPathNode::Index(Some(SNBT::Integer(index))), array) => array_length(array)
+            .and_then(|length| resolve_index(*index, length))
+            .and_then(|index| array_element(array, index))
+            .map(Cow::Owned)
+            .into_iter()
+            .collect(),
+        (NbtPathNode::Index(None), SNBT::List(values)) => {
+            values.iter().map(Cow::Borrowed).collect()
+        }
+        (NbtPathNode::Index(None), array) => (0..array_length(array).unwrap_or(0))
+            .filter_map(|index| array_element(array, index))
+            .map(Cow::Owned)
+            .collect(),
+        (NbtPathNode::Index(Some(pattern @ SNBT::Compound(_))), SNBT::List(values)) => values
+            .iter()
+            .filter(|value| value.matches(pattern))
+            .map(Cow::Borrowed)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn compound_matches_value(value: &SNBT, pattern: &SNBTCompound) -> bool {
+    matches!(value, SNBT::Compound(compound) if compound_matches(compound, pattern))
+}
+
+/// Like [`children`], but creates a missing child from `create` where the game does. Array
+/// elements aren't returned.
+fn children_mut<'a>(
+    value: &'a mut SNBT,
+    node: &NbtPathNode,
+    create: Option<SNBT>,
+) -> Vec<&'a mut SNBT> {
+    match (node, value) {
+        (NbtPathNode::RootCompound(pattern), value) => {
+            if matches!(value, SNBT::Compound(compound) if compound_matches(compound, pattern)) {
+                vec![value]
+            } else {
+                vec![]
+            }
+        }
+        (NbtPathNode::Named(SNBTString(_, name), filter), SNBT::Compound(compound)) => {
+            let key = key(name);
+
+            if !compound.contains_key(&key)
+                && let Some(create) = create
+            {
+                let child = match filter {
+                    Some(filter) => SNBT::Compound(filter.clone()),
+                    None => create,
+                };
+
+                compound.insert(key.clone(), child);
+            }
+
+            compound
+                .get_mut(&key)
+                .filter(|child| {
+                    filter
+                        .as_ref()
+                        .is_none_or(|f| compound_matches_value(child, f))
+                })
+                .into_iter()
+                .collect()
+        }
+        (NbtPathNode::Index(Some(SNBT::Integer(index))), SNBT::List(values)) => {
+            match resolve_index(*index, values.len()) {
+                Some(index) => vec![&mut values[index]],
+                None => vec![],
+            }
+        }
+        (NbtPathNode::Index(None), SNBT::List(values)) => {
+            if values.is_empty()
+                && let Some(create) = create
+            {
+                values.push(create);
+            }
+
+            values.iter_mut().collect()
+        }
+        (NbtPathNode::Index(Some(pattern @ SNBT::Compound(_))), SNBT::List(values)) => {
+            if create.is_some() && !values.iter().any(|value| value.matches(pattern)) {
+                values.push(pattern.clone());
+            }
+
+            values
+                .iter_mut()
+                .filter(|value| value.matches(pattern))
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Sets what `node` selects in `parent` to `value` and returns how many values changed.
+fn set_child(parent: &mut SNBT, node: &NbtPathNode, value: &SNBT) -> usize {
+    match (node, parent) {
+        (NbtPathNode::Named(SNBTString(_, name), filter), SNBT::Compound(compound)) => {
+            let key = key(name);
+
+            if let Some(filter) = filter
+                && !compound
+                    .get(&key)
+                    .is_some_and(|child| compound_matches_value(child, filter))
+            {
+                return 0;
+            }
+
+            usize::from(compound.insert(key, value.clone()).as_ref() != Some(value))
+        }
+        (NbtPathNode::Index(Some(SNBT::Integer(index))), collection) => {
+            collection_length(collection)
+                .and_then(|length| resolve_index(*index, length))
+                .map_or(0, |index| {
+                    usize::from(set_element(collection, index, value))
+                })
+        }
+        (NbtPathNode::Index(None), collection) => match collection_length(collection) {
+            Some(0) => usize::from(insert_element(collection, 0, value)),
+            Some(length) => (0..length)
+                .filter(|index| set_element(collection, *index, value))
+                .count(),
+            None => 0,
+        },
+        (NbtPathNode::Index(Some(SNBT::Compound(_))), SNBT::List(values)) if values.is_empty() => {
+            values.push(value.clone());
+            1
+        }
+        (NbtPathNode::Index(Some(pattern @ SNBT::Compound(_))), SNBT::List(values)) => values
+            .iter_mut()
+            .filter(|element| element.matches(pattern) && *element != value)
+            .map(|element| *element = value.clone())
+            .count(),
+        _ => 0,
+    }
+}
+
+/// Removes what `node` selects in `parent` and returns how many values were removed.
+fn remove_child(parent: &mut SNBT, node: &NbtPathNode) -> usize {
+    fn retain<T>(values: &mut Vec<T>, mut keep: impl FnMut(usize, &T) -> bool) -> usize {
+        let length = values.len();
+        let mut index = 0;
+
+        values.retain(|value| {
+            index += 1;
+            keep(index - 1, value)
+        });
+
+        length - values.len()
+    }
+
+    fn remove_where(collection: &mut SNBT, keep: impl Fn(usize, &SNBT) -> bool) -> usize {
+        match collection {
+            SNBT::List(values) => retain(values, |index, value| keep(index, value)),
+            SNBT::ByteArray(values) => retain(values, |index, v| keep(index, &SNBT::Byte(*v))),
+            SNBT::IntegerArray(values) => {
+                retain(values, |index, v| keep(index, &SNBT::Integer(*v)))
+            }
+            SNBT::LongArray(values) => retain(values, |index, v| keep(index, &SNBT::Long(*v))),
+            _ => 0,
+        }
+    }
+
+    match (node, parent) {
+        (NbtPathNode::Named(SNBTString(_, name), filter), SNBT::Compound(compound)) => {
+            let key = key(name);
+
+            match compound.get(&key) {
+                Some(child)
+                    if filter
+                        .as_ref()
+                        .is_none_or(|f| compound_matches_value(child, f)) =>
+                {
+                    compound.remove(&key);
+                    1
+                }
+                _ => 0,
+            }
+        }
+        (NbtPathNode::Index(Some(SNBT::Integer(index))), collection) => {
+            match collection_length(collection).and_then(|length| resolve_index(*index, length)) {
+                Some(removed) => remove_where(collection, |index, _| index != removed),
+                None => 0,
+            }
+        }
+        (NbtPathNode::Index(None), collection) => remove_where(collection, |_, _| false),
+        (NbtPathNode::Index(Some(pattern @ SNBT::Compound(_))), collection) => {
+            remove_where(collection, |_, value| !value.matches(pattern))
+        }
+        _ => 0,
+    }
+}
+
+/// The value the game creates for a missing parent of `next`.
+fn empty_parent(next: &NbtPathNode) -> SNBT {
+    match next {
+        NbtPathNode::Index(_) => SNBT::List(vec![]),
+        _ => SNBT::Compound(SNBTCompound::new()),
+    }
+}
+
+impl NbtPath {
+    fn check_macros(&self) -> Result<(), NbtPathError> {
+        if self.has_macro() {
+            Err(NbtPathError::Macro)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn not_found(&self, node: usize) -> NbtPathError {
+        let nodes = self.0.iter().take(node + 1).cloned().collect::<Vec<_>>();
+
+        NbtPathError::NotFound(NbtPath(
+            NonEmpty::from_vec(nodes).expect("node index is in bounds"),
+        ))
+    }
+
+    /// Resolves the first `depth` nodes. With `leaf`, missing values are created, the last one
+    /// from `leaf`.
+    fn resolve_mut<'a>(
+        &self,
+        root: &'a mut SNBT,
+        depth: usize,
+        leaf: Option<&SNBT>,
+    ) -> Result<Vec<&'a mut SNBT>, NbtPathError> {
+        let mut current = vec![root];
+
+        for (i, node) in self.0.iter().take(depth).enumerate() {
+            let create = leaf.map(|leaf| match self.0.get(i + 1) {
+                Some(next) => empty_parent(next),
+                None => leaf.clone(),
+            });
+
+            current = current
+                .into_iter()
+                .flat_map(|value| children_mut(value, node, create.clone()))
+                .collect();
+
+            if current.is_empty() {
+                return Err(self.not_found(i));
+            }
+        }
+
+        Ok(current)
+    }
+
+    /// Returns every value the path matches in `root`, like `data get`.
+    pub fn get(&self, root: &SNBT) -> Result<Vec<SNBT>, NbtPathError> {
+        self.check_macros()?;
+
+        let mut current = vec![Cow::Borrowed(root)];
+
+        for (i, node) in self.0.iter().enumerate() {
+            current = current
+                .iter()
+                .flat_map(|value| match value {
+                    Cow::Borrowed(value) => children(value, node),
+                    // Copied array elements are numbers, which have no children.
+                    Cow::Owned(_) => vec![],
+                })
+                .collect();
+
+            if current.is_empty() {
+                return Err(self.not_found(i));
+            }
+        }
+
+        Ok(current.into_iter().map(Cow::into_owned).collect())
+    }
+
+    /// Sets every matched value to `value`, creating missing parents. Returns how many values
+    /// changed.
+    pub fn set(&self, root: &mut SNBT, value: &SNBT) -> Result<usize, NbtPathError> {
+        self.check_macros()?;
+
+        let empty = SNBT::Compound(SNBTCompound::new());
+        let last = self.0.last();
+
+        Ok(self
+            .resolve_mut(root, self.0.len() - 1, Some(&empty))?
+            .into_iter()
+            .map(|parent| set_child(parent, last, value))
+            .sum())
+    }
+
+    /// Removes every matched value and returns how many were removed.
+    pub fn remove(&self, root: &mut SNBT) -> Result<usize, NbtPathError> {
+        self.check_macros()?;
+
+        let last = self.0.last();
+
+        Ok(self
+            .resolve_mut(root, self.0.len() - 1, None)?
+            .into_iter()
+            .map(|parent| remove_child(parent, last))
+            .sum())
+    }
+
+    /// Inserts `values` into every matched list at `index`, where negative indices count from
+    /// the end and `-1` appends. Missing lists are created. Returns how many lists changed.
+    pub fn insert(
+        &self,
+        root: &mut SNBT,
+        index: i32,
+        values: &[SNBT],
+    ) -> Result<usize, NbtPathError> {
+        self.check_macros()?;
+
+        let mut changed = 0;
+
+        for target in self.resolve_mut(root, self.0.len(), Some(&SNBT::List(vec![])))? {
+            let length = collection_length(target)
+                .ok_or_else(|| NbtPathError::ExpectedList(target.clone()))?;
+            let mut index = if index < 0 {
+                length as i64 + index as i64 + 1
+            } else {
+                index as i64
+            };
+            let mut inserted = false;
+
+            for value in values {
+                let length = collection_length(target).unwrap_or(0);
+
+                if !(0..=length as i64).contains(&index) {
+                    return Err(NbtPathError::InvalidIndex(index as i32));
+                }
+
+                if insert_element(target, index as usize, value) {
+                    index += 1;
+                    inserted = true;
+                }
+            }
+
+            changed += usize::from(inserted);
+        }
+
+        Ok(changed)
+    }
+
+    /// Merges every compound of `values` into every matched compound, creating missing ones.
+    /// Returns how many compounds changed.
+    pub fn merge(&self, root: &mut SNBT, values: &[SNBT]) -> Result<usize, NbtPathError> {
+        self.check_macros()?;
+
+        let sources = values
+            .iter()
+            .map(|value| match value {
+                SNBT::Compound(compound) => Ok(compound),
+                value => Err(NbtPathError::ExpectedCompound(value.clone())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let empty = SNBT::Compound(SNBTCompound::new());
+        let mut changed = 0;
+
+        for target in self.resolve_mut(root, self.0.len(), Some(&empty))? {
+            let SNBT::Compound(compound) = target else {
+                return Err(NbtPathError::ExpectedCompound(target.clone()));
+            };
+
+            let original = compound.clone();
+
+            for source in &sources {
+                merge_compound(compound, source);
+            }
+
+            changed += usize::from(*compound != original);
+        }
+
+        Ok(changed)
+    }
+
+    /// Applies `mode` with `values` like `data modify`. `set` uses the last value.
+    pub fn modify(
+        &self,
+        root: &mut SNBT,
+        mode: &DataCommandModificationMode,
+        values: &[SNBT],
+    ) -> Result<usize, NbtPathError> {
+        if values.iter().any(HasMacro::has_macro) {
+            return Err(NbtPathError::Macro);
+        }
+
+        match mode {
+            DataCommandModificationMode::Set => match values.last() {
+                Some(value) => self.set(root, value),
+                None => Ok(0),
+            },
+            DataCommandModificationMode::Merge => self.merge(root, values),
+            DataCommandModificationMode::Append => self.insert(root, -1, values),
+            DataCommandModificationMode::Prepend => self.insert(root, 0, values),
+            DataCommandModificationMode::Insert(index) => self.insert(root, *index, values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snbt(input: &str) -> SNBT {
+        input.parse().unwrap()
+    }
+
+    fn path(input: &str) -> NbtPath {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_get() {
+        let root = snbt(
+            "{Items:[{Slot:0b,id:\"stone\"},{Slot:1b,id:\"dirt\",tag:{a:1}}],Bytes:[B;1b,2b],Tags:[\"x\"]}",
+        );
+
+        assert_eq!(
+            path("Items[].id").get(&root),
+            Ok(vec![snbt("\"stone\""), snbt("\"dirt\"")])
+        );
+        assert_eq!(path("Items[-1].Slot").get(&root), Ok(vec![SNBT::Byte(1)]));
+        assert_eq!(
+            path("Items[{Slot:1b}].tag.a").get(&root),
+            Ok(vec![SNBT::Integer(1)])
+        );
+        assert_eq!(path("Bytes[1]").get(&root), Ok(vec![SNBT::Byte(2)]));
+        assert_eq!(
+            path("{Tags:[\"x\"]}.Bytes[]").get(&root),
+            Ok(vec![SNBT::Byte(1), SNBT::Byte(2)])
+        );
+        assert_eq!(
+            path("Items[{Slot:2b}].id").get(&root),
+            Err(NbtPathError::NotFound(path("Items[{Slot:2b}]")))
+        );
+        assert_eq!(path("a.$(b)").get(&root), Err(NbtPathError::Macro));
+    }
+
+    #[test]
+    fn test_set_and_remove() {
+        let mut root = snbt("{}");
+
+        assert_eq!(path("a.b[].c").set(&mut root, &SNBT::Integer(1)), Ok(1));
+        assert_eq!(root, snbt("{a:{b:[{c:1}]}}"));
+        assert_eq!(path("a.b[0].c").set(&mut root, &SNBT::Integer(1)), Ok(0));
+
+        let mut root = snbt("{list:[1,2,3],bytes:[B;1b,2b]}");
+
+        assert_eq!(path("list[]").set(&mut root, &SNBT::Integer(2)), Ok(2));
+        assert_eq!(path("bytes[0]").set(&mut root, &SNBT::Integer(300)), Ok(1));
+        assert_eq!(root, snbt("{list:[2,2,2],bytes:[B;44b,2b]}"));
+
+        let mut empty = snbt("{list:[]}");
+
+        assert_eq!(
+            path("list[{a:1}]").set(&mut empty, &snbt("{a:1,b:2}")),
+            Ok(1)
+        );
+        assert_eq!(empty, snbt("{list:[{a:1,b:2}]}"));
+
+        assert_eq!(path("list[1]").remove(&mut root), Ok(1));
+        assert_eq!(path("bytes[]").remove(&mut root), Ok(2));
+        assert_eq!(root, snbt("{list:[2,2],bytes:[B;]}"));
+        assert_eq!(
+            path("missing.key").remove(&mut root),
+            Err(NbtPathError::NotFound(path("missing")))
+        );
+    }
+
+    #[test]
+    fn test_modify() {
+        let mut root = snbt("{list:[1,2]}");
+
+        let append = DataCommandModificationMode::Append;
+        let prepend = DataCommandModificationMode::Prepend;
+        let insert = DataCommandModificationMode::Insert(1);
+        let merge = DataCommandModificationMode::Merge;
+
+        assert_eq!(
+            path("list").modify(&mut root, &append, &[SNBT::Integer(3)]),
+            Ok(1)
+        );
+        assert_eq!(
+            path("list").modify(&mut root, &prepend, &[SNBT::Integer(0)]),
+            Ok(1)
+        );
+        assert_eq!(
+            path("list").modify(&mut root, &insert, &[snbt("\"a\""), snbt("\"b\"")]),
+            Ok(1)
+        );
+        assert_eq!(root, snbt("{list:[0,\"a\",\"b\",1,2,3]}"));
+
+        assert_eq!(
+            path("new").modify(&mut root, &append, &[SNBT::Integer(1)]),
+            Ok(1)
+        );
+        assert_eq!(
+            path("list").modify(
+                &mut root,
+                &DataCommandModificationMode::Insert(10),
+                &[SNBT::Integer(1)]
+            ),
+            Err(NbtPathError::InvalidIndex(10))
+        );
+
+        let mut root = snbt("{a:{b:{c:1,d:2}}}");
+
+        assert_eq!(
+            path("a").modify(&mut root, &merge, &[snbt("{b:{c:3},e:4}")]),
+            Ok(1)
+        );
+        assert_eq!(root, snbt("{a:{b:{c:3,d:2},e:4}}"));
+        assert_eq!(
+            path("a.b.c").modify(&mut root, &merge, &[snbt("{}")]),
+            Err(NbtPathError::ExpectedCompound(SNBT::Integer(3)))
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let value = snbt("{a:1b,list:[{id:1},{id:2}],empty:[]}");
+
+        assert!(value.matches(&snbt("{a:1b,list:[{id:2}]}")));
+        assert!(value.matches(&snbt("{empty:[]}")));
+        assert!(!value.matches(&snbt("{a:1}")));
+        assert!(!value.matches(&snbt("{list:[]}")));
+    }
+}
@@ -1,16 +1,20 @@
 use crate::has_macro::HasMacro;
-use crate::snbt::{SNBT, SNBTString, fmt_snbt_compound};
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, StringReader, parse_all};
+use crate::snbt::{SNBT, SNBTString, fmt_snbt_compound, parse_macro, parse_snbt_compound};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use nonempty::NonEmpty;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 pub type SNBTCompound = BTreeMap<SNBTString, SNBT>;
 
 fn escape_nbt_path_key(name: &str) -> String {
-    let needs_quotes = name
-        .chars()
-        .any(|c| matches!(c, ' ' | '"' | '\'' | '[' | ']' | '.' | '{' | '}'));
+    let needs_quotes = name.is_empty()
+        || name.starts_with("$(")
+        || name
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '[' | ']' | '.' | '{' | '}'));
 
     if needs_quotes {
         let escaped_content = name.replace('\\', "\\\\").replace('"', "\\\"");
@@ -20,11 +24,20 @@ fn escape_nbt_path_key(name: &str) -> String {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[inline]
+fn is_allowed_in_nbt_path_key(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '"' | '\'' | '[' | ']' | '.' | '{' | '}')
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NbtPathNode {
-    RootCompound(SNBTCompound),
-    Named(SNBTString, Option<SNBTCompound>),
-    Index(Option<SNBT>),
+    RootCompound(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBTCompound),
+    Named(
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] SNBTString,
+        #[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBTCompound>,
+    ),
+    Index(#[cfg_attr(feature = "serde", serde(with = "crate::snbt_serde"))] Option<SNBT>),
 }
 
 impl NbtPathNode {
@@ -37,7 +50,9 @@ impl NbtPathNode {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[visit(hook = nbt_path)]
 pub struct NbtPath(pub NonEmpty<NbtPathNode>);
 
 impl NbtPath {
@@ -66,8 +81,13 @@ impl Display for NbtPathNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             NbtPathNode::RootCompound(compound) => fmt_snbt_compound(f, compound),
-            NbtPathNode::Named(SNBTString(_, name), filter) => {
-                f.write_str(&escape_nbt_path_key(name))?;
+            NbtPathNode::Named(SNBTString(is_macro, name), filter) => {
+                // Unlike plain keys, keys with macro slots may start with `$(`.
+                if *is_macro && name.chars().all(is_allowed_in_nbt_path_key) {
+                    f.write_str(name)?;
+                } else {
+                    f.write_str(&escape_nbt_path_key(name))?;
+                }
 
                 if let Some(filter) = filter
                     && !filter.is_empty()
@@ -95,3 +115,143 @@ impl Display for NbtPath {
         Ok(())
     }
 }
+
+impl NbtPathNode {
+    fn parse_index(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        reader.expect('[')?;
+        reader.skip_whitespace();
+
+        let index = match reader.peek() {
+            Some(']') => None,
+            Some('{') => Some(SNBT::Compound(parse_snbt_compound(reader)?)),
+            Some('$') => Some(SNBT::Macro(parse_macro(reader)?)),
+            _ => Some(SNBT::Integer(reader.read_int()?)),
+        };
+
+        reader.skip_whitespace();
+        reader.expect(']')?;
+
+        Ok(NbtPathNode::Index(index))
+    }
+
+    fn parse_named(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let name = match reader.peek() {
+            Some('"' | '\'') => SNBTString::new(reader.read_quoted_string()?),
+            _ => {
+                // Macro slots may be anywhere in the key, like `b$(x)`.
+                let name = reader.read_while(is_allowed_in_nbt_path_key);
+
+                if name.is_empty() {
+                    return Err(reader.expected("NBT path key"));
+                }
+
+                SNBTString::new(name)
+            }
+        };
+
+        let filter = if reader.peek() == Some('{') {
+            Some(parse_snbt_compound(reader)?)
+        } else {
+            None
+        };
+
+        Ok(NbtPathNode::Named(name, filter))
+    }
+}
+
+impl Parse for NbtPath {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let first = match reader.peek() {
+            Some('{') => NbtPathNode::RootCompound(parse_snbt_compound(reader)?),
+            Some('[') => NbtPathNode::parse_index(reader)?,
+            _ => NbtPathNode::parse_named(reader)?,
+        };
+
+        let mut path = NbtPath(NonEmpty::new(first));
+
+        loop {
+            match reader.peek() {
+                Some('.') => {
+                    reader.skip();
+                    path.0.push(NbtPathNode::parse_named(reader)?);
+                }
+                Some('[') => path.0.push(NbtPathNode::parse_index(reader)?),
+                _ => return Ok(path),
+            }
+        }
+    }
+}
+
+impl FromStr for NbtPath {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ParseErrorKind;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for input in [
+            "foo",
+            "foo.bar[0].baz",
+            "Inventory[-1]",
+            "Items[{Slot:0b}].tag",
+            "{Invulnerable:1b}.Health",
+            "[]",
+            "a[].b",
+            "\"a key\".\"with.dot\"",
+            "\"quote\\\"d\"",
+            "foo{bar:1}.baz",
+            "data.$(key)[$(index)]",
+            "a.b$(x).$(y)_c",
+            "\"a $(x)\"",
+        ] {
+            let path: NbtPath = input.parse().unwrap();
+
+            assert_eq!(path.to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_parse_macro_segments() {
+        let path: NbtPath = "a.$(key)[$(index)]".parse().unwrap();
+
+        assert!(path.has_macro());
+        assert_eq!(
+            path.0.get(1),
+            Some(&NbtPathNode::named(SNBTString(true, "$(key)".to_string())))
+        );
+        assert_eq!(
+            path.0.last(),
+            &NbtPathNode::Index(Some(SNBT::Macro("index".to_string())))
+        );
+
+        let path: NbtPath = "a.b$(x)".parse().unwrap();
+
+        assert!(path.has_macro());
+        assert_eq!(path.macro_variables(), ["x".to_string()].into());
+        assert_eq!(
+            path.0.last(),
+            &NbtPathNode::named(SNBTString(true, "b$(x)".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "foo..bar".parse::<NbtPath>().unwrap_err();
+        assert_eq!(error.cursor, 4);
+        assert_eq!(error.kind, ParseErrorKind::Expected("NBT path key"));
+
+        let error = "foo[1".parse::<NbtPath>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedEndOfInput);
+
+        let error = "foo bar".parse::<NbtPath>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::TrailingData);
+    }
+}
@@ -0,0 +1,512 @@
+use ordered_float::NotNan;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedEndOfInput,
+    ExpectedCharacter(char),
+    ExpectedArgumentSeparator,
+    Expected(&'static str),
+    InvalidInteger(String),
+    InvalidFloat(String),
+    InvalidBoolean(String),
+    InvalidValue(&'static str, String),
+    InvalidResourceLocation(String),
+    UnknownCommand(String),
+    UnknownArgument(String),
+    UnknownOption(String),
+    DuplicateOption(String),
+    /// A key that can only be set once, like a block state property or an item component.
+    RepeatedKey(&'static str, String),
+    UnclosedQuotedString,
+    InvalidEscape(char),
+    MixedCoordinates,
+    TrailingData,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEndOfInput => f.write_str("Unexpected end of input"),
+            ParseErrorKind::ExpectedCharacter(c) => write!(f, "Expected '{}'", c),
+            ParseErrorKind::ExpectedArgumentSeparator => {
+                f.write_str("Expected whitespace to end one argument, but found trailing data")
+            }
+            ParseErrorKind::Expected(what) => write!(f, "Expected {}", what),
+            ParseErrorKind::InvalidInteger(value) => write!(f, "Invalid integer '{}'", value),
+            ParseErrorKind::InvalidFloat(value) => write!(f, "Invalid float '{}'", value),
+            ParseErrorKind::InvalidBoolean(value) => write!(f, "Invalid boolean '{}'", value),
+            ParseErrorKind::InvalidValue(what, value) => write!(f, "Invalid {} '{}'", what, value),
+            ParseErrorKind::InvalidResourceLocation(message) => {
+                write!(f, "Invalid resource location: {}", message)
+            }
+            ParseErrorKind::UnknownCommand(name) => write!(f, "Unknown command '{}'", name),
+            ParseErrorKind::UnknownArgument(name) => write!(f, "Unknown argument '{}'", name),
+            ParseErrorKind::UnknownOption(name) => write!(f, "Unknown option '{}'", name),
+            ParseErrorKind::DuplicateOption(name) => {
+                write!(f, "Option '{}' isn't applicable here", name)
+            }
+            ParseErrorKind::RepeatedKey(what, key) => write!(f, "Repeated {} '{}'", what, key),
+            ParseErrorKind::UnclosedQuotedString => f.write_str("Unclosed quoted string"),
+            ParseErrorKind::InvalidEscape(c) => {
+                write!(f, "Invalid escape sequence '\\{}' in quoted string", c)
+            }
+            ParseErrorKind::MixedCoordinates => {
+                f.write_str("Cannot mix world and local coordinates")
+            }
+            ParseErrorKind::TrailingData => f.write_str("Unexpected trailing data"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub cursor: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    #[inline]
+    #[must_use]
+    pub fn new(cursor: usize, kind: ParseErrorKind) -> Self {
+        Self { cursor, kind }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.cursor)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub trait Parse: Sized {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError>;
+}
+
+/// Parses `input` as a single `T`, failing if anything is left over afterwards.
+pub fn parse_all<T: Parse>(input: &str) -> Result<T, ParseError> {
+    let mut reader = StringReader::new(input);
+    let value = T::parse(&mut reader)?;
+
+    if reader.can_read() {
+        return Err(reader.error(ParseErrorKind::TrailingData));
+    }
+
+    Ok(value)
+}
+
+#[inline]
+pub fn is_allowed_in_unquoted_string(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+#[inline]
+pub fn is_allowed_number(c: char) -> bool {
+    c.is_ascii_digit() || matches!(c, '.' | '-' | '+')
+}
+
+#[inline]
+pub fn is_allowed_in_resource_location(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.' | ':' | '/')
+}
+
+#[derive(Debug, Clone)]
+pub struct StringReader<'a> {
+    input: &'a str,
+    cursor: usize,
+}
+
+impl<'a> StringReader<'a> {
+    #[inline]
+    #[must_use]
+    pub fn new(input: &'a str) -> Self {
+        Self { input, cursor: 0 }
+    }
+
+    #[inline]
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[inline]
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor;
+    }
+
+    #[inline]
+    pub fn remaining(&self) -> &'a str {
+        &self.input[self.cursor..]
+    }
+
+    #[inline]
+    pub fn can_read(&self) -> bool {
+        self.cursor < self.input.len()
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    #[inline]
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.remaining().chars().nth(n)
+    }
+
+    #[inline]
+    pub fn peek_str(&self, s: &str) -> bool {
+        self.remaining().starts_with(s)
+    }
+
+    pub fn read(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.cursor += c.len_utf8();
+        Some(c)
+    }
+
+    #[inline]
+    pub fn skip(&mut self) {
+        self.read();
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.skip();
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.cursor, kind)
+    }
+
+    /// Returns an error for an unexpected character, or for the end of input if nothing is left.
+    #[must_use]
+    pub fn expected(&self, what: &'static str) -> ParseError {
+        if self.can_read() {
+            self.error(ParseErrorKind::Expected(what))
+        } else {
+            self.error(ParseErrorKind::UnexpectedEndOfInput)
+        }
+    }
+
+    pub fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.peek() == Some(c) {
+            self.skip();
+            Ok(())
+        } else if self.can_read() {
+            Err(self.error(ParseErrorKind::ExpectedCharacter(c)))
+        } else {
+            Err(self.error(ParseErrorKind::UnexpectedEndOfInput))
+        }
+    }
+
+    /// Consumes `c` if it is the next character.
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.skip();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if another space-separated argument follows.
+    #[inline]
+    pub fn has_argument(&self) -> bool {
+        self.peek() == Some(' ')
+    }
+
+    pub fn expect_separator(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(' ') => {
+                self.skip();
+                Ok(())
+            }
+            Some(_) => Err(self.error(ParseErrorKind::ExpectedArgumentSeparator)),
+            None => Err(self.error(ParseErrorKind::UnexpectedEndOfInput)),
+        }
+    }
+
+    pub fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.cursor;
+
+        while self.peek().is_some_and(&predicate) {
+            self.skip();
+        }
+
+        &self.input[start..self.cursor]
+    }
+
+    /// Reads everything up to the next whitespace character.
+    pub fn read_word(&mut self) -> &'a str {
+        self.read_while(|c| !c.is_whitespace())
+    }
+
+    /// Like [`StringReader::read_word`], but fails on an empty word.
+    pub fn read_required_word(&mut self, what: &'static str) -> Result<&'a str, ParseError> {
+        let word = self.read_word();
+
+        if word.is_empty() {
+            return Err(self.expected(what));
+        }
+
+        Ok(word)
+    }
+
+    /// Reads the rest of the input.
+    pub fn read_greedy(&mut self) -> &'a str {
+        let rest = self.remaining();
+        self.cursor = self.input.len();
+        rest
+    }
+
+    pub fn read_unquoted_string(&mut self) -> &'a str {
+        self.read_while(is_allowed_in_unquoted_string)
+    }
+
+    pub fn read_quoted_string(&mut self) -> Result<String, ParseError> {
+        let quote = match self.peek() {
+            Some(c @ ('"' | '\'')) => c,
+            Some(_) => return Err(self.error(ParseErrorKind::Expected("quoted string"))),
+            None => return Err(self.error(ParseErrorKind::UnexpectedEndOfInput)),
+        };
+        self.skip();
+
+        let mut result = String::new();
+
+        loop {
+            match self.read() {
+                Some('\\') => match self.read() {
+                    Some(c) if c == quote || c == '\\' => result.push(c),
+                    Some(c) => {
+                        return Err(ParseError::new(
+                            self.cursor - c.len_utf8(),
+                            ParseErrorKind::InvalidEscape(c),
+                        ));
+                    }
+                    None => return Err(self.error(ParseErrorKind::UnclosedQuotedString)),
+                },
+                Some(c) if c == quote => return Ok(result),
+                Some(c) => result.push(c),
+                None => return Err(self.error(ParseErrorKind::UnclosedQuotedString)),
+            }
+        }
+    }
+
+    /// Reads either a quoted or an unquoted string.
+    pub fn read_string(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some('"' | '\'') => self.read_quoted_string(),
+            _ => Ok(self.read_unquoted_string().to_string()),
+        }
+    }
+
+    /// Reads the characters of a number, stopping before a `..` range separator.
+    pub fn read_number_str(&mut self) -> &'a str {
+        let start = self.cursor;
+
+        while let Some(c) = self.peek() {
+            if !is_allowed_number(c) || self.peek_str("..") {
+                break;
+            }
+
+            self.skip();
+        }
+
+        &self.input[start..self.cursor]
+    }
+
+    fn read_number<T: FromStr>(
+        &mut self,
+        what: &'static str,
+        error: fn(String) -> ParseErrorKind,
+    ) -> Result<T, ParseError> {
+        let start = self.cursor;
+        let number = self.read_number_str();
+
+        if number.is_empty() {
+            return Err(self.expected(what));
+        }
+
+        number
+            .parse()
+            .map_err(|_| ParseError::new(start, error(number.to_string())))
+    }
+
+    pub fn read_int(&mut self) -> Result<i32, ParseError> {
+        self.read_number("integer", ParseErrorKind::InvalidInteger)
+    }
+
+    pub fn read_long(&mut self) -> Result<i64, ParseError> {
+        self.read_number("integer", ParseErrorKind::InvalidInteger)
+    }
+
+    pub fn read_float(&mut self) -> Result<NotNan<f32>, ParseError> {
+        let start = self.cursor;
+        let value: f32 = self.read_number("float", ParseErrorKind::InvalidFloat)?;
+
+        NotNan::new(value)
+            .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidFloat(value.to_string())))
+    }
+
+    pub fn read_double(&mut self) -> Result<NotNan<f64>, ParseError> {
+        let start = self.cursor;
+        let value: f64 = self.read_number("double", ParseErrorKind::InvalidFloat)?;
+
+        NotNan::new(value)
+            .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidFloat(value.to_string())))
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, ParseError> {
+        let start = self.cursor;
+
+        match self.read_unquoted_string() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            "" => Err(self.expected("boolean")),
+            value => Err(ParseError::new(
+                start,
+                ParseErrorKind::InvalidBoolean(value.to_string()),
+            )),
+        }
+    }
+
+    /// Reads a keyword such as a command or subcommand name.
+    pub fn read_literal(&mut self) -> Result<&'a str, ParseError> {
+        let literal = self.read_while(|c| is_allowed_in_unquoted_string(c) && c != '+');
+
+        if literal.is_empty() {
+            return Err(self.expected("literal"));
+        }
+
+        Ok(literal)
+    }
+
+    /// Reads a word and converts it with [`FromStr`], as the `create_enum!` enums support.
+    pub fn read_enum<T: FromStr>(&mut self, what: &'static str) -> Result<T, ParseError> {
+        let start = self.cursor;
+        let word = self.read_required_word(what)?;
+
+        word.parse().map_err(|_| {
+            ParseError::new(start, ParseErrorKind::InvalidValue(what, word.to_string()))
+        })
+    }
+
+    /// Runs `f`, rewinding the cursor if it fails.
+    pub fn try_parse<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let start = self.cursor;
+        let result = f(self);
+
+        if result.is_err() {
+            self.cursor = start;
+        }
+
+        result
+    }
+
+    pub fn unknown_argument(&self, start: usize, literal: &str) -> ParseError {
+        ParseError::new(start, ParseErrorKind::UnknownArgument(literal.to_string()))
+    }
+}
+
+impl Parse for bool {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        reader.read_bool()
+    }
+}
+
+impl Parse for i32 {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        reader.read_int()
+    }
+}
+
+impl Parse for NotNan<f32> {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        reader.read_float()
+    }
+}
+
+impl Parse for NotNan<f64> {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        reader.read_double()
+    }
+}
+
+impl<T: Parse> Parse for Box<T> {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        T::parse(reader).map(Box::new)
+    }
+}
+
+/// Parses a trailing optional argument, consuming the separating space when one is present.
+pub fn parse_optional<T>(
+    reader: &mut StringReader<'_>,
+    f: impl FnOnce(&mut StringReader<'_>) -> Result<T, ParseError>,
+) -> Result<Option<T>, ParseError> {
+    if !reader.has_argument() {
+        return Ok(None);
+    }
+
+    reader.expect_separator()?;
+    f(reader).map(Some)
+}
+
+/// Parses a space followed by a `T`.
+pub fn parse_argument<T: Parse>(reader: &mut StringReader<'_>) -> Result<T, ParseError> {
+    reader.expect_separator()?;
+    T::parse(reader)
+}
+
+/// Parses an optional space followed by a `T`.
+pub fn parse_optional_argument<T: Parse>(
+    reader: &mut StringReader<'_>,
+) -> Result<Option<T>, ParseError> {
+    parse_optional(reader, T::parse)
+}
+
+/// Reads a single whitespace-delimited word, such as an objective, team or tag name.
+pub fn parse_word(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    reader.read_required_word("word").map(str::to_string)
+}
+
+/// Reads the rest of the input, such as a chat message or a ban reason.
+pub fn parse_greedy(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    Ok(reader.read_greedy().to_string())
+}
+
+pub fn expect_literal(reader: &mut StringReader<'_>, expected: &str) -> Result<(), ParseError> {
+    let start = reader.cursor();
+    let literal = reader.read_literal()?;
+
+    if literal != expected {
+        return Err(reader.unknown_argument(start, literal));
+    }
+
+    Ok(())
+}
+
+/// Parses an optional argument that may only appear after an earlier optional argument.
+pub fn parse_chained_argument<T: Parse, U>(
+    reader: &mut StringReader<'_>,
+    previous: &Option<U>,
+) -> Result<Option<T>, ParseError> {
+    if previous.is_none() {
+        return Ok(None);
+    }
+
+    parse_optional_argument(reader)
+}
@@ -1,9 +1,14 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
 use std::ops::Range;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntegerRange {
     pub min: Option<i32>,
     pub max: Option<i32>,
@@ -15,13 +20,22 @@ impl IntegerRange {
             panic!("min and/or max must be Some")
         }
 
+        Self::try_new(min, max).expect("min must be smaller or equal to max")
+    }
+
+    #[must_use]
+    pub fn try_new(min: Option<i32>, max: Option<i32>) -> Option<IntegerRange> {
+        if min.is_none() && max.is_none() {
+            return None;
+        }
+
         if let (Some(min), Some(max)) = (min, max)
             && min > max
         {
-            panic!("min must be smaller or equal to max");
+            return None;
         }
 
-        IntegerRange { min, max }
+        Some(IntegerRange { min, max })
     }
 
     pub fn new_min(min: i32) -> IntegerRange {
@@ -39,6 +53,11 @@ impl IntegerRange {
     pub fn new_single(value: i32) -> IntegerRange {
         Self::new(Some(value), Some(value))
     }
+
+    #[must_use]
+    pub fn contains(&self, value: i32) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
 }
 
 impl Display for IntegerRange {
@@ -100,7 +119,10 @@ impl From<Range<Option<i32>>> for IntegerRange {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatRange {
     pub min: Option<NotNan<f32>>,
     pub max: Option<NotNan<f32>>,
@@ -113,13 +135,22 @@ impl FloatRange {
             panic!("min and/or max must be Some")
         }
 
+        Self::try_new(min, max).expect("min must be smaller or equal to max")
+    }
+
+    #[must_use]
+    pub fn try_new(min: Option<NotNan<f32>>, max: Option<NotNan<f32>>) -> Option<FloatRange> {
+        if min.is_none() && max.is_none() {
+            return None;
+        }
+
         if let (Some(min), Some(max)) = (min, max)
             && min > max
         {
-            panic!("min must be smaller or equal to max");
+            return None;
         }
 
-        FloatRange { min, max }
+        Some(FloatRange { min, max })
     }
 
     #[inline]
@@ -205,3 +236,116 @@ impl From<Range<Option<NotNan<f32>>>> for FloatRange {
         FloatRange::new(value.start, value.end)
     }
 }
+
+fn parse_range<T: PartialOrd + Copy>(
+    reader: &mut StringReader<'_>,
+    read_bound: impl Fn(&mut StringReader<'_>) -> Result<T, ParseError>,
+) -> Result<(Option<T>, Option<T>), ParseError> {
+    let start = reader.cursor();
+
+    let min = if reader.peek_str("..") {
+        None
+    } else {
+        Some(read_bound(reader)?)
+    };
+
+    let max = if reader.peek_str("..") {
+        reader.skip();
+        reader.skip();
+
+        if reader
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+        {
+            Some(read_bound(reader)?)
+        } else {
+            None
+        }
+    } else {
+        min
+    };
+
+    if min.is_none() && max.is_none() {
+        return Err(ParseError::new(
+            start,
+            ParseErrorKind::Expected("range bound"),
+        ));
+    }
+
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        return Err(ParseError::new(
+            start,
+            ParseErrorKind::InvalidValue(
+                "range",
+                reader.input()[start..reader.cursor()].to_string(),
+            ),
+        ));
+    }
+
+    Ok((min, max))
+}
+
+impl Parse for IntegerRange {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let (min, max) = parse_range(reader, i32::parse)?;
+
+        Ok(IntegerRange { min, max })
+    }
+}
+
+impl Parse for FloatRange {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let (min, max) = parse_range(reader, NotNan::<f32>::parse)?;
+
+        Ok(FloatRange { min, max })
+    }
+}
+
+impl FromStr for IntegerRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+impl FromStr for FloatRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1..".parse(), Ok(IntegerRange::new_min(1)));
+        assert_eq!("..5".parse(), Ok(IntegerRange::new_max(5)));
+        assert_eq!("3".parse(), Ok(IntegerRange::new_single(3)));
+        assert_eq!("-2..7".parse(), Ok(IntegerRange::new_min_max(-2, 7)));
+
+        let range: FloatRange = "0.5..1.5".parse().unwrap();
+        assert_eq!(range.to_string(), "0.5..1.5");
+        assert_eq!(
+            "..-1.5".parse(),
+            Ok(FloatRange::new_max(NotNan::new(-1.5).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("5..1".parse::<IntegerRange>().is_err());
+        assert!("..".parse::<IntegerRange>().is_err());
+        assert!("1.5".parse::<IntegerRange>().is_err());
+        assert!("2.5..1".parse::<FloatRange>().is_err());
+
+        assert_eq!(IntegerRange::try_new(Some(5), Some(1)), None);
+        assert_eq!(IntegerRange::try_new(None, None), None);
+    }
+}
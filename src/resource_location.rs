@@ -1,15 +1,23 @@
+use crate::parser::{
+    Parse, ParseError, ParseErrorKind, StringReader, is_allowed_in_resource_location,
+};
 use itertools::Itertools;
-use minecraft_command_types_derive::HasMacro;
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use nonempty::{NonEmpty, nonempty};
 use serde::de::Visitor;
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+/// IDs are compared by [`ResourceLocation::namespace`], so `minecraft:stone` and `stone` are
+/// equal.
+#[derive(Debug, Clone, HasMacro, Visit, VisitMut, Fold)]
+#[visit(hook = resource_location)]
 pub struct ResourceLocation {
     pub is_tag: bool,
-    namespace: Option<String>,
+    pub(crate) namespace: Option<String>,
     pub paths: NonEmpty<String>,
 }
 
@@ -62,6 +70,40 @@ impl ResourceLocation {
     }
 }
 
+impl PartialEq for ResourceLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_tag == other.is_tag
+            && self.namespace() == other.namespace()
+            && self.paths == other.paths
+    }
+}
+
+impl Eq for ResourceLocation {}
+
+impl PartialOrd for ResourceLocation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ResourceLocation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.is_tag, self.namespace(), &self.paths).cmp(&(
+            other.is_tag,
+            other.namespace(),
+            &other.paths,
+        ))
+    }
+}
+
+impl Hash for ResourceLocation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_tag.hash(state);
+        self.namespace().hash(state);
+        self.paths.hash(state);
+    }
+}
+
 impl Display for ResourceLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.is_tag {
@@ -155,6 +197,34 @@ impl FromStr for ResourceLocation {
     }
 }
 
+impl Parse for ResourceLocation {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let is_tag = reader.eat('#');
+        reader.read_while(is_allowed_in_resource_location);
+
+        let text = &reader.input()[start..reader.cursor()];
+
+        if text.is_empty() {
+            return Err(reader.expected("resource location"));
+        }
+
+        text.parse().map_err(|e: ResourceLocationParseError| {
+            let message = match e {
+                ResourceLocationParseError::EmptyString => {
+                    "Path component cannot be empty".to_string()
+                }
+                ResourceLocationParseError::InvalidFormat(message) => message,
+            };
+
+            ParseError::new(
+                if is_tag { start + 1 } else { start },
+                ParseErrorKind::InvalidResourceLocation(message),
+            )
+        })
+    }
+}
+
 impl Serialize for ResourceLocation {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -197,3 +267,27 @@ impl<'de> Deserialize<'de> for ResourceLocation {
         deserializer.deserialize_string(ResourceLocationVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash(id: &ResourceLocation) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_default_namespace() {
+        let explicit: ResourceLocation = "minecraft:stone".parse().unwrap();
+        let implicit: ResourceLocation = "stone".parse().unwrap();
+
+        assert_eq!(explicit, implicit);
+        assert_eq!(explicit.cmp(&implicit), Ordering::Equal);
+        assert_eq!(hash(&explicit), hash(&implicit));
+        assert_ne!(explicit, "foo:stone".parse().unwrap());
+        assert_ne!(explicit, "#minecraft:stone".parse().unwrap());
+    }
+}
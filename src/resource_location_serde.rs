@@ -0,0 +1,86 @@
+//! A serde representation of resource locations for the `serde` feature, used through
+//! `#[serde(with = "crate::resource_location_serde")]` on AST fields.
+//!
+//! The [`Serialize`] implementation of [`ResourceLocation`] itself writes the shortest form (for
+//! example in tags and JSON files), which drops an explicit `minecraft` namespace.
+
+use crate::resource_location::ResourceLocation;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Types containing resource locations that are written with their namespace as parsed.
+pub trait Namespaced {
+    fn serialize_namespaced<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+struct AsNamespaced<'a, T>(&'a T);
+
+impl<T: Namespaced> Serialize for AsNamespaced<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_namespaced(serializer)
+    }
+}
+
+impl Namespaced for ResourceLocation {
+    fn serialize_namespaced<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.namespace {
+            Some(namespace) if namespace == "minecraft" => serializer.collect_str(&format_args!(
+                "{}{}:{}",
+                if self.is_tag { "#" } else { "" },
+                namespace,
+                self.paths_string()
+            )),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<T: Namespaced> Namespaced for Option<T> {
+    fn serialize_namespaced<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(value) => serializer.serialize_some(&AsNamespaced(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<V: Serialize> Namespaced for BTreeMap<ResourceLocation, V> {
+    fn serialize_namespaced<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|(key, value)| (AsNamespaced(key), value)))
+    }
+}
+
+pub fn serialize<T: Namespaced, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_namespaced(serializer)
+}
+
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::Command;
+    use crate::resource_location::ResourceLocation;
+
+    #[test]
+    fn test_namespace() {
+        let command: Command =
+            "kill @e[type=minecraft:pig,advancements={minecraft:story/root=true}]"
+                .parse()
+                .unwrap();
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert!(json.contains("\"minecraft:pig\""));
+        assert!(json.contains("\"minecraft:story/root\""));
+        assert_eq!(serde_json::from_str::<Command>(&json).unwrap(), command);
+
+        let id: ResourceLocation = "minecraft:stone".parse().unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"stone\"");
+    }
+}
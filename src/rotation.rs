@@ -1,8 +1,12 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::coordinate::WorldCoordinate;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rotation(pub NotNan<f32>, pub NotNan<f32>);
 
 impl Display for Rotation {
@@ -10,3 +14,110 @@ impl Display for Rotation {
         write!(f, "{} {}", self.0, self.1)
     }
 }
+
+impl Parse for Rotation {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let yaw = reader.read_float()?;
+        reader.expect_separator()?;
+        let pitch = reader.read_float()?;
+
+        Ok(Rotation(yaw, pitch))
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+/// A rotation argument, whose yaw and pitch may be relative (`~`) to the rotation of the
+/// execution context.
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeRotation(pub WorldCoordinate, pub WorldCoordinate);
+
+impl RelativeRotation {
+    #[inline]
+    #[must_use]
+    pub fn new(yaw: WorldCoordinate, pitch: WorldCoordinate) -> Self {
+        RelativeRotation(yaw, pitch)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn relative_zero() -> Self {
+        RelativeRotation(
+            WorldCoordinate::relative_zero(),
+            WorldCoordinate::relative_zero(),
+        )
+    }
+}
+
+impl From<Rotation> for RelativeRotation {
+    fn from(rotation: Rotation) -> Self {
+        RelativeRotation(
+            WorldCoordinate::absolute(rotation.0),
+            WorldCoordinate::absolute(rotation.1),
+        )
+    }
+}
+
+impl Display for RelativeRotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
+fn parse_angle(reader: &mut StringReader<'_>) -> Result<WorldCoordinate, ParseError> {
+    if reader.peek() == Some('^') {
+        return Err(reader.error(ParseErrorKind::Expected("rotation")));
+    }
+
+    WorldCoordinate::parse(reader)
+}
+
+impl Parse for RelativeRotation {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let yaw = parse_angle(reader)?;
+        reader.expect_separator()?;
+        let pitch = parse_angle(reader)?;
+
+        Ok(RelativeRotation(yaw, pitch))
+    }
+}
+
+impl FromStr for RelativeRotation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let rotation: Rotation = "90 -10.5".parse().unwrap();
+        assert_eq!(
+            rotation,
+            Rotation(NotNan::new(90.0).unwrap(), NotNan::new(-10.5).unwrap())
+        );
+        assert!("~ ~".parse::<Rotation>().is_err());
+
+        assert_eq!(
+            "~ ~".parse::<RelativeRotation>().unwrap(),
+            RelativeRotation::relative_zero()
+        );
+        assert_eq!(
+            "90 -10.5".parse::<RelativeRotation>().unwrap(),
+            RelativeRotation::from(rotation)
+        );
+        assert!("^ ^".parse::<RelativeRotation>().is_err());
+    }
+}
@@ -0,0 +1,520 @@
+use crate::command::enums::gamemode::Gamemode;
+use crate::command::enums::sort::Sort;
+use crate::entity_selector::{
+    AdvancementChoiceType, EntitySelector, EntitySelectorOption, EntitySelectorVariable,
+};
+use crate::nbt_path::SNBTCompound;
+use crate::range::FloatRange;
+use crate::resource_location::ResourceLocation;
+use crate::simulator::position::wrap_degrees;
+use crate::simulator::{Context, SimulationError, World};
+use crate::snbt::{SNBT, SNBTString};
+use ordered_float::NotNan;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An entity in a [`World`]. Its scores are kept in the world's scoreboard under
+/// [`Entity::score_holder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub uuid: String,
+    pub entity_type: ResourceLocation,
+    pub tags: BTreeSet<String>,
+    pub team: Option<String>,
+    /// The custom name, or the name of a player.
+    pub name: Option<String>,
+    pub position: [f64; 3],
+    /// The yaw and pitch in degrees.
+    pub rotation: [f32; 2],
+    /// The size of the hitbox, which `dx`, `dy` and `dz` test against.
+    pub width: f64,
+    pub height: f64,
+    /// NBT data other than the position, rotation and tags.
+    pub nbt: SNBTCompound,
+    pub gamemode: Option<Gamemode>,
+    pub level: i32,
+    /// Whether each criterion of an advancement is completed.
+    pub advancements: BTreeMap<ResourceLocation, BTreeMap<String, bool>>,
+}
+
+impl Entity {
+    #[must_use]
+    pub fn new<T: ToString>(uuid: T, entity_type: ResourceLocation) -> Self {
+        Self {
+            uuid: uuid.to_string(),
+            entity_type,
+            tags: BTreeSet::new(),
+            team: None,
+            name: None,
+            position: [0.0; 3],
+            rotation: [0.0; 2],
+            width: 0.0,
+            height: 0.0,
+            nbt: SNBTCompound::new(),
+            gamemode: None,
+            level: 0,
+            advancements: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn player<U: ToString, N: ToString>(uuid: U, name: N) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            width: 0.6,
+            height: 1.8,
+            gamemode: Some(Gamemode::Survival),
+            ..Self::new(
+                uuid,
+                ResourceLocation::new_namespace_path("minecraft", "player"),
+            )
+        }
+    }
+
+    #[must_use]
+    pub fn is_player(&self) -> bool {
+        self.entity_type.namespace() == "minecraft" && self.entity_type.paths_string() == "player"
+    }
+
+    /// The name players have on the scoreboard, or the UUID of other entities.
+    #[must_use]
+    pub fn score_holder(&self) -> String {
+        match &self.name {
+            Some(name) if self.is_player() => name.clone(),
+            _ => self.uuid.clone(),
+        }
+    }
+
+    /// The NBT data of the entity, including its position, rotation and tags.
+    #[must_use]
+    pub fn to_nbt(&self) -> SNBTCompound {
+        let key = |name: &str| SNBTString(false, name.to_string());
+        let mut nbt = self.nbt.clone();
+
+        nbt.insert(
+            key("Pos"),
+            SNBT::List(
+                self.position
+                    .iter()
+                    .map(|value| SNBT::Double(NotNan::new(*value).unwrap_or_default()))
+                    .collect(),
+            ),
+        );
+        nbt.insert(
+            key("Rotation"),
+            SNBT::List(
+                self.rotation
+                    .iter()
+                    .map(|value| SNBT::Float(NotNan::new(*value).unwrap_or_default()))
+                    .collect(),
+            ),
+        );
+
+        if !self.tags.is_empty() {
+            nbt.insert(
+                key("Tags"),
+                SNBT::List(self.tags.iter().map(SNBT::string).collect()),
+            );
+        }
+
+        nbt
+    }
+
+    fn distance_squared(&self, position: [f64; 3]) -> f64 {
+        self.position
+            .iter()
+            .zip(position)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum()
+    }
+
+    /// Whether the hitbox intersects the box from `min` to `max`.
+    fn intersects(&self, min: [f64; 3], max: [f64; 3]) -> bool {
+        let [x, y, z] = self.position;
+        let radius = self.width / 2.0;
+        let hitbox_min = [x - radius, y, z - radius];
+        let hitbox_max = [x + radius, y + self.height, z + radius];
+
+        (0..3).all(|axis| min[axis] < hitbox_max[axis] && max[axis] > hitbox_min[axis])
+    }
+}
+
+impl World {
+    /// Finds a player by name or any entity by UUID.
+    #[must_use]
+    pub fn entity(&self, name: &str) -> Option<&Entity> {
+        self.entities.iter().find(|entity| {
+            entity.uuid == name || (entity.is_player() && entity.name.as_deref() == Some(name))
+        })
+    }
+
+    /// Whether `entity_type` is `id`, or is in the entity type tag `id`.
+    #[must_use]
+    pub fn is_entity_type(&self, entity_type: &ResourceLocation, id: &ResourceLocation) -> bool {
+        self.is_entity_type_in(entity_type, id, &mut BTreeSet::new())
+    }
+
+    fn is_entity_type_in(
+        &self,
+        entity_type: &ResourceLocation,
+        id: &ResourceLocation,
+        visited_tags: &mut BTreeSet<(String, String)>,
+    ) -> bool {
+        if !id.is_tag {
+            return same_id(entity_type, id);
+        }
+
+        if !visited_tags.insert((id.namespace().to_string(), id.paths_string())) {
+            return false;
+        }
+
+        self.entity_type_tags
+            .iter()
+            .filter(|(tag, _)| same_id(tag, id))
+            .flat_map(|(_, values)| values)
+            .any(|value| self.is_entity_type_in(entity_type, value, visited_tags))
+    }
+}
+
+fn same_id(a: &ResourceLocation, b: &ResourceLocation) -> bool {
+    a.namespace() == b.namespace() && a.paths == b.paths
+}
+
+impl EntitySelector {
+    /// Selects entities of `world` like the game does, in the order it would use them.
+    ///
+    /// `predicate` options can't be evaluated and are [`SimulationError::Unsupported`].
+    pub fn select<'w>(
+        &self,
+        world: &'w World,
+        context: &Context,
+    ) -> Result<Vec<&'w Entity>, SimulationError> {
+        let (variable, options) = match self {
+            EntitySelector::Name(name) => return Ok(world.entity(name).into_iter().collect()),
+            EntitySelector::Variable(variable, options) => (variable, options),
+        };
+
+        let mut origin = context.position;
+        let mut volume = None;
+        let (mut sort, mut limit) = match variable {
+            EntitySelectorVariable::P | EntitySelectorVariable::N => (Sort::Nearest, Some(1)),
+            EntitySelectorVariable::R => (Sort::Random, Some(1)),
+            _ => (Sort::Arbitrary, None),
+        };
+
+        for option in options {
+            match option {
+                EntitySelectorOption::X(x) => origin[0] = x.into_inner() as f64,
+                EntitySelectorOption::Y(y) => origin[1] = y.into_inner() as f64,
+                EntitySelectorOption::Z(z) => origin[2] = z.into_inner() as f64,
+                EntitySelectorOption::DistanceX(dx) => {
+                    volume.get_or_insert([0.0; 3])[0] = dx.into_inner() as f64
+                }
+                EntitySelectorOption::DistanceY(dy) => {
+                    volume.get_or_insert([0.0; 3])[1] = dy.into_inner() as f64
+                }
+                EntitySelectorOption::DistanceZ(dz) => {
+                    volume.get_or_insert([0.0; 3])[2] = dz.into_inner() as f64
+                }
+                EntitySelectorOption::Sort(option) => sort = *option,
+                EntitySelectorOption::Limit(option) => limit = Some((*option).max(0) as usize),
+                EntitySelectorOption::Predicate(..) => {
+                    return Err(SimulationError::Unsupported(self.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        let candidates: Vec<&Entity> = match variable {
+            EntitySelectorVariable::S => context
+                .executor
+                .as_deref()
+                .and_then(|executor| world.entity(executor))
+                .into_iter()
+                .collect(),
+            EntitySelectorVariable::P | EntitySelectorVariable::R | EntitySelectorVariable::A => {
+                world
+                    .entities
+                    .iter()
+                    .filter(|entity| entity.is_player())
+                    .collect()
+            }
+            EntitySelectorVariable::E | EntitySelectorVariable::N => {
+                world.entities.iter().collect()
+            }
+        };
+
+        let mut selected = candidates
+            .into_iter()
+            .filter(|entity| {
+                volume.is_none_or(|volume| {
+                    let min = [0, 1, 2].map(|axis| origin[axis] + volume[axis].min(0.0));
+                    let max = [0, 1, 2].map(|axis| origin[axis] + volume[axis].max(0.0) + 1.0);
+
+                    entity.intersects(min, max)
+                }) && options
+                    .iter()
+                    .all(|option| option_matches(option, entity, world, origin))
+            })
+            .collect::<Vec<_>>();
+
+        match sort {
+            Sort::Arbitrary => {}
+            Sort::Nearest => selected.sort_by(|a, b| {
+                a.distance_squared(origin)
+                    .total_cmp(&b.distance_squared(origin))
+            }),
+            Sort::Furthest => selected.sort_by(|a, b| {
+                b.distance_squared(origin)
+                    .total_cmp(&a.distance_squared(origin))
+            }),
+            Sort::Random => shuffle(&mut selected, world.seed),
+        }
+
+        if let Some(limit) = limit {
+            selected.truncate(limit);
+        }
+
+        Ok(selected)
+    }
+}
+
+fn option_matches(
+    option: &EntitySelectorOption,
+    entity: &Entity,
+    world: &World,
+    origin: [f64; 3],
+) -> bool {
+    match option {
+        EntitySelectorOption::Distance(range) => {
+            let distance = entity.distance_squared(origin).sqrt();
+
+            range
+                .min
+                .is_none_or(|min| distance >= min.into_inner() as f64)
+                && range
+                    .max
+                    .is_none_or(|max| distance <= max.into_inner() as f64)
+        }
+        EntitySelectorOption::XRotation(range) => rotation_matches(range, entity.rotation[1]),
+        EntitySelectorOption::YRotation(range) => rotation_matches(range, entity.rotation[0]),
+        EntitySelectorOption::Scores(scores) => {
+            let holder = entity.score_holder();
+
+            scores.iter().all(|(objective, range)| {
+                world
+                    .scoreboard
+                    .score(&holder, objective)
+                    .is_some_and(|score| range.contains(score))
+            })
+        }
+        // An empty tag or team matches entities without any.
+        EntitySelectorOption::Tag(is_inverted, tag) if tag.is_empty() => {
+            entity.tags.is_empty() != *is_inverted
+        }
+        EntitySelectorOption::Tag(is_inverted, tag) => entity.tags.contains(tag) != *is_inverted,
+        EntitySelectorOption::Team(is_inverted, team) if team.is_empty() => {
+            entity.team.is_none() != *is_inverted
+        }
+        EntitySelectorOption::Team(is_inverted, team) => {
+            (entity.team.as_ref() == Some(team)) != *is_inverted
+        }
+        EntitySelectorOption::Name(is_inverted, name) => {
+            (entity.name.as_ref() == Some(name)) != *is_inverted
+        }
+        EntitySelectorOption::Type(is_inverted, id) => {
+            world.is_entity_type(&entity.entity_type, id) != *is_inverted
+        }
+        EntitySelectorOption::Nbt(is_inverted, nbt) => {
+            SNBT::Compound(entity.to_nbt()).matches(nbt) != *is_inverted
+        }
+        // Only players have a gamemode, level and advancements.
+        EntitySelectorOption::Gamemode(is_inverted, gamemode) => {
+            entity.is_player() && (entity.gamemode == Some(*gamemode)) != *is_inverted
+        }
+        EntitySelectorOption::Level(range) => entity.is_player() && range.contains(entity.level),
+        EntitySelectorOption::Advancements(advancements) => {
+            entity.is_player()
+                && advancements.iter().all(|(id, choice)| {
+                    let criteria = entity
+                        .advancements
+                        .iter()
+                        .find(|(advancement, _)| same_id(advancement, id))
+                        .map(|(_, criteria)| criteria);
+
+                    match choice {
+                        AdvancementChoiceType::Boolean(done) => {
+                            let is_done = criteria.is_some_and(|criteria| {
+                                !criteria.is_empty() && criteria.values().all(|done| *done)
+                            });
+
+                            is_done == *done
+                        }
+                        AdvancementChoiceType::Criterion(expected) => {
+                            expected.iter().all(|(criterion, done)| {
+                                let is_done = criteria
+                                    .and_then(|criteria| criteria.get(criterion))
+                                    .copied()
+                                    .unwrap_or(false);
+
+                                is_done == *done
+                            })
+                        }
+                    }
+                })
+        }
+        _ => true,
+    }
+}
+
+/// Like the game, the bounds and the rotation are wrapped to `[-180, 180)` and a range whose
+/// minimum is greater than its maximum wraps around.
+fn rotation_matches(range: &FloatRange, rotation: f32) -> bool {
+    let min = wrap_degrees(range.min.map_or(0.0, NotNan::into_inner));
+    let max = wrap_degrees(range.max.map_or(359.0, NotNan::into_inner));
+    let rotation = wrap_degrees(rotation);
+
+    if min > max {
+        rotation >= min || rotation <= max
+    } else {
+        rotation >= min && rotation <= max
+    }
+}
+
+/// A Fisher-Yates shuffle driven by SplitMix64, so random selection is reproducible for a seed.
+fn shuffle<T>(values: &mut [T], seed: u64) {
+    let mut state = seed;
+
+    for i in (1..values.len()).rev() {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        values.swap(i, (z % (i as u64 + 1)) as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        let mut world = World::new();
+
+        let mut steve = Entity::player("0-0-0-0-1", "Steve");
+        steve.tags.insert("admin".to_string());
+        steve.team = Some("red".to_string());
+        steve.level = 30;
+        steve.advancements.insert(
+            "story/root".parse().unwrap(),
+            [("crafting_table".to_string(), true)].into(),
+        );
+
+        let mut alex = Entity::player("0-0-0-0-2", "Alex");
+        alex.position = [10.0, 0.0, 0.0];
+        alex.rotation = [90.0, -45.0];
+        alex.gamemode = Some(Gamemode::Creative);
+
+        let mut zombie = Entity::new("0-0-0-0-3", "zombie".parse().unwrap());
+        zombie.position = [3.0, 0.0, 4.0];
+        zombie.width = 0.6;
+        zombie.height = 1.95;
+        zombie
+            .nbt
+            .insert(SNBTString(false, "IsBaby".to_string()), SNBT::Byte(1));
+
+        let mut cow = Entity::new("0-0-0-0-4", "cow".parse().unwrap());
+        cow.position = [-2.0, 0.0, 0.0];
+        cow.name = Some("Bessie".to_string());
+
+        world.entities = vec![steve, alex, zombie, cow];
+        world.entity_type_tags.insert(
+            "undead".parse().unwrap(),
+            vec!["zombie".parse().unwrap(), "skeleton".parse().unwrap()],
+        );
+        world.scoreboard.add_objective("kills", "dummy").unwrap();
+        world.scoreboard.set_score("Alex", "kills", 5).unwrap();
+        world.scoreboard.set_score("0-0-0-0-3", "kills", 1).unwrap();
+
+        world
+    }
+
+    fn select(world: &World, context: &Context, selector: &str) -> Vec<String> {
+        selector
+            .parse::<EntitySelector>()
+            .unwrap()
+            .select(world, context)
+            .unwrap()
+            .into_iter()
+            .map(|entity| entity.uuid[8..].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let world = world();
+        let context = Context::with_executor("Steve");
+
+        assert_eq!(select(&world, &context, "@a"), ["1", "2"]);
+        assert_eq!(select(&world, &context, "@e"), ["1", "2", "3", "4"]);
+        assert_eq!(select(&world, &context, "@s"), ["1"]);
+        assert_eq!(select(&world, &context, "@s[tag=!admin]"), [] as [&str; 0]);
+        assert_eq!(select(&world, &context, "@p[x=9]"), ["2"]);
+        assert_eq!(select(&world, &context, "@n[type=!player]"), ["4"]);
+        assert_eq!(
+            select(&world, &context, "@e[sort=furthest,limit=2]"),
+            ["2", "3"]
+        );
+        assert_eq!(
+            select(&world, &context, "@e[distance=..5]"),
+            ["1", "3", "4"]
+        );
+        assert_eq!(select(&world, &context, "@e[x=2,y=0,z=3,dx=1,dz=1]"), ["3"]);
+        assert_eq!(select(&world, &context, "@e[type=#undead]"), ["3"]);
+        assert_eq!(select(&world, &context, "@e[nbt={IsBaby:1b}]"), ["3"]);
+        assert_eq!(
+            select(&world, &context, "@e[nbt={Tags:[\"admin\"]}]"),
+            ["1"]
+        );
+        assert_eq!(
+            select(&world, &context, "@e[scores={kills=1..}]"),
+            ["2", "3"]
+        );
+        assert_eq!(select(&world, &context, "@e[tag=]"), ["2", "3", "4"]);
+        assert_eq!(select(&world, &context, "@e[team=!]"), ["1"]);
+        assert_eq!(
+            select(&world, &context, "@e[name=!Bessie,type=!player]"),
+            ["3"]
+        );
+        assert_eq!(select(&world, &context, "@a[x_rotation=-90..-10]"), ["2"]);
+        assert_eq!(select(&world, &context, "@a[gamemode=!survival]"), ["2"]);
+        assert_eq!(select(&world, &context, "@e[level=10..]"), ["1"]);
+        assert_eq!(
+            select(&world, &context, "@a[advancements={story/root=true}]"),
+            ["1"]
+        );
+        assert_eq!(
+            select(
+                &world,
+                &context,
+                "@a[advancements={story/root={crafting_table=false}}]"
+            ),
+            ["2"]
+        );
+        assert_eq!(select(&world, &context, "Alex"), ["2"]);
+        assert_eq!(select(&world, &context, "0-0-0-0-4"), ["4"]);
+
+        let random = select(&world, &context, "@e[sort=random]");
+        assert_eq!(random.len(), 4);
+        assert_eq!(random, select(&world, &context, "@e[sort=random]"));
+
+        assert!(matches!(
+            "@e[predicate=test:p]"
+                .parse::<EntitySelector>()
+                .unwrap()
+                .select(&world, &context),
+            Err(SimulationError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,1565 @@
+//! An interpreter for a subset of commands, for testing datapack logic without the game.
+//!
+//! Score holders are plain names, or the entities of the [`World`] that selectors select. NBT data
+//! can only be read from and written to command storage.
+
+pub mod entity;
+pub mod position;
+pub mod scoreboard;
+pub mod storage;
+
+use crate::command::Command;
+use crate::command::PlayerScore;
+use crate::command::data::{DataCommand, DataCommandModification, DataTarget};
+use crate::command::enums::entity_anchor::EntityAnchor;
+use crate::command::enums::numeric_snbt_type::NumericSNBTType;
+use crate::command::enums::score_operation_operator::ScoreOperationOperator;
+use crate::command::enums::store_type::StoreType;
+use crate::command::execute::{
+    ExecuteIfSubcommand, ExecuteStoreSubcommand, ExecuteSubcommand, Facing, Positioned, Rotated,
+    ScoreComparison, ScoreComparisonOperator,
+};
+use crate::command::function::FunctionCommandArguments;
+use crate::command::r#return::ReturnCommand;
+use crate::command::scoreboard::{
+    ObjectivesScoreboardCommand, PlayersDisplayScoreboardCommand, PlayersScoreboardCommand,
+    ScoreboardCommand, ScoreboardModification,
+};
+use crate::datapack::Datapack;
+use crate::datapack::function::{McFunction, McFunctionLine};
+use crate::datapack::tag::{TagType, TagValue};
+use crate::entity_selector::{EntitySelector, EntitySelectorVariable};
+use crate::macros::MacroError;
+use crate::nbt_eval::{NbtPathError, merge_compound};
+use crate::nbt_path::{NbtPath, SNBTCompound};
+use crate::parser::{ParseError, parse_all};
+use crate::resource_location::ResourceLocation;
+use crate::simulator::entity::Entity;
+use crate::simulator::scoreboard::{Scoreboard, apply_operation};
+use crate::simulator::storage::{Storage, as_number, as_text, substring};
+use crate::snbt::SNBT;
+use ordered_float::NotNan;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SimulationError {
+    UnknownObjective(String),
+    DuplicateObjective(String),
+    ReadOnlyObjective(String),
+    NotATrigger(String),
+    /// A score holder has no score in an objective.
+    NoScore(String, String),
+    DivideByZero,
+    NoTargets,
+    ConditionFailed,
+    /// A function ended with `return fail`.
+    ReturnFail,
+    Macro(MacroError),
+    Nbt(NbtPathError),
+    /// A command that must change NBT data didn't.
+    NothingChanged,
+    /// A path that must match a single value matched several.
+    MultipleValues,
+    NotANumber(SNBT),
+    ExpectedValue(SNBT),
+    InvalidSubstring(i64, i64),
+    UnknownFunction(ResourceLocation),
+    InvalidCommand(String, ParseError),
+    Unsupported(String),
+    CommandChainLimit,
+    RecursionLimit,
+}
+
+impl SimulationError {
+    /// Whether the game treats this as a failed command and carries on with the next one.
+    /// Other errors stop the simulation.
+    #[must_use]
+    pub fn is_command_failure(&self) -> bool {
+        !matches!(
+            self,
+            SimulationError::UnknownFunction(_)
+                | SimulationError::InvalidCommand(..)
+                | SimulationError::Unsupported(_)
+                | SimulationError::CommandChainLimit
+                | SimulationError::RecursionLimit
+        )
+    }
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulationError::UnknownObjective(name) => {
+                write!(f, "Unknown scoreboard objective '{}'", name)
+            }
+            SimulationError::DuplicateObjective(name) => {
+                write!(f, "An objective already exists by the name '{}'", name)
+            }
+            SimulationError::ReadOnlyObjective(name) => {
+                write!(f, "Objective '{}' is read-only", name)
+            }
+            SimulationError::NotATrigger(name) => {
+                write!(f, "Objective '{}' is not a trigger objective", name)
+            }
+            SimulationError::NoScore(holder, objective) => {
+                write!(
+                    f,
+                    "Can't get value of {} for {}; none is set",
+                    objective, holder
+                )
+            }
+            SimulationError::DivideByZero => f.write_str("Cannot divide by zero"),
+            SimulationError::NoTargets => f.write_str("No entity was found"),
+            SimulationError::ConditionFailed => f.write_str("Test failed"),
+            SimulationError::ReturnFail => f.write_str("Function returned with failure"),
+            SimulationError::Macro(error) => error.fmt(f),
+            SimulationError::Nbt(error) => error.fmt(f),
+            SimulationError::NothingChanged => {
+                f.write_str("Nothing changed. The specified properties already have these values")
+            }
+            SimulationError::MultipleValues => f.write_str("This target has multiple NBT elements"),
+            SimulationError::NotANumber(value) => {
+                write!(f, "Can't get {}; only numeric tags are allowed", value)
+            }
+            SimulationError::ExpectedValue(value) => write!(f, "Expected value, got: {}", value),
+            SimulationError::InvalidSubstring(start, end) => {
+                write!(f, "Invalid substring indices: {} to {}", start, end)
+            }
+            SimulationError::UnknownFunction(function) => {
+                write!(f, "Unknown function '{}'", function)
+            }
+            SimulationError::InvalidCommand(command, error) => {
+                write!(f, "Invalid command '{}': {}", command, error)
+            }
+            SimulationError::Unsupported(command) => {
+                write!(f, "Simulating '{}' is not supported", command)
+            }
+            SimulationError::CommandChainLimit => f.write_str("Command chain limit reached"),
+            SimulationError::RecursionLimit => f.write_str("Function recursion limit reached"),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+impl From<NbtPathError> for SimulationError {
+    fn from(error: NbtPathError) -> Self {
+        SimulationError::Nbt(error)
+    }
+}
+
+/// The state commands act on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct World {
+    pub scoreboard: Scoreboard,
+    pub storage: Storage,
+    /// Entities in the order the game iterates them.
+    pub entities: Vec<Entity>,
+    /// The entries of entity type tags, which may be tags themselves.
+    pub entity_type_tags: BTreeMap<ResourceLocation, Vec<ResourceLocation>>,
+    /// Seeds `@r` and `sort=random`, so random selections are reproducible.
+    pub seed: u64,
+}
+
+impl World {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The context a command is executed in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    /// The score holder `@s` refers to, which is the entity with that [`Entity::score_holder`]
+    /// if there is one.
+    pub executor: Option<String>,
+    pub position: [f64; 3],
+    /// The yaw and pitch in degrees.
+    pub rotation: [f32; 2],
+    pub anchor: EntityAnchor,
+}
+
+impl Context {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_executor<T: ToString>(executor: T) -> Self {
+        Self {
+            executor: Some(executor.to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+enum Flow {
+    /// The command finished. Functions without `return` have no result.
+    Result(Option<i32>),
+    /// A `return` ended the current function, `None` for `return fail`.
+    Return(Option<i32>),
+}
+
+pub struct Simulator<'a> {
+    pub world: World,
+    /// The most commands a single [`Simulator::run`] or [`Simulator::run_function`] may execute,
+    /// like the `maxCommandChainLength` game rule.
+    pub max_command_chain_length: usize,
+    /// How deeply functions may call each other before [`SimulationError::RecursionLimit`]. The
+    /// game has no such limit, but each call uses memory here.
+    pub max_function_depth: usize,
+    datapack: Option<&'a Datapack>,
+    commands_run: usize,
+    depth: usize,
+}
+
+impl<'a> Simulator<'a> {
+    #[must_use]
+    pub fn new(world: World) -> Self {
+        Self {
+            world,
+            max_command_chain_length: 65536,
+            max_function_depth: 16384,
+            datapack: None,
+            commands_run: 0,
+            depth: 0,
+        }
+    }
+
+    /// `function` commands run the functions of `datapack`. Overlays aren't considered.
+    #[must_use]
+    pub fn with_datapack(world: World, datapack: &'a Datapack) -> Self {
+        Self {
+            datapack: Some(datapack),
+            ..Self::new(world)
+        }
+    }
+
+    /// Runs a single command and returns its result, which is `None` for a function that
+    /// doesn't `return`. Errors the game would only report as a failed command are returned too,
+    /// see [`SimulationError::is_command_failure`].
+    pub fn run(
+        &mut self,
+        command: &Command,
+        context: &Context,
+    ) -> Result<Option<i32>, SimulationError> {
+        self.commands_run = 0;
+
+        match self.exec(command, context)? {
+            Flow::Result(result) => Ok(result),
+            Flow::Return(value) => value.map(Some).ok_or(SimulationError::ReturnFail),
+        }
+    }
+
+    /// Runs a function or every function of a function tag, like `function` does.
+    pub fn run_function(
+        &mut self,
+        function: &ResourceLocation,
+        arguments: Option<&SNBTCompound>,
+        context: &Context,
+    ) -> Result<Option<i32>, SimulationError> {
+        self.commands_run = 0;
+        self.call_function(function, arguments, context)
+    }
+
+    fn exec(&mut self, command: &Command, context: &Context) -> Result<Flow, SimulationError> {
+        self.commands_run += 1;
+
+        if self.commands_run > self.max_command_chain_length {
+            return Err(SimulationError::CommandChainLimit);
+        }
+
+        match command {
+            Command::Scoreboard(command) => self
+                .scoreboard(command, context)
+                .map(|result| Flow::Result(Some(result))),
+            Command::Function(function, arguments) => {
+                let arguments = match arguments {
+                    None => None,
+                    Some(FunctionCommandArguments::Compound(compound)) => Some(compound.clone()),
+                    Some(FunctionCommandArguments::DataTarget(target, path)) => {
+                        Some(self.function_arguments(target, path.as_ref())?)
+                    }
+                };
+
+                self.call_function(function, arguments.as_ref(), context)
+                    .map(Flow::Result)
+            }
+            Command::Data(command) => self.data(command).map(|result| Flow::Result(Some(result))),
+            Command::Return(ReturnCommand::Value(value)) => Ok(Flow::Return(Some(*value))),
+            Command::Return(ReturnCommand::Fail) => Ok(Flow::Return(None)),
+            Command::Return(ReturnCommand::Run(command)) => match self.exec(command, context) {
+                Ok(Flow::Result(Some(result))) => Ok(Flow::Return(Some(result))),
+                Err(error) if error.is_command_failure() => Ok(Flow::Return(None)),
+                flow => flow,
+            },
+            Command::Execute(subcommand) => self.execute(subcommand, context),
+            _ => Err(SimulationError::Unsupported(command.to_string())),
+        }
+    }
+
+    fn functions(
+        &self,
+        function: &ResourceLocation,
+    ) -> Result<Vec<&'a McFunction>, SimulationError> {
+        let mut functions = Vec::new();
+        self.collect_functions(function, &mut functions, &mut BTreeSet::new())?;
+
+        Ok(functions)
+    }
+
+    fn collect_functions(
+        &self,
+        function: &ResourceLocation,
+        functions: &mut Vec<&'a McFunction>,
+        visited_tags: &mut BTreeSet<ResourceLocation>,
+    ) -> Result<(), SimulationError> {
+        let unknown = || SimulationError::UnknownFunction(function.clone());
+        let namespace = self
+            .datapack
+            .and_then(|datapack| datapack.namespaces.get(function.namespace()))
+            .ok_or_else(unknown)?;
+
+        if !function.is_tag {
+            functions.push(
+                namespace
+                    .functions
+                    .get(&function.paths)
+                    .ok_or_else(unknown)?,
+            );
+
+            return Ok(());
+        }
+
+        if !visited_tags.insert(function.clone()) {
+            return Ok(());
+        }
+
+        let tag = namespace
+            .tags
+            .get(&TagType::Function)
+            .and_then(|tags| tags.get(&function.paths))
+            .ok_or_else(unknown)?;
+
+        for value in &tag.values {
+            let (id, required) = match value {
+                TagValue::ResourceLocation(id) => (id.clone(), true),
+                TagValue::Id(id) | TagValue::Explicit { id, required: None } => {
+                    (parse_tag_entry(id)?, true)
+                }
+                TagValue::Explicit {
+                    id,
+                    required: Some(required),
+                } => (parse_tag_entry(id)?, *required),
+            };
+
+            match self.collect_functions(&id, functions, visited_tags) {
+                Err(SimulationError::UnknownFunction(_)) if !required => {}
+                result => result?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn call_function(
+        &mut self,
+        function: &ResourceLocation,
+        arguments: Option<&SNBTCompound>,
+        context: &Context,
+    ) -> Result<Option<i32>, SimulationError> {
+        let mut result = Ok(None);
+
+        for function in self.functions(function)? {
+            match self.run_body(function, arguments, context) {
+                Err(error) if !error.is_command_failure() => return Err(error),
+                Ok(None) => {}
+                function_result => result = function_result,
+            }
+        }
+
+        result
+    }
+
+    fn run_body(
+        &mut self,
+        function: &McFunction,
+        arguments: Option<&SNBTCompound>,
+        context: &Context,
+    ) -> Result<Option<i32>, SimulationError> {
+        if self.depth >= self.max_function_depth {
+            return Err(SimulationError::RecursionLimit);
+        }
+
+        // Every call recurses, so the stack is grown on the heap for deep recursion.
+        self.depth += 1;
+        let result = stacker::maybe_grow(512 * 1024, 8 * 1024 * 1024, || {
+            self.run_lines(function, arguments, context)
+        });
+        self.depth -= 1;
+
+        result
+    }
+
+    fn run_lines(
+        &mut self,
+        function: &McFunction,
+        arguments: Option<&SNBTCompound>,
+        context: &Context,
+    ) -> Result<Option<i32>, SimulationError> {
+        let substituted;
+        let function = if arguments.is_some() || !function.macro_variables().is_empty() {
+            substituted = function
+                .substitute_macros(arguments.unwrap_or(&SNBTCompound::new()))
+                .map_err(SimulationError::Macro)?;
+
+            &substituted
+        } else {
+            function
+        };
+
+        for line in &function.lines {
+            let flow = match line {
+                McFunctionLine::Command(command) => self.exec(command, context),
+                McFunctionLine::Raw(raw) => {
+                    let raw = raw.trim();
+
+                    if raw.is_empty() || raw.starts_with('#') {
+                        continue;
+                    }
+
+                    let command: Command = parse_all(raw)
+                        .map_err(|error| SimulationError::InvalidCommand(raw.to_string(), error))?;
+
+                    self.exec(&command, context)
+                }
+                McFunctionLine::Comment(_) | McFunctionLine::Blank => continue,
+            };
+
+            match flow {
+                Ok(Flow::Return(value)) => {
+                    return value.map(Some).ok_or(SimulationError::ReturnFail);
+                }
+                Err(error) if !error.is_command_failure() => return Err(error),
+                _ => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn select(
+        &self,
+        selector: &EntitySelector,
+        context: &Context,
+    ) -> Result<Vec<String>, SimulationError> {
+        match selector {
+            EntitySelector::Name(name) => Ok(vec![name.clone()]),
+            EntitySelector::Variable(EntitySelectorVariable::S, options) if options.is_empty() => {
+                Ok(context.executor.iter().cloned().collect())
+            }
+            selector => Ok(selector
+                .select(&self.world, context)?
+                .into_iter()
+                .map(Entity::score_holder)
+                .collect()),
+        }
+    }
+
+    /// Like [`Simulator::select`], but `*` selects every score holder and selecting nothing is an
+    /// error.
+    fn score_holders(
+        &self,
+        selector: &EntitySelector,
+        context: &Context,
+    ) -> Result<Vec<String>, SimulationError> {
+        let holders = match selector {
+            EntitySelector::Name(name) if name == "*" => {
+                self.world.scoreboard.holders().into_iter().collect()
+            }
+            selector => self.select(selector, context)?,
+        };
+
+        if holders.is_empty() {
+            return Err(SimulationError::NoTargets);
+        }
+
+        Ok(holders)
+    }
+
+    fn score_holder(
+        &self,
+        selector: &EntitySelector,
+        context: &Context,
+    ) -> Result<String, SimulationError> {
+        Ok(self.score_holders(selector, context)?.swap_remove(0))
+    }
+
+    fn execute(
+        &mut self,
+        subcommand: &ExecuteSubcommand,
+        context: &Context,
+    ) -> Result<Flow, SimulationError> {
+        match subcommand {
+            ExecuteSubcommand::As(selector, next) => {
+                let contexts = self
+                    .select(selector, context)?
+                    .into_iter()
+                    .map(|executor| {
+                        let mut context = context.clone();
+                        context.executor = Some(executor);
+                        context
+                    })
+                    .collect();
+
+                self.fork(contexts, next)
+            }
+            ExecuteSubcommand::At(selector, next) => {
+                let contexts = self.contexts_for(selector, context, |context, entity| {
+                    context.position = entity.position;
+                    context.rotation = entity.rotation;
+                })?;
+
+                self.fork(contexts, next)
+            }
+            ExecuteSubcommand::Positioned(Positioned::As(selector), next) => {
+                let contexts = self.contexts_for(selector, context, |context, entity| {
+                    context.position = entity.position;
+                })?;
+
+                self.fork(contexts, next)
+            }
+            ExecuteSubcommand::Rotated(Rotated::As(selector), next) => {
+                let contexts = self.contexts_for(selector, context, |context, entity| {
+                    context.rotation = entity.rotation;
+                })?;
+
+                self.fork(contexts, next)
+            }
+            ExecuteSubcommand::Facing(Facing::Entity(selector, anchor), next) => {
+                let contexts = self.contexts_for(selector, context, |context, entity| {
+                    context.face(&self.world, entity.anchor_position(*anchor));
+                })?;
+
+                self.fork(contexts, next)
+            }
+            ExecuteSubcommand::Positioned(Positioned::Position(coordinates), next) => {
+                let mut context = context.clone();
+                context.position = coordinates.resolve(&self.world, &context);
+                context.anchor = EntityAnchor::Feet;
+
+                self.execute(next, &context)
+            }
+            ExecuteSubcommand::Rotated(Rotated::Rotation(rotation), next) => {
+                let mut context = context.clone();
+                context.rotation = rotation.resolve(&context);
+
+                self.execute(next, &context)
+            }
+            ExecuteSubcommand::Facing(Facing::Position(coordinates), next) => {
+                let mut context = context.clone();
+                context.face(&self.world, coordinates.resolve(&self.world, &context));
+
+                self.execute(next, &context)
+            }
+            ExecuteSubcommand::Align(axes, next) => {
+                let mut context = context.clone();
+                context.align(axes);
+
+                self.execute(next, &context)
+            }
+            ExecuteSubcommand::Anchored(anchor, next) => {
+                let mut context = context.clone();
+                context.anchor = *anchor;
+
+                self.execute(next, &context)
+            }
+            // Dimensions aren't simulated.
+            ExecuteSubcommand::In(_, next) => self.execute(next, context),
+            ExecuteSubcommand::Positioned(Positioned::Over(_), _)
+            | ExecuteSubcommand::On(..)
+            | ExecuteSubcommand::Summon(..) => {
+                Err(SimulationError::Unsupported(subcommand.to_string()))
+            }
+            ExecuteSubcommand::If(is_inverted, condition) => {
+                self.execute_if(*is_inverted, condition, context)
+            }
+            ExecuteSubcommand::Store(store_type, store) => {
+                self.execute_store(store_type, store, context)
+            }
+            ExecuteSubcommand::Run(command) => self.exec(command, context),
+        }
+    }
+
+    /// A copy of `context` for each entity `selector` selects, changed by `update`.
+    fn contexts_for(
+        &self,
+        selector: &EntitySelector,
+        context: &Context,
+        update: impl Fn(&mut Context, &Entity),
+    ) -> Result<Vec<Context>, SimulationError> {
+        Ok(selector
+            .select(&self.world, context)?
+            .into_iter()
+            .map(|entity| {
+                let mut context = context.clone();
+                update(&mut context, entity);
+                context
+            })
+            .collect())
+    }
+
+    /// Runs `next` in each context. The result is the one of the last context.
+    fn fork(
+        &mut self,
+        contexts: Vec<Context>,
+        next: &ExecuteSubcommand,
+    ) -> Result<Flow, SimulationError> {
+        let mut result = Err(SimulationError::NoTargets);
+
+        for context in contexts {
+            match self.execute(next, &context) {
+                Ok(Flow::Return(value)) => return Ok(Flow::Return(value)),
+                Err(error) if !error.is_command_failure() => return Err(error),
+                flow => result = flow,
+            }
+        }
+
+        result
+    }
+
+    fn execute_if(
+        &mut self,
+        is_inverted: bool,
+        condition: &ExecuteIfSubcommand,
+        context: &Context,
+    ) -> Result<Flow, SimulationError> {
+        let (passed, next) = match condition {
+            ExecuteIfSubcommand::Score(score, comparison, next) => (
+                self.test_score(score, comparison, context)?,
+                next.as_deref(),
+            ),
+            ExecuteIfSubcommand::Function(function, next) => {
+                (self.test_function(function, context)?, Some(next.as_ref()))
+            }
+            ExecuteIfSubcommand::Data(target, path, next) => {
+                let root = self.read_data(target)?;
+
+                (path.get(&root).is_ok(), next.as_deref())
+            }
+            _ => {
+                return Err(SimulationError::Unsupported(format!(
+                    "execute {} {}",
+                    if is_inverted { "unless" } else { "if" },
+                    condition
+                )));
+            }
+        };
+
+        if passed == is_inverted {
+            return Err(SimulationError::ConditionFailed);
+        }
+
+        match next {
+            Some(next) => self.execute(next, context),
+            None => Ok(Flow::Result(Some(1))),
+        }
+    }
+
+    fn test_score(
+        &self,
+        score: &PlayerScore,
+        comparison: &ScoreComparison,
+        context: &Context,
+    ) -> Result<bool, SimulationError> {
+        let holder = self.score_holder(&score.selector, context)?;
+        let objective = self.world.scoreboard.objective(&score.objective)?;
+        let Some(&value) = objective.scores.get(&holder) else {
+            return Ok(false);
+        };
+
+        match comparison {
+            ScoreComparison::Range(range) => Ok(range.contains(value)),
+            ScoreComparison::Score(operator, other) => {
+                let other_holder = self.score_holder(&other.selector, context)?;
+                let other_objective = self.world.scoreboard.objective(&other.objective)?;
+                let Some(&other_value) = other_objective.scores.get(&other_holder) else {
+                    return Ok(false);
+                };
+
+                Ok(match operator {
+                    ScoreComparisonOperator::LessThan => value < other_value,
+                    ScoreComparisonOperator::LessThanOrEqualTo => value <= other_value,
+                    ScoreComparisonOperator::EqualTo => value == other_value,
+                    ScoreComparisonOperator::GreaterThan => value > other_value,
+                    ScoreComparisonOperator::GreaterThanOrEqualTo => value >= other_value,
+                })
+            }
+        }
+    }
+
+    /// Passes if any of the functions returns a non-zero value.
+    fn test_function(
+        &mut self,
+        function: &ResourceLocation,
+        context: &Context,
+    ) -> Result<bool, SimulationError> {
+        let mut passed = false;
+
+        for function in self.functions(function)? {
+            match self.run_body(function, None, context) {
+                Ok(Some(value)) => passed |= value != 0,
+                Err(error) if !error.is_command_failure() => return Err(error),
+                _ => {}
+            }
+        }
+
+        Ok(passed)
+    }
+
+    fn execute_store(
+        &mut self,
+        store_type: &StoreType,
+        store: &ExecuteStoreSubcommand,
+        context: &Context,
+    ) -> Result<Flow, SimulationError> {
+        // The targets are selected before the rest of the chain changes the context.
+        let (holders, next) = match store {
+            ExecuteStoreSubcommand::Score(score, next) => {
+                (self.score_holders(&score.selector, context)?, next)
+            }
+            ExecuteStoreSubcommand::Data(target, .., next) => {
+                self.read_data(target)?;
+
+                (Vec::new(), next)
+            }
+            ExecuteStoreSubcommand::Bossbar(..) => {
+                return Err(SimulationError::Unsupported(format!(
+                    "execute store {} {}",
+                    store_type, store
+                )));
+            }
+        };
+
+        let flow = self.execute(next, context);
+
+        let value = match (&flow, store_type) {
+            (Ok(Flow::Result(Some(result))), StoreType::Result) => *result,
+            (Ok(Flow::Result(Some(_))), StoreType::Success) => 1,
+            (Err(error), _) if error.is_command_failure() => 0,
+            _ => return flow,
+        };
+
+        match store {
+            ExecuteStoreSubcommand::Score(score, _) => {
+                for holder in &holders {
+                    self.world
+                        .scoreboard
+                        .set_score(holder, &score.objective, value)?;
+                }
+            }
+            ExecuteStoreSubcommand::Data(target, path, numeric_type, scale, _) => {
+                let mut root = self.read_data(target)?;
+                path.set(&mut root, &stored_value(value, numeric_type, *scale))?;
+                self.write_data(target, root)?;
+            }
+            ExecuteStoreSubcommand::Bossbar(..) => {}
+        }
+
+        flow
+    }
+
+    fn scoreboard(
+        &mut self,
+        command: &ScoreboardCommand,
+        context: &Context,
+    ) -> Result<i32, SimulationError> {
+        match command {
+            ScoreboardCommand::Objectives(command) => self.objectives(command),
+            ScoreboardCommand::Players(command) => self.players(command, context),
+        }
+    }
+
+    fn objectives(
+        &mut self,
+        command: &ObjectivesScoreboardCommand,
+    ) -> Result<i32, SimulationError> {
+        let scoreboard = &mut self.world.scoreboard;
+
+        match command {
+            ObjectivesScoreboardCommand::List => {}
+            ObjectivesScoreboardCommand::Add(name, criterion, display_name) => {
+                scoreboard.add_objective(name, criterion)?.display_name = display_name.clone();
+            }
+            ObjectivesScoreboardCommand::Remove(name) => {
+                scoreboard
+                    .objectives
+                    .remove(name)
+                    .ok_or_else(|| SimulationError::UnknownObjective(name.clone()))?;
+                scoreboard
+                    .display_slots
+                    .retain(|_, objective| objective != name);
+            }
+            ObjectivesScoreboardCommand::SetDisplay(slot, name) => {
+                match name {
+                    Some(name) => {
+                        scoreboard.objective(name)?;
+                        scoreboard.display_slots.insert(slot.clone(), name.clone());
+                    }
+                    None => {
+                        scoreboard.display_slots.remove(slot);
+                    }
+                }
+
+                return Ok(0);
+            }
+            ObjectivesScoreboardCommand::Modify(name, modification) => {
+                let objective = scoreboard.objective_mut(name)?;
+
+                if let ScoreboardModification::DisplayName(display_name) = modification {
+                    objective.display_name = Some(display_name.clone());
+                }
+
+                return Ok(0);
+            }
+        }
+
+        Ok(scoreboard.objectives.len() as i32)
+    }
+
+    fn players(
+        &mut self,
+        command: &PlayersScoreboardCommand,
+        context: &Context,
+    ) -> Result<i32, SimulationError> {
+        match command {
+            PlayersScoreboardCommand::List(None) => {
+                Ok(self.world.scoreboard.holders().len() as i32)
+            }
+            PlayersScoreboardCommand::List(Some(selector)) => {
+                let holder = self.score_holder(selector, context)?;
+
+                Ok(self
+                    .world
+                    .scoreboard
+                    .objectives
+                    .values()
+                    .filter(|objective| objective.scores.contains_key(&holder))
+                    .count() as i32)
+            }
+            PlayersScoreboardCommand::Get(score) => {
+                let holder = self.score_holder(&score.selector, context)?;
+
+                self.world
+                    .scoreboard
+                    .objective(&score.objective)?
+                    .scores
+                    .get(&holder)
+                    .copied()
+                    .ok_or_else(|| SimulationError::NoScore(holder, score.objective.clone()))
+            }
+            PlayersScoreboardCommand::Set(score, value) => {
+                let holders = self.score_holders(&score.selector, context)?;
+                let objective = self.world.scoreboard.writable_objective(&score.objective)?;
+
+                for holder in &holders {
+                    objective.scores.insert(holder.clone(), *value);
+                }
+
+                Ok(value.wrapping_mul(holders.len() as i32))
+            }
+            PlayersScoreboardCommand::Add(score, amount) => {
+                self.add_scores(score, *amount, context)
+            }
+            PlayersScoreboardCommand::Remove(score, amount) => {
+                self.add_scores(score, amount.wrapping_neg(), context)
+            }
+            PlayersScoreboardCommand::Reset(selector, objective) => {
+                let holders = self.score_holders(selector, context)?;
+                let scoreboard = &mut self.world.scoreboard;
+
+                let objectives = match objective {
+                    Some(objective) => vec![scoreboard.objective_mut(objective)?],
+                    None => scoreboard.objectives.values_mut().collect(),
+                };
+
+                for objective in objectives {
+                    for holder in &holders {
+                        objective.scores.remove(holder);
+                    }
+                }
+
+                Ok(holders.len() as i32)
+            }
+            PlayersScoreboardCommand::Enable(score) => {
+                let holders = self.score_holders(&score.selector, context)?;
+                let objective = self.world.scoreboard.objective_mut(&score.objective)?;
+
+                if objective.criterion != "trigger" {
+                    return Err(SimulationError::NotATrigger(score.objective.clone()));
+                }
+
+                for holder in &holders {
+                    objective.scores.entry(holder.clone()).or_insert(0);
+                }
+
+                Ok(holders.len() as i32)
+            }
+            PlayersScoreboardCommand::Operation(target, operator, source) => {
+                self.operation(target, *operator, source, context)
+            }
+            PlayersScoreboardCommand::Display(
+                PlayersDisplayScoreboardCommand::Name(score, _)
+                | PlayersDisplayScoreboardCommand::NumberFormat(score, _),
+            ) => {
+                let holders = self.score_holders(&score.selector, context)?;
+                self.world.scoreboard.objective(&score.objective)?;
+
+                Ok(holders.len() as i32)
+            }
+        }
+    }
+
+    /// Adds `amount` to every selected score and returns the sum of the new scores.
+    fn add_scores(
+        &mut self,
+        score: &PlayerScore,
+        amount: i32,
+        context: &Context,
+    ) -> Result<i32, SimulationError> {
+        let holders = self.score_holders(&score.selector, context)?;
+        let objective = self.world.scoreboard.writable_objective(&score.objective)?;
+        let mut sum = 0i32;
+
+        for holder in holders {
+            let value = objective.scores.entry(holder).or_insert(0);
+            *value = value.wrapping_add(amount);
+            sum = sum.wrapping_add(*value);
+        }
+
+        Ok(sum)
+    }
+
+    /// Applies `operator` for every pair of target and source, and returns the sum of the new
+    /// target scores. Missing scores are created as 0.
+    fn operation(
+        &mut self,
+        target: &PlayerScore,
+        operator: ScoreOperationOperator,
+        source: &PlayerScore,
+        context: &Context,
+    ) -> Result<i32, SimulationError> {
+        let targets = self.score_holders(&target.selector, context)?;
+        let sources = self.score_holders(&source.selector, context)?;
+        let scoreboard = &mut self.world.scoreboard;
+
+        scoreboard.writable_objective(&target.objective)?;
+        scoreboard.objective(&source.objective)?;
+
+        let mut sum = 0i32;
+
+        for target_holder in &targets {
+            for source_holder in &sources {
+                let target_value = scoreboard
+                    .score(target_holder, &target.objective)
+                    .unwrap_or(0);
+                let source_value = *scoreboard
+                    .objective_mut(&source.objective)?
+                    .scores
+                    .entry(source_holder.clone())
+                    .or_insert(0);
+
+                let (target_value, source_value) =
+                    apply_operation(operator, target_value, source_value)?;
+
+                scoreboard.set_score(target_holder, &target.objective, target_value)?;
+
+                if operator == ScoreOperationOperator::Swap {
+                    scoreboard.set_score(source_holder, &source.objective, source_value)?;
+                }
+            }
+
+            let value = scoreboard
+                .score(target_holder, &target.objective)
+                .unwrap_or(0);
+            sum = sum.wrapping_add(value);
+        }
+
+        Ok(sum)
+    }
+
+    fn read_data(&self, target: &DataTarget) -> Result<SNBT, SimulationError> {
+        match target {
+            DataTarget::Storage(id) => Ok(SNBT::Compound(self.world.storage.get(id))),
+            target => Err(SimulationError::Unsupported(target.to_string())),
+        }
+    }
+
+    fn write_data(&mut self, target: &DataTarget, data: SNBT) -> Result<(), SimulationError> {
+        match (target, data) {
+            (DataTarget::Storage(id), SNBT::Compound(compound)) => {
+                self.world.storage.set(id, compound);
+
+                Ok(())
+            }
+            (DataTarget::Storage(_), data) => Err(NbtPathError::ExpectedCompound(data).into()),
+            (target, _) => Err(SimulationError::Unsupported(target.to_string())),
+        }
+    }
+
+    /// The macro arguments of `function … with`.
+    fn function_arguments(
+        &self,
+        target: &DataTarget,
+        path: Option<&NbtPath>,
+    ) -> Result<SNBTCompound, SimulationError> {
+        let root = self.read_data(target)?;
+
+        match path {
+            None => Ok(root),
+            Some(path) => single_value(path, &root),
+        }
+        .and_then(|arguments| match arguments {
+            SNBT::Compound(compound) => Ok(compound),
+            arguments => Err(NbtPathError::ExpectedCompound(arguments).into()),
+        })
+    }
+
+    fn data(&mut self, command: &DataCommand) -> Result<i32, SimulationError> {
+        match command {
+            DataCommand::Get(target, None, _) => {
+                self.read_data(target)?;
+
+                Ok(1)
+            }
+            DataCommand::Get(target, Some(path), scale) => {
+                let value = single_value(path, &self.read_data(target)?)?;
+
+                match scale {
+                    Some(scale) => as_number(&value)
+                        .map(|number| (number * scale.into_inner() as f64).floor() as i32)
+                        .ok_or(SimulationError::NotANumber(value)),
+                    None => data_value(&value),
+                }
+            }
+            DataCommand::Merge(target, nbt) => {
+                let SNBT::Compound(source) = nbt else {
+                    return Err(NbtPathError::ExpectedCompound(nbt.clone()).into());
+                };
+
+                let mut root = self.read_data(target)?;
+                let SNBT::Compound(compound) = &mut root else {
+                    return Err(NbtPathError::ExpectedCompound(root).into());
+                };
+                let original = compound.clone();
+                merge_compound(compound, source);
+
+                if *compound == original {
+                    return Err(SimulationError::NothingChanged);
+                }
+
+                self.write_data(target, root)?;
+
+                Ok(1)
+            }
+            DataCommand::Modify(target, path, mode, modification) => {
+                let values = match modification {
+                    DataCommandModification::Value(value) => vec![value.clone()],
+                    DataCommandModification::From(source, source_path) => {
+                        let source = self.read_data(source)?;
+
+                        match source_path {
+                            Some(source_path) => source_path.get(&source)?,
+                            None => vec![source],
+                        }
+                    }
+                    DataCommandModification::String(source, source_path, start, end) => {
+                        let source = self.read_data(source)?;
+                        let values = match source_path {
+                            Some(source_path) => source_path.get(&source)?,
+                            None => vec![source],
+                        };
+
+                        values
+                            .iter()
+                            .map(|value| {
+                                Ok(SNBT::string(substring(&as_text(value)?, *start, *end)?))
+                            })
+                            .collect::<Result<_, SimulationError>>()?
+                    }
+                };
+
+                let mut root = self.read_data(target)?;
+                let changed = path.modify(&mut root, mode, &values)?;
+
+                if changed == 0 {
+                    return Err(SimulationError::NothingChanged);
+                }
+
+                self.write_data(target, root)?;
+
+                Ok(changed as i32)
+            }
+            DataCommand::Remove(target, path) => {
+                let mut root = self.read_data(target)?;
+                let removed = path.remove(&mut root)?;
+
+                if removed == 0 {
+                    return Err(SimulationError::NothingChanged);
+                }
+
+                self.write_data(target, root)?;
+
+                Ok(removed as i32)
+            }
+        }
+    }
+}
+
+/// The only value `path` matches in `root`.
+fn single_value(path: &NbtPath, root: &SNBT) -> Result<SNBT, SimulationError> {
+    let mut values = path.get(root)?;
+
+    if values.len() > 1 {
+        return Err(SimulationError::MultipleValues);
+    }
+
+    Ok(values.swap_remove(0))
+}
+
+/// The result of `data get` for a value: numbers are floored, collections and strings give their
+/// length.
+fn data_value(value: &SNBT) -> Result<i32, SimulationError> {
+    if let Some(number) = as_number(value) {
+        return Ok(number.floor() as i32);
+    }
+
+    Ok(match value {
+        SNBT::String(string) => string.1.encode_utf16().count() as i32,
+        SNBT::List(values) => values.len() as i32,
+        SNBT::Compound(compound) => compound.len() as i32,
+        SNBT::ByteArray(values) => values.len() as i32,
+        SNBT::IntegerArray(values) => values.len() as i32,
+        SNBT::LongArray(values) => values.len() as i32,
+        value => return Err(SimulationError::ExpectedValue(value.clone())),
+    })
+}
+
+/// The value `execute store … storage` writes, which Java casts from `result * scale`.
+fn stored_value(result: i32, numeric_type: &NumericSNBTType, scale: NotNan<f32>) -> SNBT {
+    let value = result as f64 * scale.into_inner() as f64;
+
+    match numeric_type {
+        NumericSNBTType::Byte => SNBT::Byte(value as i32 as i8),
+        NumericSNBTType::Short => SNBT::Short(value as i32 as i16),
+        NumericSNBTType::Integer => SNBT::Integer(value as i32),
+        NumericSNBTType::Long => SNBT::Long(value as i64),
+        NumericSNBTType::Float => SNBT::Float(NotNan::new(value as f32).unwrap_or_default()),
+        NumericSNBTType::Double => SNBT::Double(NotNan::new(value).unwrap_or_default()),
+    }
+}
+
+fn parse_tag_entry(id: &str) -> Result<ResourceLocation, SimulationError> {
+    id.parse()
+        .map_err(|_| SimulationError::Unsupported(format!("function tag entry '{}'", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datapack::tag::Tag;
+    use nonempty::nonempty;
+    use serde_json::Value;
+
+    fn run(simulator: &mut Simulator, command: &str) -> Result<Option<i32>, SimulationError> {
+        simulator.run(&command.parse().unwrap(), &Context::with_executor("Steve"))
+    }
+
+    fn score(simulator: &Simulator, holder: &str, objective: &str) -> Option<i32> {
+        simulator.world.scoreboard.score(holder, objective)
+    }
+
+    fn datapack() -> Datapack {
+        let mut datapack = Datapack::new(48, Value::from("test"));
+        let namespace = datapack.get_namespace_mut("math");
+
+        namespace.add_function(
+            &nonempty!["abs".to_string()],
+            "execute if score @s value matches 0.. run return run scoreboard players get @s value\n\
+             scoreboard players operation @s value *= #-1 const\n\
+             return run scoreboard players get @s value",
+        );
+        namespace.add_function(
+            &nonempty!["count".to_string()],
+            "scoreboard players add @s value 1\n\
+             execute if score @s value < #limit const run function math:count",
+        );
+        namespace.add_function(
+            &nonempty!["scale".to_string()],
+            "$scoreboard players set @s value $(factor)\n\
+             return 1",
+        );
+        namespace.add_function(&nonempty!["loop".to_string()], "function math:loop");
+        namespace.add_tag(
+            TagType::Function,
+            &nonempty!["all".to_string()],
+            Tag {
+                replace: None,
+                values: vec![TagValue::ResourceLocation("math:count".parse().unwrap())],
+            },
+        );
+
+        datapack
+    }
+
+    #[test]
+    fn test_scoreboard() {
+        let mut simulator = Simulator::new(World::new());
+
+        run(&mut simulator, "scoreboard objectives add value dummy").unwrap();
+        assert_eq!(
+            run(&mut simulator, "scoreboard objectives add value dummy"),
+            Err(SimulationError::DuplicateObjective("value".to_string()))
+        );
+
+        run(&mut simulator, "scoreboard players set #a value 2147483647").unwrap();
+        run(&mut simulator, "scoreboard players add #a value 1").unwrap();
+        assert_eq!(score(&simulator, "#a", "value"), Some(i32::MIN));
+
+        run(&mut simulator, "scoreboard players set @s value -7").unwrap();
+        run(&mut simulator, "scoreboard players set #b value 2").unwrap();
+        run(
+            &mut simulator,
+            "scoreboard players operation @s value /= #b value",
+        )
+        .unwrap();
+        assert_eq!(score(&simulator, "Steve", "value"), Some(-4));
+
+        run(
+            &mut simulator,
+            "scoreboard players operation @s value >< #b value",
+        )
+        .unwrap();
+        assert_eq!(score(&simulator, "Steve", "value"), Some(2));
+        assert_eq!(score(&simulator, "#b", "value"), Some(-4));
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "scoreboard players operation @s value %= #c value"
+            ),
+            Err(SimulationError::DivideByZero)
+        );
+        assert_eq!(score(&simulator, "#c", "value"), Some(0));
+
+        assert_eq!(
+            run(&mut simulator, "scoreboard players get #b value"),
+            Ok(Some(-4))
+        );
+        assert_eq!(
+            run(&mut simulator, "scoreboard players get #d value"),
+            Err(SimulationError::NoScore(
+                "#d".to_string(),
+                "value".to_string()
+            ))
+        );
+
+        run(&mut simulator, "scoreboard players reset * value").unwrap();
+        assert!(simulator.world.scoreboard.holders().is_empty());
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut simulator = Simulator::new(World::new());
+
+        run(&mut simulator, "scoreboard objectives add value dummy").unwrap();
+        run(&mut simulator, "scoreboard players set Alex value 5").unwrap();
+
+        assert_eq!(
+            run(&mut simulator, "execute if score Alex value matches 1..5"),
+            Ok(Some(1))
+        );
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute unless score Alex value matches 1..5"
+            ),
+            Err(SimulationError::ConditionFailed)
+        );
+        assert_eq!(
+            run(&mut simulator, "execute if score @s value = Alex value"),
+            Err(SimulationError::ConditionFailed)
+        );
+
+        run(
+            &mut simulator,
+            "execute as Alex store result score Steve value run scoreboard players add @s value 3",
+        )
+        .unwrap();
+        assert_eq!(score(&simulator, "Alex", "value"), Some(8));
+        assert_eq!(score(&simulator, "Steve", "value"), Some(8));
+
+        run(
+            &mut simulator,
+            "execute store success score #ok value if score @s value > Alex value",
+        )
+        .unwrap_err();
+        assert_eq!(score(&simulator, "#ok", "value"), Some(0));
+
+        assert!(matches!(
+            run(&mut simulator, "execute as @e[predicate=test:p] run say hi"),
+            Err(SimulationError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_entities() {
+        let mut world = World::new();
+        let mut zombie = Entity::new("0-0-0-0-1", "zombie".parse().unwrap());
+        zombie.position = [5.0, 0.0, 0.0];
+        world.entities = vec![Entity::player("0-0-0-0-0", "Steve"), zombie];
+
+        let mut simulator = Simulator::new(world);
+
+        run(&mut simulator, "scoreboard objectives add value dummy").unwrap();
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute as @e[type=zombie] run scoreboard players set @s value 3"
+            ),
+            Ok(Some(3))
+        );
+        assert_eq!(score(&simulator, "0-0-0-0-1", "value"), Some(3));
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute at @e[type=zombie] as @p run scoreboard players add @s value 1"
+            ),
+            Ok(Some(1))
+        );
+        assert_eq!(score(&simulator, "Steve", "value"), Some(1));
+        assert_eq!(
+            run(
+                &mut simulator,
+                "scoreboard players add @e[distance=1..] value 1"
+            ),
+            Ok(Some(4))
+        );
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute rotated -90 0 positioned ^ ^ ^5 as @e[distance=..0.5] run \
+                 scoreboard players get @s value"
+            ),
+            Ok(Some(4))
+        );
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute facing entity @e[type=zombie] feet positioned ^ ^ ^2.5 align x \
+                 positioned ~2.5 ~ ~ as @e[distance=..0.5] run scoreboard players get @s value"
+            ),
+            Ok(Some(4))
+        );
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute anchored eyes rotated -90 0 positioned ^ ^ ^5 \
+                 as @e[type=zombie,distance=1.6..1.7] run scoreboard players get @s value"
+            ),
+            Ok(Some(4))
+        );
+        // Whole absolute x and z are centered on the block, 0.71 blocks from the zombie.
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute positioned 5 0 0 as @e[type=zombie,distance=0.7..0.71] run \
+                 scoreboard players get @s value"
+            ),
+            Ok(Some(4))
+        );
+        assert!(matches!(
+            run(
+                &mut simulator,
+                "execute positioned over world_surface run say hi"
+            ),
+            Err(SimulationError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_data() {
+        let mut simulator = Simulator::new(World::new());
+        let storage = |simulator: &Simulator| {
+            SNBT::Compound(simulator.world.storage.get(&"test:data".parse().unwrap()))
+        };
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "data merge storage test:data {a:1,list:[1,2]}"
+            ),
+            Ok(Some(1))
+        );
+        assert_eq!(
+            run(&mut simulator, "data merge storage test:data {a:1}"),
+            Err(SimulationError::NothingChanged)
+        );
+        assert_eq!(
+            run(&mut simulator, "data get storage test:data list"),
+            Ok(Some(2))
+        );
+        assert_eq!(
+            run(&mut simulator, "data get storage test:data a 2.5"),
+            Ok(Some(2))
+        );
+        assert_eq!(
+            run(&mut simulator, "data get storage test:data list[]"),
+            Err(SimulationError::MultipleValues)
+        );
+
+        run(
+            &mut simulator,
+            "data modify storage test:data name set value \"hello world\"",
+        )
+        .unwrap();
+        run(
+            &mut simulator,
+            "data modify storage test:data first set string storage test:data name 0 5",
+        )
+        .unwrap();
+        run(
+            &mut simulator,
+            "data modify storage test:data list append from storage test:data a",
+        )
+        .unwrap();
+        assert_eq!(
+            run(&mut simulator, "data remove storage test:data name"),
+            Ok(Some(1))
+        );
+        assert_eq!(
+            storage(&simulator),
+            "{a:1,first:\"hello\",list:[1,2,1]}".parse().unwrap()
+        );
+
+        run(&mut simulator, "scoreboard objectives add value dummy").unwrap();
+        run(&mut simulator, "scoreboard players set @s value 7").unwrap();
+        run(
+            &mut simulator,
+            "execute store result storage test:data score double 0.5 run scoreboard players get @s value",
+        )
+        .unwrap();
+        assert_eq!(
+            run(&mut simulator, "data get storage test:data score 10"),
+            Ok(Some(35))
+        );
+        assert_eq!(
+            run(&mut simulator, "execute if data storage test:data list[{}]"),
+            Err(SimulationError::ConditionFailed)
+        );
+
+        run(
+            &mut simulator,
+            "data modify storage test:data list set value []",
+        )
+        .unwrap();
+        assert_eq!(
+            run(
+                &mut simulator,
+                "data modify storage test:data list[{a:1}] set value {a:1,b:2}"
+            ),
+            Ok(Some(1))
+        );
+        assert_eq!(
+            run(&mut simulator, "data get storage test:data list[0].b"),
+            Ok(Some(2))
+        );
+        assert!(matches!(
+            run(&mut simulator, "data get entity @s Pos"),
+            Err(SimulationError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_functions() {
+        let datapack = datapack();
+        let mut simulator = Simulator::with_datapack(World::new(), &datapack);
+
+        run(&mut simulator, "scoreboard objectives add value dummy").unwrap();
+        run(&mut simulator, "scoreboard objectives add const dummy").unwrap();
+        run(&mut simulator, "scoreboard players set #-1 const -1").unwrap();
+        run(&mut simulator, "scoreboard players set #limit const 10").unwrap();
+
+        run(&mut simulator, "scoreboard players set @s value -12").unwrap();
+        assert_eq!(run(&mut simulator, "function math:abs"), Ok(Some(12)));
+        assert_eq!(run(&mut simulator, "function math:abs"), Ok(Some(12)));
+
+        run(&mut simulator, "scoreboard players set @s value 0").unwrap();
+        assert_eq!(run(&mut simulator, "function #math:all"), Ok(None));
+        assert_eq!(score(&simulator, "Steve", "value"), Some(10));
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute store result score #r value run function math:scale {factor:3b}"
+            ),
+            Ok(Some(1))
+        );
+        assert_eq!(score(&simulator, "Steve", "value"), Some(3));
+        assert_eq!(score(&simulator, "#r", "value"), Some(1));
+
+        assert_eq!(
+            run(
+                &mut simulator,
+                "execute if function math:abs run scoreboard players get #r value"
+            ),
+            Ok(Some(1))
+        );
+        run(
+            &mut simulator,
+            "data modify storage math:args scale set value {factor:4}",
+        )
+        .unwrap();
+        run(
+            &mut simulator,
+            "function math:scale with storage math:args scale",
+        )
+        .unwrap();
+        assert_eq!(score(&simulator, "Steve", "value"), Some(4));
+        assert!(matches!(
+            run(&mut simulator, "function math:scale with storage math:args"),
+            Err(SimulationError::Macro(_))
+        ));
+
+        assert!(matches!(
+            run(&mut simulator, "function math:scale"),
+            Err(SimulationError::Macro(_))
+        ));
+
+        simulator.max_function_depth = 512;
+        assert_eq!(
+            run(&mut simulator, "function math:loop"),
+            Err(SimulationError::RecursionLimit)
+        );
+
+        simulator.max_function_depth = 16384;
+        run(&mut simulator, "scoreboard players set @s value 0").unwrap();
+        run(&mut simulator, "scoreboard players set #limit const 1000").unwrap();
+        assert_eq!(run(&mut simulator, "function math:count"), Ok(None));
+        assert_eq!(score(&simulator, "Steve", "value"), Some(1000));
+        assert_eq!(
+            run(&mut simulator, "function math:missing"),
+            Err(SimulationError::UnknownFunction(
+                "math:missing".parse().unwrap()
+            ))
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::command::enums::axis::Axis;
+use crate::command::enums::entity_anchor::EntityAnchor;
+use crate::coordinate::{Coordinates, WorldCoordinate};
+use crate::rotation::RelativeRotation;
+use crate::simulator::entity::Entity;
+use crate::simulator::{Context, World};
+use ordered_float::NotNan;
+use std::collections::BTreeSet;
+use std::f64::consts::PI;
+
+impl WorldCoordinate {
+    /// The value of the coordinate, where `~` is relative to `origin`.
+    #[must_use]
+    pub fn resolve(&self, origin: f64) -> f64 {
+        let value = self.value.map_or(0.0, |value| value.into_inner() as f64);
+
+        if self.relative { origin + value } else { value }
+    }
+}
+
+impl Coordinates {
+    /// The position the coordinates point to in `context`. `~` is relative to the position and
+    /// `^` to the anchored position, along the axes of the rotation. The eyes of the executor are
+    /// looked up in `world`.
+    ///
+    /// Like the game does for entity positions, absolute x and z without a decimal point are
+    /// centered on their block. Whole numbers are always written without one, so `5` and `5.0`
+    /// both resolve to `5.5`.
+    #[must_use]
+    pub fn resolve(&self, world: &World, context: &Context) -> [f64; 3] {
+        match self {
+            Coordinates::World(x, y, z) => {
+                let [origin_x, origin_y, origin_z] = context.position;
+                let centered = |coordinate: &WorldCoordinate, origin: f64| {
+                    let value = coordinate.resolve(origin);
+                    let is_integer = coordinate
+                        .value
+                        .is_some_and(|value| value.into_inner().fract() == 0.0);
+
+                    if !coordinate.relative && is_integer {
+                        value + 0.5
+                    } else {
+                        value
+                    }
+                };
+
+                [
+                    centered(x, origin_x),
+                    y.resolve(origin_y),
+                    centered(z, origin_z),
+                ]
+            }
+            Coordinates::Local(left, up, forwards) => {
+                let value = |value: &Option<NotNan<f32>>| {
+                    value.map_or(0.0, |value| value.into_inner() as f64)
+                };
+                let (forward_axis, up_axis, left_axis) = local_axes(context.rotation);
+                let origin = context.anchor_position(world);
+
+                [0, 1, 2].map(|axis| {
+                    origin[axis]
+                        + forward_axis[axis] * value(forwards)
+                        + up_axis[axis] * value(up)
+                        + left_axis[axis] * value(left)
+                })
+            }
+        }
+    }
+
+    /// The block position the coordinates point to. Unlike [`Coordinates::resolve`], absolute
+    /// values aren't centered.
+    #[must_use]
+    pub fn resolve_block(&self, world: &World, context: &Context) -> [i32; 3] {
+        let position = match self {
+            Coordinates::World(x, y, z) => {
+                let [origin_x, origin_y, origin_z] = context.position;
+
+                [
+                    x.resolve(origin_x),
+                    y.resolve(origin_y),
+                    z.resolve(origin_z),
+                ]
+            }
+            Coordinates::Local(..) => self.resolve(world, context),
+        };
+
+        position.map(|value| value.floor() as i32)
+    }
+}
+
+impl RelativeRotation {
+    /// The yaw and pitch in `context`, where `~` is relative to its rotation.
+    #[must_use]
+    pub fn resolve(&self, context: &Context) -> [f32; 2] {
+        let [yaw, pitch] = context.rotation;
+
+        [
+            self.0.resolve(yaw as f64) as f32,
+            self.1.resolve(pitch as f64) as f32,
+        ]
+    }
+}
+
+impl Context {
+    /// The position offset by the eye height of the executor if anchored to the eyes. Executors
+    /// that aren't entities of `world` have no eye height.
+    #[must_use]
+    pub fn anchor_position(&self, world: &World) -> [f64; 3] {
+        let [x, y, z] = self.position;
+
+        match self.anchor {
+            EntityAnchor::Eyes => {
+                let eye_height = self
+                    .executor
+                    .as_deref()
+                    .and_then(|executor| world.entity(executor))
+                    .map_or(0.0, Entity::eye_height);
+
+                [x, y + eye_height, z]
+            }
+            EntityAnchor::Feet => [x, y, z],
+        }
+    }
+
+    /// Rotates towards `target` from the anchored position, like `execute facing`.
+    pub fn face(&mut self, world: &World, target: [f64; 3]) {
+        let [x, y, z] = self.anchor_position(world);
+        let (dx, dy, dz) = (target[0] - x, target[1] - y, target[2] - z);
+        let horizontal = (dx * dx + dz * dz).sqrt();
+
+        let pitch = wrap_degrees((-(dy.atan2(horizontal) * 180.0 / PI)) as f32);
+        let yaw = wrap_degrees((dz.atan2(dx) * 180.0 / PI) as f32 - 90.0);
+
+        self.rotation = [yaw, pitch];
+    }
+
+    /// Floors the position on `axes`, like `execute align`.
+    pub fn align(&mut self, axes: &BTreeSet<Axis>) {
+        for axis in axes {
+            let index = match axis {
+                Axis::X => 0,
+                Axis::Y => 1,
+                Axis::Z => 2,
+            };
+
+            self.position[index] = self.position[index].floor();
+        }
+    }
+}
+
+impl Entity {
+    /// The height of the eyes above the position, which `anchored eyes` uses.
+    #[must_use]
+    pub fn eye_height(&self) -> f64 {
+        if self.is_player() {
+            1.62
+        } else {
+            self.height * 0.85
+        }
+    }
+
+    /// The position of the feet or eyes.
+    #[must_use]
+    pub fn anchor_position(&self, anchor: EntityAnchor) -> [f64; 3] {
+        let [x, y, z] = self.position;
+
+        match anchor {
+            EntityAnchor::Eyes => [x, y + self.eye_height(), z],
+            EntityAnchor::Feet => [x, y, z],
+        }
+    }
+}
+
+/// The forward, up and left axes of a rotation, computed in `f32` like the game.
+fn local_axes([yaw, pitch]: [f32; 2]) -> ([f64; 3], [f64; 3], [f64; 3]) {
+    let f = mth_cos((yaw + 90.0).to_radians());
+    let g = mth_sin((yaw + 90.0).to_radians());
+    let h = mth_cos((-pitch).to_radians());
+    let i = mth_sin((-pitch).to_radians());
+    let j = mth_cos((-pitch + 90.0).to_radians());
+    let k = mth_sin((-pitch + 90.0).to_radians());
+
+    let forward = [(f * h) as f64, i as f64, (g * h) as f64];
+    let up = [(f * j) as f64, k as f64, (g * j) as f64];
+    let left = [
+        -(forward[1] * up[2] - forward[2] * up[1]),
+        -(forward[2] * up[0] - forward[0] * up[2]),
+        -(forward[0] * up[1] - forward[1] * up[0]),
+    ];
+
+    (forward, up, left)
+}
+
+/// `Mth.sin`, which looks the value up in a table of 65536 steps.
+fn mth_sin(radians: f32) -> f32 {
+    sin_table((radians * 10430.378) as i32)
+}
+
+fn mth_cos(radians: f32) -> f32 {
+    sin_table((radians * 10430.378 + 16384.0) as i32)
+}
+
+fn sin_table(index: i32) -> f32 {
+    ((index & 0xFFFF) as f64 * PI * 2.0 / 65536.0).sin() as f32
+}
+
+/// `Mth.wrapDegrees`, which wraps an angle to `[-180, 180)`.
+pub(crate) fn wrap_degrees(degrees: f32) -> f32 {
+    let degrees = degrees % 360.0;
+
+    if degrees >= 180.0 {
+        degrees - 360.0
+    } else if degrees < -180.0 {
+        degrees + 360.0
+    } else {
+        degrees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: [f64; 3], expected: [f64; 3]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-4),
+            "{:?} is not {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn resolve(coordinates: &str, world: &World, context: &Context) -> [f64; 3] {
+        coordinates
+            .parse::<Coordinates>()
+            .unwrap()
+            .resolve(world, context)
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut world = World::new();
+        let mut context = Context::new();
+        context.position = [10.5, 64.0, -3.25];
+
+        assert_near(resolve("~ ~1 5", &world, &context), [10.5, 65.0, 5.5]);
+        assert_near(resolve("5 64 -3", &world, &context), [5.5, 64.0, -2.5]);
+        assert_near(resolve("5.25 64 ~", &world, &context), [5.25, 64.0, -3.25]);
+        assert_eq!(
+            "5 64 -3"
+                .parse::<Coordinates>()
+                .unwrap()
+                .resolve_block(&world, &context),
+            [5, 64, -3]
+        );
+        assert_near(resolve("^ ^ ^5", &world, &context), [10.5, 64.0, 1.75]);
+        assert_near(resolve("^1 ^ ^", &world, &context), [11.5, 64.0, -3.25]);
+        assert_eq!(
+            "~ ~-0.5 ~"
+                .parse::<Coordinates>()
+                .unwrap()
+                .resolve_block(&world, &context),
+            [10, 63, -4]
+        );
+
+        context.rotation = [90.0, 0.0];
+        assert_near(resolve("^ ^ ^5", &world, &context), [5.5, 64.0, -3.25]);
+
+        context.rotation = [0.0, -90.0];
+        assert_near(resolve("^ ^ ^2", &world, &context), [10.5, 66.0, -3.25]);
+
+        context.rotation = [0.0, 0.0];
+        context.anchor = EntityAnchor::Eyes;
+        assert_near(resolve("^ ^ ^1", &world, &context), [10.5, 64.0, -2.25]);
+
+        world.entities.push(Entity::player("uuid", "Steve"));
+        context.executor = Some("Steve".to_string());
+        assert_near(resolve("^ ^ ^1", &world, &context), [10.5, 65.62, -2.25]);
+        assert_near(resolve("~ ~ ~", &world, &context), [10.5, 64.0, -3.25]);
+
+        assert_eq!(
+            "~90 ~-10"
+                .parse::<RelativeRotation>()
+                .unwrap()
+                .resolve(&context),
+            [90.0, -10.0]
+        );
+    }
+
+    #[test]
+    fn test_face() {
+        let world = World::new();
+        let mut context = Context::new();
+
+        context.face(&world, [5.0, 0.0, 0.0]);
+        assert_eq!(context.rotation, [-90.0, 0.0]);
+
+        context.face(&world, [0.0, 5.0, 0.0]);
+        assert_eq!(context.rotation[1], -90.0);
+
+        context.position = [0.7, -0.2, 3.9];
+        context.align(&[Axis::X, Axis::Y].into());
+        assert_eq!(context.position, [0.0, -1.0, 3.9]);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::command::enums::score_operation_operator::ScoreOperationOperator;
+use crate::simulator::SimulationError;
+use crate::snbt::SNBT;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Criteria whose scores are managed by the game and can't be changed by commands.
+const READ_ONLY_CRITERIA: [&str; 6] = ["health", "food", "air", "armor", "xp", "level"];
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Objective {
+    pub criterion: String,
+    pub display_name: Option<SNBT>,
+    pub scores: BTreeMap<String, i32>,
+}
+
+impl Objective {
+    #[inline]
+    #[must_use]
+    pub fn new<T: ToString>(criterion: T) -> Self {
+        Self {
+            criterion: criterion.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        READ_ONLY_CRITERIA.contains(&self.criterion.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Scoreboard {
+    pub objectives: BTreeMap<String, Objective>,
+    /// Objective names by display slot.
+    pub display_slots: BTreeMap<String, String>,
+}
+
+impl Scoreboard {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_objective<N: ToString, C: ToString>(
+        &mut self,
+        name: N,
+        criterion: C,
+    ) -> Result<&mut Objective, SimulationError> {
+        let name = name.to_string();
+
+        if self.objectives.contains_key(&name) {
+            return Err(SimulationError::DuplicateObjective(name));
+        }
+
+        Ok(self
+            .objectives
+            .entry(name)
+            .or_insert(Objective::new(criterion)))
+    }
+
+    pub fn objective(&self, name: &str) -> Result<&Objective, SimulationError> {
+        self.objectives
+            .get(name)
+            .ok_or_else(|| SimulationError::UnknownObjective(name.to_string()))
+    }
+
+    pub fn objective_mut(&mut self, name: &str) -> Result<&mut Objective, SimulationError> {
+        self.objectives
+            .get_mut(name)
+            .ok_or_else(|| SimulationError::UnknownObjective(name.to_string()))
+    }
+
+    /// Like [`Scoreboard::objective_mut`], but fails for read-only objectives.
+    pub fn writable_objective(&mut self, name: &str) -> Result<&mut Objective, SimulationError> {
+        let objective = self.objective_mut(name)?;
+
+        if objective.is_read_only() {
+            return Err(SimulationError::ReadOnlyObjective(name.to_string()));
+        }
+
+        Ok(objective)
+    }
+
+    #[must_use]
+    pub fn score(&self, holder: &str, objective: &str) -> Option<i32> {
+        self.objectives
+            .get(objective)
+            .and_then(|objective| objective.scores.get(holder))
+            .copied()
+    }
+
+    pub fn set_score(
+        &mut self,
+        holder: &str,
+        objective: &str,
+        value: i32,
+    ) -> Result<(), SimulationError> {
+        self.writable_objective(objective)?
+            .scores
+            .insert(holder.to_string(), value);
+
+        Ok(())
+    }
+
+    /// Returns every score holder with a score in any objective, which is what `*` selects.
+    #[must_use]
+    pub fn holders(&self) -> BTreeSet<String> {
+        self.objectives
+            .values()
+            .flat_map(|objective| objective.scores.keys().cloned())
+            .collect()
+    }
+}
+
+/// Applies `operator` to the target and source scores and returns their new values, with Java's
+/// wrapping integer arithmetic and flooring division.
+pub fn apply_operation(
+    operator: ScoreOperationOperator,
+    target: i32,
+    source: i32,
+) -> Result<(i32, i32), SimulationError> {
+    let target = match operator {
+        ScoreOperationOperator::Set => source,
+        ScoreOperationOperator::Add => target.wrapping_add(source),
+        ScoreOperationOperator::Subtract => target.wrapping_sub(source),
+        ScoreOperationOperator::Multiply => target.wrapping_mul(source),
+        ScoreOperationOperator::Divide => floor_div(target, source)?,
+        ScoreOperationOperator::Modulo => floor_mod(target, source)?,
+        ScoreOperationOperator::Swap => return Ok((source, target)),
+        ScoreOperationOperator::ChooseMinimum => target.min(source),
+        ScoreOperationOperator::ChooseMaximum => target.max(source),
+    };
+
+    Ok((target, source))
+}
+
+/// `Math.floorDiv`, where `i32::MIN / -1` wraps back to `i32::MIN`.
+fn floor_div(a: i32, b: i32) -> Result<i32, SimulationError> {
+    if b == 0 {
+        return Err(SimulationError::DivideByZero);
+    }
+
+    let quotient = a.wrapping_div(b);
+
+    if a.wrapping_rem(b) != 0 && (a < 0) != (b < 0) {
+        Ok(quotient - 1)
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// `Math.floorMod`, whose result has the sign of `b`.
+fn floor_mod(a: i32, b: i32) -> Result<i32, SimulationError> {
+    if b == 0 {
+        return Err(SimulationError::DivideByZero);
+    }
+
+    let remainder = a.wrapping_rem(b);
+
+    if remainder != 0 && (remainder < 0) != (b < 0) {
+        Ok(remainder + b)
+    } else {
+        Ok(remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(operator: ScoreOperationOperator, target: i32, source: i32) -> i32 {
+        apply_operation(operator, target, source).unwrap().0
+    }
+
+    #[test]
+    fn test_operations() {
+        use ScoreOperationOperator::*;
+
+        assert_eq!(apply(Add, i32::MAX, 1), i32::MIN);
+        assert_eq!(apply(Subtract, i32::MIN, 1), i32::MAX);
+        assert_eq!(apply(Multiply, 65536, 65536), 0);
+        assert_eq!(apply(Divide, 7, 2), 3);
+        assert_eq!(apply(Divide, -7, 2), -4);
+        assert_eq!(apply(Divide, 7, -2), -4);
+        assert_eq!(apply(Divide, i32::MIN, -1), i32::MIN);
+        assert_eq!(apply(Modulo, -7, 3), 2);
+        assert_eq!(apply(Modulo, 7, -3), -2);
+        assert_eq!(apply(Modulo, i32::MIN, -1), 0);
+        assert_eq!(apply(ChooseMinimum, 3, -2), -2);
+        assert_eq!(apply(ChooseMaximum, 3, -2), 3);
+        assert_eq!(apply_operation(Swap, 1, 2).unwrap(), (2, 1));
+        assert_eq!(
+            apply_operation(Divide, 1, 0),
+            Err(SimulationError::DivideByZero)
+        );
+        assert_eq!(
+            apply_operation(Modulo, 1, 0),
+            Err(SimulationError::DivideByZero)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::nbt_path::SNBTCompound;
+use crate::resource_location::ResourceLocation;
+use crate::simulator::SimulationError;
+use crate::snbt::{SNBT, SNBTString};
+use std::collections::BTreeMap;
+use std::fmt::{Display, LowerExp};
+
+/// Command storage, keyed by IDs with an explicit namespace.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Storage {
+    pub data: BTreeMap<ResourceLocation, SNBTCompound>,
+}
+
+fn storage_key(id: &ResourceLocation) -> ResourceLocation {
+    ResourceLocation::new_namespace_paths(id.namespace(), id.paths.clone())
+}
+
+impl Storage {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the data of a storage, which is empty if nothing was stored yet.
+    #[must_use]
+    pub fn get(&self, id: &ResourceLocation) -> SNBTCompound {
+        self.data.get(&storage_key(id)).cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, id: &ResourceLocation, data: SNBTCompound) {
+        self.data.insert(storage_key(id), data);
+    }
+}
+
+/// The value of an NBT value as a number, used by `data get` and `execute store`.
+pub(crate) fn as_number(value: &SNBT) -> Option<f64> {
+    Some(match value {
+        SNBT::Byte(v) => *v as f64,
+        SNBT::Short(v) => *v as f64,
+        SNBT::Integer(v) => *v as f64,
+        SNBT::Long(v) => *v as f64,
+        SNBT::Float(v) => v.into_inner() as f64,
+        SNBT::Double(v) => v.into_inner(),
+        _ => return None,
+    })
+}
+
+/// Formats a floating-point number like Java's `Float.toString` or `Double.toString`.
+fn java_float_string<T: Copy + Display + LowerExp + Into<f64>>(value: T) -> String {
+    let magnitude = value.into().abs();
+
+    if magnitude == 0.0 || (1e-3..1e7).contains(&magnitude) {
+        let display = value.to_string();
+
+        return if display.contains('.') {
+            display
+        } else {
+            format!("{}.0", display)
+        };
+    }
+
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+
+    if mantissa.contains('.') {
+        format!("{}E{}", mantissa, exponent)
+    } else {
+        format!("{}.0E{}", mantissa, exponent)
+    }
+}
+
+/// The text `data modify … string` reads from a value. Only strings and numbers have one; numbers
+/// are written as SNBT, with their type suffix.
+pub(crate) fn as_text(value: &SNBT) -> Result<String, SimulationError> {
+    Ok(match value {
+        SNBT::String(SNBTString(false, text)) => text.clone(),
+        SNBT::Byte(v) => format!("{}b", v),
+        SNBT::Short(v) => format!("{}s", v),
+        SNBT::Integer(v) => v.to_string(),
+        SNBT::Long(v) => format!("{}L", v),
+        SNBT::Float(v) => format!("{}f", java_float_string(v.into_inner())),
+        SNBT::Double(v) => format!("{}d", java_float_string(v.into_inner())),
+        value => return Err(SimulationError::ExpectedValue(value.clone())),
+    })
+}
+
+/// Slices `text` in UTF-16 code units like `data modify … string`, where negative indices count
+/// from the end.
+pub(crate) fn substring(
+    text: &str,
+    start: Option<i32>,
+    end: Option<i32>,
+) -> Result<String, SimulationError> {
+    let units = text.encode_utf16().collect::<Vec<_>>();
+    let length = units.len() as i64;
+    let offset = |index: i32| {
+        if index >= 0 {
+            index as i64
+        } else {
+            length + index as i64
+        }
+    };
+
+    let start = start.map_or(0, offset);
+    let end = end.map_or(length, offset);
+
+    if start < 0 || end > length || start > end {
+        return Err(SimulationError::InvalidSubstring(start, end));
+    }
+
+    Ok(String::from_utf16_lossy(
+        &units[start as usize..end as usize],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::NotNan;
+
+    #[test]
+    fn test_as_text() {
+        assert_eq!(as_text(&SNBT::Byte(1)).unwrap(), "1b");
+        assert_eq!(as_text(&SNBT::Short(-2)).unwrap(), "-2s");
+        assert_eq!(as_text(&SNBT::Integer(3)).unwrap(), "3");
+        assert_eq!(as_text(&SNBT::Long(4)).unwrap(), "4L");
+        assert_eq!(
+            as_text(&SNBT::Double(NotNan::new(2.0).unwrap())).unwrap(),
+            "2.0d"
+        );
+        assert_eq!(
+            as_text(&SNBT::Double(NotNan::new(12.5).unwrap())).unwrap(),
+            "12.5d"
+        );
+        assert_eq!(
+            as_text(&SNBT::Float(NotNan::new(0.1).unwrap())).unwrap(),
+            "0.1f"
+        );
+        assert_eq!(
+            as_text(&SNBT::Float(NotNan::new(20.0).unwrap())).unwrap(),
+            "20.0f"
+        );
+        assert_eq!(
+            as_text(&SNBT::Float(NotNan::new(1.1e10).unwrap())).unwrap(),
+            "1.1E10f"
+        );
+        assert_eq!(
+            as_text(&SNBT::Double(NotNan::new(1e10).unwrap())).unwrap(),
+            "1.0E10d"
+        );
+        assert!(as_text(&SNBT::List(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_substring() {
+        assert_eq!(substring("hello", Some(1), None).unwrap(), "ello");
+        assert_eq!(substring("hello", Some(-3), Some(-1)).unwrap(), "ll");
+        assert_eq!(substring("héllo", Some(0), Some(2)).unwrap(), "hé");
+        assert_eq!(
+            substring("hello", Some(4), Some(2)),
+            Err(SimulationError::InvalidSubstring(4, 2))
+        );
+        assert_eq!(
+            substring("hello", Some(-6), None),
+            Err(SimulationError::InvalidSubstring(-1, 5))
+        );
+    }
+}
@@ -1,15 +1,36 @@
 use crate::has_macro::HasMacro;
+use crate::macros::macro_variables;
 use crate::nbt_path::SNBTCompound;
+use crate::parser::{
+    Parse, ParseError, ParseErrorKind, StringReader, is_allowed_in_unquoted_string,
+};
+use minecraft_command_types_derive::{Fold, Visit, VisitMut};
 use ordered_float::NotNan;
 use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{Serialize, Serializer, de};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 use std::fmt::Formatter;
-
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+use std::str::FromStr;
+
+/// A string or compound key. The flag is set if the text contains `$(name)` macro slots, like
+/// the `$(key)` key of `{$(key):1}`, and the text always holds the full `$(name)` slot.
+///
+/// Before, a flagged key held only the name, so `SNBTString(true, "key")` meant `$(key)`. It
+/// is now the plain text `key`; use [`SNBTString::new`] to set the flag from the text.
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Visit, VisitMut, Fold)]
 pub struct SNBTString(pub bool, pub String);
 
+impl SNBTString {
+    /// A string that is flagged if `text` contains macro slots.
+    #[must_use]
+    pub fn new<T: ToString>(text: T) -> Self {
+        let text = text.to_string();
+
+        SNBTString(!macro_variables(&text).is_empty(), text)
+    }
+}
+
 impl HasMacro for SNBTString {
     fn has_macro(&self) -> bool {
         self.0
@@ -18,6 +39,12 @@ impl HasMacro for SNBTString {
     fn has_macro_conflict(&self) -> bool {
         false
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        if self.0 {
+            variables.extend(macro_variables(&self.1));
+        }
+    }
 }
 
 impl Serialize for SNBTString {
@@ -25,17 +52,12 @@ impl Serialize for SNBTString {
     where
         S: Serializer,
     {
-        match self {
-            SNBTString(false, value) => serializer.serialize_str(value),
-            SNBTString(true, name) => {
-                let formatted = format!("$({})", name);
-                serializer.serialize_str(&formatted)
-            }
-        }
+        serializer.serialize_str(&self.1)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Visit, VisitMut, Fold)]
+#[visit(hook = snbt)]
 pub enum SNBT {
     Byte(i8),
     Short(i16),
@@ -100,22 +122,57 @@ impl HasMacro for SNBT {
             _ => false,
         }
     }
+
+    fn collect_macro_variables(&self, variables: &mut BTreeSet<String>) {
+        match self {
+            SNBT::Macro(name) => {
+                variables.insert(name.clone());
+            }
+            SNBT::String(string) => string.collect_macro_variables(variables),
+            SNBT::List(values) => values.collect_macro_variables(variables),
+            SNBT::Compound(compound) => {
+                for (key, value) in compound {
+                    key.collect_macro_variables(variables);
+                    value.collect_macro_variables(variables);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 pub fn fmt_snbt_compound(f: &mut Formatter<'_>, compound: &SNBTCompound) -> std::fmt::Result {
     f.write_str("{")?;
 
-    for (i, (SNBTString(_, k), v)) in compound.iter().enumerate() {
+    for (i, (key, v)) in compound.iter().enumerate() {
         if i > 0 {
             f.write_str(", ")?;
         }
 
-        write!(f, "\"{}\":{}", escape(k), v)?;
+        match key {
+            SNBTString(true, k) if is_macro_slot(k) => write!(f, "{}:{}", k, v)?,
+            SNBTString(_, k) if is_unquoted_key(k) => write!(f, "{}:{}", k, v)?,
+            SNBTString(_, k) => write!(f, "\"{}\":{}", escape(k), v)?,
+        }
     }
 
     f.write_str("}")
 }
 
+/// Whether `text` is a single `$(name)` slot.
+#[must_use]
+fn is_macro_slot(text: &str) -> bool {
+    text.strip_prefix("$(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .is_some_and(|name| !name.is_empty() && name.chars().all(is_allowed_in_macro_name))
+}
+
+#[inline]
+#[must_use]
+fn is_unquoted_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(is_allowed_in_unquoted_string)
+}
+
 #[inline]
 #[must_use]
 fn escape(input: &str) -> String {
@@ -192,6 +249,321 @@ impl Display for SNBT {
     }
 }
 
+pub(crate) fn is_allowed_in_macro_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Parses a `$(name)` macro slot, returning the name.
+pub(crate) fn parse_macro(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    reader.expect('$')?;
+    reader.expect('(')?;
+
+    let name = reader.read_while(is_allowed_in_macro_name);
+
+    if name.is_empty() {
+        return Err(reader.expected("macro name"));
+    }
+
+    reader.expect(')')?;
+
+    Ok(name.to_string())
+}
+
+/// Reads the character of an escape sequence after its backslash.
+fn read_escape(reader: &mut StringReader<'_>) -> Result<char, ParseError> {
+    let start = reader.cursor();
+    let invalid = |reader: &StringReader<'_>| {
+        ParseError::new(
+            start,
+            ParseErrorKind::InvalidValue(
+                "escape sequence",
+                reader.input()[start..reader.cursor()].to_string(),
+            ),
+        )
+    };
+    let read_hex = |reader: &mut StringReader<'_>, digits: usize| {
+        let hex_start = reader.cursor();
+
+        for _ in 0..digits {
+            if !reader.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid(reader));
+            }
+
+            reader.skip();
+        }
+
+        u32::from_str_radix(&reader.input()[hex_start..reader.cursor()], 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| invalid(reader))
+    };
+
+    Ok(match reader.read() {
+        Some(c @ ('"' | '\\' | '\'')) => c,
+        Some('b') => '\u{8}',
+        Some('s') => ' ',
+        Some('t') => '\t',
+        Some('n') => '\n',
+        Some('f') => '\u{c}',
+        Some('r') => '\r',
+        Some('x') => read_hex(reader, 2)?,
+        Some('u') => read_hex(reader, 4)?,
+        Some('U') => read_hex(reader, 8)?,
+        Some('N') => {
+            reader.expect('{')?;
+            let name = reader.read_while(|c| c != '}');
+            reader.expect('}')?;
+
+            unicode_names2::character(name).ok_or_else(|| invalid(reader))?
+        }
+        Some(c) => return Err(ParseError::new(start, ParseErrorKind::InvalidEscape(c))),
+        None => return Err(reader.error(ParseErrorKind::UnclosedQuotedString)),
+    })
+}
+
+/// Reads a quoted string, which supports more escape sequences in SNBT than in other arguments,
+/// like `\n` and `\u00e9`.
+fn read_quoted_snbt_string(reader: &mut StringReader<'_>) -> Result<String, ParseError> {
+    let quote = match reader.peek() {
+        Some(c @ ('"' | '\'')) => c,
+        _ => return Err(reader.expected("quoted string")),
+    };
+    reader.skip();
+
+    let mut result = String::new();
+
+    loop {
+        match reader.read() {
+            Some('\\') => result.push(read_escape(reader)?),
+            Some(c) if c == quote => return Ok(result),
+            Some(c) => result.push(c),
+            None => return Err(reader.error(ParseErrorKind::UnclosedQuotedString)),
+        }
+    }
+}
+
+fn parse_typed_array<T>(
+    reader: &mut StringReader<'_>,
+    parse_element: impl Fn(&SNBT) -> Option<T>,
+) -> Result<Vec<T>, ParseError> {
+    let mut values = Vec::new();
+
+    reader.skip_whitespace();
+
+    if reader.eat(']') {
+        return Ok(values);
+    }
+
+    loop {
+        reader.skip_whitespace();
+
+        let start = reader.cursor();
+        let element = SNBT::parse(reader)?;
+        let Some(element) = parse_element(&element) else {
+            return Err(ParseError::new(
+                start,
+                ParseErrorKind::InvalidValue("array element", element.to_string()),
+            ));
+        };
+        values.push(element);
+
+        reader.skip_whitespace();
+
+        if reader.eat(']') {
+            return Ok(values);
+        }
+
+        reader.expect(',')?;
+    }
+}
+
+fn parse_list(reader: &mut StringReader<'_>) -> Result<Vec<SNBT>, ParseError> {
+    let mut values = Vec::new();
+
+    reader.skip_whitespace();
+
+    if reader.eat(']') {
+        return Ok(values);
+    }
+
+    loop {
+        reader.skip_whitespace();
+        values.push(SNBT::parse(reader)?);
+        reader.skip_whitespace();
+
+        if reader.eat(']') {
+            return Ok(values);
+        }
+
+        reader.expect(',')?;
+    }
+}
+
+pub fn parse_snbt_compound(reader: &mut StringReader<'_>) -> Result<SNBTCompound, ParseError> {
+    let mut compound = SNBTCompound::new();
+
+    reader.expect('{')?;
+    reader.skip_whitespace();
+
+    if reader.eat('}') {
+        return Ok(compound);
+    }
+
+    loop {
+        reader.skip_whitespace();
+
+        let key = if reader.peek_str("$(") {
+            SNBTString(true, format!("$({})", parse_macro(reader)?))
+        } else {
+            let key = match reader.peek() {
+                Some('"' | '\'') => read_quoted_snbt_string(reader)?,
+                _ => reader.read_unquoted_string().to_string(),
+            };
+
+            if key.is_empty() {
+                return Err(reader.expected("key"));
+            }
+
+            SNBTString::new(key)
+        };
+
+        reader.skip_whitespace();
+        reader.expect(':')?;
+        reader.skip_whitespace();
+
+        let value = SNBT::parse(reader)?;
+        compound.insert(key, value);
+
+        reader.skip_whitespace();
+
+        if reader.eat('}') {
+            return Ok(compound);
+        }
+
+        reader.expect(',')?;
+    }
+}
+
+fn parse_unquoted_value(value: &str) -> SNBT {
+    match value {
+        "true" => return SNBT::Byte(1),
+        "false" => return SNBT::Byte(0),
+        _ => {}
+    }
+
+    let is_integer = |body: &str| {
+        let digits = body.strip_prefix(['-', '+']).unwrap_or(body);
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    };
+    let is_float = |body: &str| {
+        body.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+            && body.parse::<f64>().is_ok()
+    };
+
+    let (body, suffix) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&value[..i], Some(c.to_ascii_lowercase())),
+        _ => (value, None),
+    };
+
+    let number = match suffix {
+        Some('b') if is_integer(body) => body.parse().ok().map(SNBT::Byte),
+        Some('s') if is_integer(body) => body.parse().ok().map(SNBT::Short),
+        Some('l') if is_integer(body) => body.parse().ok().map(SNBT::Long),
+        Some('f') if is_float(body) => body
+            .parse()
+            .ok()
+            .filter(|v: &f32| v.is_finite())
+            .and_then(|v| NotNan::new(v).ok())
+            .map(SNBT::Float),
+        Some('d') if is_float(body) => body
+            .parse()
+            .ok()
+            .filter(|v: &f64| v.is_finite())
+            .and_then(|v| NotNan::new(v).ok())
+            .map(SNBT::Double),
+        None if is_integer(body) => body.parse().ok().map(SNBT::Integer),
+        None if is_float(body) && body.contains('.') => body
+            .parse()
+            .ok()
+            .filter(|v: &f64| v.is_finite())
+            .and_then(|v| NotNan::new(v).ok())
+            .map(SNBT::Double),
+        _ => None,
+    };
+
+    number.unwrap_or_else(|| SNBT::string(value))
+}
+
+impl Parse for SNBT {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        match reader.peek() {
+            Some('{') => parse_snbt_compound(reader).map(SNBT::Compound),
+            Some('[') => {
+                reader.skip();
+
+                let array_type = reader.peek();
+                if matches!(array_type, Some('B' | 'I' | 'L')) && reader.peek_nth(1) == Some(';') {
+                    reader.skip();
+                    reader.skip();
+
+                    return match array_type {
+                        Some('B') => parse_typed_array(reader, |v| match v {
+                            SNBT::Byte(v) => Some(*v),
+                            _ => None,
+                        })
+                        .map(SNBT::ByteArray),
+                        Some('I') => parse_typed_array(reader, |v| match v {
+                            SNBT::Integer(v) => Some(*v),
+                            _ => None,
+                        })
+                        .map(SNBT::IntegerArray),
+                        _ => parse_typed_array(reader, |v| match v {
+                            SNBT::Long(v) => Some(*v),
+                            _ => None,
+                        })
+                        .map(SNBT::LongArray),
+                    };
+                }
+
+                parse_list(reader).map(SNBT::List)
+            }
+            Some('"' | '\'') => {
+                read_quoted_snbt_string(reader).map(|text| SNBT::String(SNBTString::new(text)))
+            }
+            Some('$') => parse_macro(reader).map(SNBT::Macro),
+            _ => {
+                let value = reader.read_unquoted_string();
+
+                if value.is_empty() {
+                    return Err(reader.expected("value"));
+                }
+
+                Ok(parse_unquoted_value(value))
+            }
+        }
+    }
+}
+
+impl FromStr for SNBT {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut reader = StringReader::new(s);
+
+        reader.skip_whitespace();
+        let value = SNBT::parse(&mut reader)?;
+        reader.skip_whitespace();
+
+        if reader.can_read() {
+            return Err(reader.error(ParseErrorKind::TrailingData));
+        }
+
+        Ok(value)
+    }
+}
+
 impl Serialize for SNBT {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -355,3 +727,168 @@ impl From<Vec<i64>> for SNBT {
         SNBT::LongArray(v)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> SNBTString {
+        SNBTString(false, name.to_string())
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        let snbt: SNBT = "{a:1b,b:[I;1,2],c:'x',d:$(m)}".parse().unwrap();
+
+        assert_eq!(snbt.get(&key("a")), Some(&SNBT::Byte(1)));
+        assert_eq!(snbt.get(&key("b")), Some(&SNBT::IntegerArray(vec![1, 2])));
+        assert_eq!(snbt.get(&key("c")), Some(&SNBT::string("x")));
+        assert_eq!(snbt.get(&key("d")), Some(&SNBT::Macro("m".to_string())));
+        assert!(snbt.has_macro());
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        let parse = |input: &str| input.parse::<SNBT>().unwrap();
+
+        assert_eq!(parse("true"), SNBT::Byte(1));
+        assert_eq!(parse("-3s"), SNBT::Short(-3));
+        assert_eq!(parse("7"), SNBT::Integer(7));
+        assert_eq!(parse("9L"), SNBT::Long(9));
+        assert_eq!(parse("1.5f"), SNBT::Float(NotNan::new(1.5).unwrap()));
+        assert_eq!(parse("2.5"), SNBT::Double(NotNan::new(2.5).unwrap()));
+        assert_eq!(parse("1d"), SNBT::Double(NotNan::new(1.0).unwrap()));
+        assert_eq!(parse("stone"), SNBT::string("stone"));
+        assert_eq!(parse("[L; 1l, 2l]"), SNBT::LongArray(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_parse_non_finite() {
+        for input in ["1.0e400", "1e400d", "-1.0e400", "1e39f"] {
+            let snbt: SNBT = input.parse().unwrap();
+
+            assert_eq!(snbt, SNBT::string(input));
+            assert_eq!(snbt.to_string().parse::<SNBT>().unwrap(), snbt);
+        }
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        let expected = SNBT::Compound(SNBTCompound::from([(key("a"), SNBT::Integer(1))]));
+
+        assert_eq!(" {a:1}".parse::<SNBT>().unwrap(), expected);
+        assert_eq!("\t{a:1} ".parse::<SNBT>().unwrap(), expected);
+        assert_eq!(
+            " {a:1} x".parse::<SNBT>().unwrap_err().kind,
+            ParseErrorKind::TrailingData
+        );
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        let parse = |input: &str| input.parse::<SNBT>();
+
+        assert_eq!(
+            parse(r#""a\nb\tc\rd\be\ff\sg""#),
+            Ok(SNBT::string("a\nb\tc\rd\u{8}e\u{c}f g"))
+        );
+        assert_eq!(parse(r#"'\'\"\\'"#), Ok(SNBT::string("'\"\\")));
+        assert_eq!(
+            parse(r#""\x41\u00e9\U0001F600""#),
+            Ok(SNBT::string("A\u{e9}\u{1F600}"))
+        );
+        assert_eq!(
+            parse(r#""\N{Snowman}\N{LATIN SMALL LETTER A}""#),
+            Ok(SNBT::string("\u{2603}a"))
+        );
+        assert_eq!(
+            parse(r#"{"\x41":1}"#).unwrap().get(&key("A")),
+            Some(&SNBT::Integer(1))
+        );
+
+        let error = parse(r#""\q""#).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidEscape('q'));
+        assert_eq!(error.cursor, 2);
+
+        let error = parse(r#""\x4g""#).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::InvalidValue("escape sequence", "x4".to_string())
+        );
+        assert_eq!(error.cursor, 2);
+
+        assert!(parse(r#""\N{NOT A CHARACTER}""#).is_err());
+        assert!(parse(r#""\uD800""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_macro_key() {
+        let snbt: SNBT = r#"{$(key): "value", "quoted key": 1}"#.parse().unwrap();
+
+        assert_eq!(
+            snbt.get(&SNBTString(true, "$(key)".to_string())),
+            Some(&SNBT::string("value"))
+        );
+        assert_eq!(snbt.to_string(), r#"{"quoted key":1, $(key):"value"}"#);
+        assert_eq!(snbt.macro_variables(), ["key".to_string()].into());
+
+        let snbt: SNBT = r#"{"a $(b)": "$(c) d"}"#.parse().unwrap();
+        assert_eq!(
+            snbt.get(&SNBTString(true, "a $(b)".to_string())),
+            Some(&SNBT::String(SNBTString(true, "$(c) d".to_string())))
+        );
+        assert_eq!(snbt.to_string(), r#"{"a $(b)":"$(c) d"}"#);
+        assert_eq!(
+            snbt.macro_variables(),
+            ["b".to_string(), "c".to_string()].into()
+        );
+        assert!(!snbt.has_macro_conflict());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "[B;1b,2]".parse::<SNBT>().unwrap_err();
+        assert_eq!(error.cursor, 6);
+
+        let error = "{a:1".parse::<SNBT>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedEndOfInput);
+        assert_eq!(error.cursor, 4);
+
+        let error = "{a:$(m}".parse::<SNBT>().unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::ExpectedCharacter(')'));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = r#"{Items:[{Count:1b, id:"minecraft:stone"}], Pos:[0.5d, 1.5d, -2.25d]}"#;
+
+        assert_eq!(input.parse::<SNBT>().unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn test_hand_set_flag() {
+        let variables = |string: &SNBTString| {
+            let mut variables = BTreeSet::new();
+            string.collect_macro_variables(&mut variables);
+            variables.into_iter().collect::<Vec<_>>()
+        };
+        let compound = |key: SNBTString| SNBT::Compound(BTreeMap::from([(key, SNBT::Byte(1))]));
+
+        let slot = SNBTString(true, "$(x)".to_string());
+        assert_eq!(SNBTString::new("$(x)"), slot);
+        assert!(slot.has_macro());
+        assert_eq!(variables(&slot), ["x"]);
+        assert_eq!(compound(slot).to_string(), "{$(x):1b}");
+
+        let plain = SNBTString(false, "plain".to_string());
+        assert_eq!(SNBTString::new("plain"), plain);
+        assert!(!plain.has_macro());
+        assert!(variables(&plain).is_empty());
+        assert_eq!(compound(plain).to_string(), "{plain:1b}");
+
+        // The old name-only form is no longer a macro slot.
+        let old = SNBTString(true, "x".to_string());
+        assert!(variables(&old).is_empty());
+        assert_eq!(compound(old).to_string(), "{x:1b}");
+    }
+}
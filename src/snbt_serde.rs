@@ -0,0 +1,166 @@
+//! A lossless serde representation of SNBT for the `serde` feature, used through
+//! `#[serde(with = "crate::snbt_serde")]` on AST fields.
+//!
+//! The [`Serialize`] implementation of [`SNBT`] itself produces plain JSON (for example for text
+//! components), which loses number types and macros.
+
+use crate::nbt_path::SNBTCompound;
+use crate::snbt::{SNBT, SNBTString};
+use ordered_float::NotNan;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize)]
+pub enum TaggedSNBT {
+    Byte(i8),
+    Short(i16),
+    Integer(i32),
+    Long(i64),
+    Float(NotNan<f32>),
+    Double(NotNan<f64>),
+    String(String),
+    /// A string containing `$(name)` slots.
+    MacroString(String),
+    List(Vec<TaggedSNBT>),
+    Compound(BTreeMap<String, TaggedSNBT>),
+    ByteArray(Vec<i8>),
+    IntegerArray(Vec<i32>),
+    LongArray(Vec<i64>),
+    Macro(String),
+}
+
+/// Types with a tagged representation.
+pub trait Tagged: Sized {
+    type Repr: Serialize + DeserializeOwned;
+
+    fn to_repr(&self) -> Self::Repr;
+
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+
+/// Keys are written as their text, and are macro keys if it contains slots.
+impl Tagged for SNBTString {
+    type Repr = String;
+
+    fn to_repr(&self) -> String {
+        self.1.clone()
+    }
+
+    fn from_repr(repr: String) -> Self {
+        SNBTString::new(repr)
+    }
+}
+
+impl Tagged for SNBT {
+    type Repr = TaggedSNBT;
+
+    fn to_repr(&self) -> TaggedSNBT {
+        match self {
+            SNBT::Byte(v) => TaggedSNBT::Byte(*v),
+            SNBT::Short(v) => TaggedSNBT::Short(*v),
+            SNBT::Integer(v) => TaggedSNBT::Integer(*v),
+            SNBT::Long(v) => TaggedSNBT::Long(*v),
+            SNBT::Float(v) => TaggedSNBT::Float(*v),
+            SNBT::Double(v) => TaggedSNBT::Double(*v),
+            SNBT::String(SNBTString(false, v)) => TaggedSNBT::String(v.clone()),
+            SNBT::String(SNBTString(true, v)) => TaggedSNBT::MacroString(v.clone()),
+            SNBT::List(values) => TaggedSNBT::List(values.iter().map(Tagged::to_repr).collect()),
+            SNBT::Compound(compound) => TaggedSNBT::Compound(compound.to_repr()),
+            SNBT::ByteArray(values) => TaggedSNBT::ByteArray(values.clone()),
+            SNBT::IntegerArray(values) => TaggedSNBT::IntegerArray(values.clone()),
+            SNBT::LongArray(values) => TaggedSNBT::LongArray(values.clone()),
+            SNBT::Macro(name) => TaggedSNBT::Macro(name.clone()),
+        }
+    }
+
+    fn from_repr(repr: TaggedSNBT) -> Self {
+        match repr {
+            TaggedSNBT::Byte(v) => SNBT::Byte(v),
+            TaggedSNBT::Short(v) => SNBT::Short(v),
+            TaggedSNBT::Integer(v) => SNBT::Integer(v),
+            TaggedSNBT::Long(v) => SNBT::Long(v),
+            TaggedSNBT::Float(v) => SNBT::Float(v),
+            TaggedSNBT::Double(v) => SNBT::Double(v),
+            TaggedSNBT::String(v) => SNBT::String(SNBTString(false, v)),
+            TaggedSNBT::MacroString(v) => SNBT::String(SNBTString(true, v)),
+            TaggedSNBT::List(values) => {
+                SNBT::List(values.into_iter().map(SNBT::from_repr).collect())
+            }
+            TaggedSNBT::Compound(compound) => SNBT::Compound(SNBTCompound::from_repr(compound)),
+            TaggedSNBT::ByteArray(values) => SNBT::ByteArray(values),
+            TaggedSNBT::IntegerArray(values) => SNBT::IntegerArray(values),
+            TaggedSNBT::LongArray(values) => SNBT::LongArray(values),
+            TaggedSNBT::Macro(name) => SNBT::Macro(name),
+        }
+    }
+}
+
+impl Tagged for SNBTCompound {
+    type Repr = BTreeMap<String, TaggedSNBT>;
+
+    fn to_repr(&self) -> Self::Repr {
+        self.iter()
+            .map(|(key, value)| (key.to_repr(), value.to_repr()))
+            .collect()
+    }
+
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr.into_iter()
+            .map(|(key, value)| (SNBTString::from_repr(key), SNBT::from_repr(value)))
+            .collect()
+    }
+}
+
+impl<T: Tagged> Tagged for Option<T> {
+    type Repr = Option<T::Repr>;
+
+    fn to_repr(&self) -> Self::Repr {
+        self.as_ref().map(Tagged::to_repr)
+    }
+
+    fn from_repr(repr: Self::Repr) -> Self {
+        repr.map(T::from_repr)
+    }
+}
+
+pub fn serialize<T: Tagged, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.to_repr().serialize(serializer)
+}
+
+pub fn deserialize<'de, T: Tagged, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    T::Repr::deserialize(deserializer).map(T::from_repr)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::Command;
+
+    fn round_trip(command: &str) {
+        let command: Command = command.parse().unwrap();
+        let json = serde_json::to_string(&command).unwrap();
+
+        assert_eq!(serde_json::from_str::<Command>(&json).unwrap(), command);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip("summon minecraft:pig ~ ~1 ~ {Health:1.5f,Age:3b,Tags:[\"a\"],$(key):$(value)}");
+        round_trip(
+            "execute as @e[nbt={Count:2s}] run data modify storage foo:bar a.b[0] set value [I;1,2]",
+        );
+        round_trip("tellraw @a {\"text\":\"$(name)\"}");
+        round_trip("give @s diamond_sword[custom_data={a:1L}] 2");
+    }
+
+    #[test]
+    fn test_snbt_tags() {
+        let command: Command = "data merge storage foo:bar {a:1b}".parse().unwrap();
+        let json = serde_json::to_value(&command).unwrap();
+
+        assert_eq!(
+            json.pointer("/Data/Merge/1/Compound/a"),
+            Some(&serde_json::json!({"Byte": 1}))
+        );
+    }
+}
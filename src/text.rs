@@ -0,0 +1,909 @@
+use crate::command::enums::team_color::TeamColor;
+use crate::coordinate::Coordinates;
+use crate::entity_selector::EntitySelector;
+use crate::nbt_path::{NbtPath, SNBTCompound};
+use crate::parser::parse_all;
+use crate::resource_location::ResourceLocation;
+use crate::snbt::{SNBT, SNBTString};
+use crate::version::GameVersion;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TextColor {
+    Named(TeamColor),
+    /// An RGB color, written as `#RRGGBB`.
+    Hex(u32),
+}
+
+impl Display for TextColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextColor::Named(color) => color.fmt(f),
+            TextColor::Hex(rgb) => write!(f, "#{:06X}", rgb & 0xFFFFFF),
+        }
+    }
+}
+
+impl From<TeamColor> for TextColor {
+    fn from(color: TeamColor) -> Self {
+        TextColor::Named(color)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum NbtSource {
+    Block(Coordinates),
+    Entity(EntitySelector),
+    Storage(ResourceLocation),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TextContent {
+    Text(String),
+    Translatable {
+        key: String,
+        fallback: Option<String>,
+        with: Vec<TextComponent>,
+    },
+    Score {
+        name: EntitySelector,
+        objective: String,
+    },
+    Selector {
+        selector: EntitySelector,
+        separator: Option<Box<TextComponent>>,
+    },
+    Keybind(String),
+    Nbt {
+        path: NbtPath,
+        source: NbtSource,
+        interpret: Option<bool>,
+        separator: Option<Box<TextComponent>>,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ClickEvent {
+    OpenUrl(String),
+    OpenFile(String),
+    RunCommand(String),
+    SuggestCommand(String),
+    ChangePage(i32),
+    CopyToClipboard(String),
+}
+
+impl ClickEvent {
+    fn action(&self) -> &'static str {
+        match self {
+            ClickEvent::OpenUrl(_) => "open_url",
+            ClickEvent::OpenFile(_) => "open_file",
+            ClickEvent::RunCommand(_) => "run_command",
+            ClickEvent::SuggestCommand(_) => "suggest_command",
+            ClickEvent::ChangePage(_) => "change_page",
+            ClickEvent::CopyToClipboard(_) => "copy_to_clipboard",
+        }
+    }
+
+    fn encode(&self, legacy: bool) -> SNBT {
+        let (field, value) = match self {
+            ClickEvent::OpenUrl(url) => ("url", SNBT::string(url)),
+            ClickEvent::OpenFile(path) => ("path", SNBT::string(path)),
+            ClickEvent::RunCommand(command) | ClickEvent::SuggestCommand(command) => {
+                ("command", SNBT::string(command))
+            }
+            ClickEvent::ChangePage(page) if legacy => ("value", SNBT::string(page)),
+            ClickEvent::ChangePage(page) => ("page", SNBT::Integer(*page)),
+            ClickEvent::CopyToClipboard(value) => ("value", SNBT::string(value)),
+        };
+
+        let mut compound = SNBTCompound::new();
+        compound.insert(key("action"), SNBT::string(self.action()));
+        compound.insert(key(if legacy { "value" } else { field }), value);
+
+        SNBT::Compound(compound)
+    }
+
+    fn decode(value: &SNBT) -> Option<ClickEvent> {
+        let mut fields = Fields::of(value)?;
+
+        let event = match fields.string("action")??.as_str() {
+            "open_url" => ClickEvent::OpenUrl(fields.string("url")??),
+            "open_file" => ClickEvent::OpenFile(fields.string("path")??),
+            "run_command" => ClickEvent::RunCommand(fields.string("command")??),
+            "suggest_command" => ClickEvent::SuggestCommand(fields.string("command")??),
+            "change_page" => ClickEvent::ChangePage(fields.integer("page")??),
+            "copy_to_clipboard" => ClickEvent::CopyToClipboard(fields.string("value")??),
+            _ => return None,
+        };
+
+        fields.finish(event)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum HoverEvent {
+    ShowText(Box<TextComponent>),
+    ShowItem {
+        id: ResourceLocation,
+        count: Option<i32>,
+        components: Option<SNBTCompound>,
+    },
+    ShowEntity {
+        id: ResourceLocation,
+        uuid: String,
+        name: Option<Box<TextComponent>>,
+    },
+}
+
+impl HoverEvent {
+    fn encode(&self, legacy: bool) -> SNBT {
+        let mut contents = SNBTCompound::new();
+
+        let (action, text) = match self {
+            HoverEvent::ShowText(text) => ("show_text", Some(text.encode(legacy))),
+            HoverEvent::ShowItem {
+                id,
+                count,
+                components,
+            } => {
+                contents.insert(key("id"), SNBT::string(id));
+
+                if let Some(count) = count {
+                    contents.insert(key("count"), SNBT::Integer(*count));
+                }
+
+                if let Some(components) = components {
+                    contents.insert(key("components"), SNBT::Compound(components.clone()));
+                }
+
+                ("show_item", None)
+            }
+            HoverEvent::ShowEntity { id, uuid, name } => {
+                let (type_key, uuid_key) = if legacy {
+                    ("type", "id")
+                } else {
+                    ("id", "uuid")
+                };
+                contents.insert(key(type_key), SNBT::string(id));
+                contents.insert(key(uuid_key), SNBT::string(uuid));
+
+                if let Some(name) = name {
+                    contents.insert(key("name"), name.encode(legacy));
+                }
+
+                ("show_entity", None)
+            }
+        };
+
+        let mut compound = SNBTCompound::new();
+        compound.insert(key("action"), SNBT::string(action));
+
+        match (text, legacy) {
+            (Some(text), true) => {
+                compound.insert(key("contents"), text);
+            }
+            (Some(text), false) => {
+                compound.insert(key("value"), text);
+            }
+            (None, true) => {
+                compound.insert(key("contents"), SNBT::Compound(contents));
+            }
+            (None, false) => compound.extend(contents),
+        }
+
+        SNBT::Compound(compound)
+    }
+
+    fn decode(value: &SNBT) -> Option<HoverEvent> {
+        let mut fields = Fields::of(value)?;
+
+        let event = match fields.string("action")??.as_str() {
+            "show_text" => HoverEvent::ShowText(Box::new(fields.component("value")??)),
+            "show_item" => HoverEvent::ShowItem {
+                id: fields.string("id")??.parse().ok()?,
+                count: fields.integer("count")?,
+                components: match fields.take("components") {
+                    Some(SNBT::Compound(components)) => Some(components),
+                    Some(_) => return None,
+                    None => None,
+                },
+            },
+            "show_entity" => HoverEvent::ShowEntity {
+                id: fields.string("id")??.parse().ok()?,
+                uuid: fields.string("uuid")??,
+                name: fields.component("name")?.map(Box::new),
+            },
+            _ => return None,
+        };
+
+        fields.finish(event)
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Style {
+    pub color: Option<TextColor>,
+    pub font: Option<ResourceLocation>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+    /// An ARGB color.
+    pub shadow_color: Option<i32>,
+    pub insertion: Option<String>,
+    pub click_event: Option<ClickEvent>,
+    pub hover_event: Option<HoverEvent>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TextComponent {
+    pub content: TextContent,
+    pub style: Style,
+    pub extra: Vec<TextComponent>,
+}
+
+#[inline]
+#[must_use]
+fn key(name: &str) -> SNBTString {
+    SNBTString(false, name.to_string())
+}
+
+fn boolean(value: bool) -> SNBT {
+    SNBT::Byte(value as i8)
+}
+
+/// The fields of a compound that weren't decoded yet. Each getter returns `None` if the field has
+/// the wrong type, and `Some(None)` if it's missing.
+struct Fields(SNBTCompound);
+
+impl Fields {
+    fn of(value: &SNBT) -> Option<Fields> {
+        match value {
+            SNBT::Compound(compound) => Some(Fields(compound.clone())),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self, name: &str) -> Option<SNBT> {
+        self.0.remove(&key(name))
+    }
+
+    fn string(&mut self, name: &str) -> Option<Option<String>> {
+        match self.take(name) {
+            Some(SNBT::String(SNBTString(_, value))) => Some(Some(value)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    }
+
+    fn integer(&mut self, name: &str) -> Option<Option<i32>> {
+        match self.take(name) {
+            Some(SNBT::Integer(value)) => Some(Some(value)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    }
+
+    fn boolean(&mut self, name: &str) -> Option<Option<bool>> {
+        match self.take(name) {
+            Some(SNBT::Byte(value @ (0 | 1))) => Some(Some(value == 1)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    }
+
+    fn component(&mut self, name: &str) -> Option<Option<TextComponent>> {
+        match self.take(name) {
+            Some(value) => TextComponent::from_snbt(&value).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn components(&mut self, name: &str) -> Option<Vec<TextComponent>> {
+        match self.take(name) {
+            Some(SNBT::List(values)) => values.iter().map(TextComponent::from_snbt).collect(),
+            Some(_) => None,
+            None => Some(Vec::new()),
+        }
+    }
+
+    /// Returns `value` if every field was decoded.
+    fn finish<T>(self, value: T) -> Option<T> {
+        self.0.is_empty().then_some(value)
+    }
+}
+
+fn parse_color(color: &str) -> Option<TextColor> {
+    match color.strip_prefix('#') {
+        Some(rgb) => u32::from_str_radix(rgb, 16).ok().map(TextColor::Hex),
+        None => parse_all(color).ok().map(TextColor::Named),
+    }
+}
+
+fn to_json(value: &SNBT) -> Value {
+    match value {
+        // The game reads JSON booleans as the numbers 0 and 1, so this works for any byte field.
+        SNBT::Byte(v @ (0 | 1)) => Value::Bool(*v == 1),
+        SNBT::Byte(v) => Value::from(*v),
+        SNBT::Short(v) => Value::from(*v),
+        SNBT::Integer(v) => Value::from(*v),
+        SNBT::Long(v) => Value::from(*v),
+        SNBT::Float(v) => Value::from(**v),
+        SNBT::Double(v) => Value::from(**v),
+        SNBT::String(SNBTString(_, v)) => Value::from(v.as_str()),
+        SNBT::List(values) => values.iter().map(to_json).collect(),
+        SNBT::Compound(compound) => compound
+            .iter()
+            .map(|(SNBTString(_, k), v)| (k.clone(), to_json(v)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        SNBT::ByteArray(values) => Value::from(values.clone()),
+        SNBT::IntegerArray(values) => Value::from(values.clone()),
+        SNBT::LongArray(values) => Value::from(values.clone()),
+        SNBT::Macro(name) => Value::from(format!("$({})", name)),
+    }
+}
+
+impl TextComponent {
+    /// The first version that stores text components as SNBT, with snake_case event keys.
+    pub const SNBT_VERSION: GameVersion = GameVersion::new(1, 21, 5);
+
+    #[inline]
+    #[must_use]
+    pub fn new(content: TextContent) -> Self {
+        TextComponent {
+            content,
+            style: Style::default(),
+            extra: Vec::new(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn text<T: ToString>(text: T) -> Self {
+        Self::new(TextContent::Text(text.to_string()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn translatable<T: ToString>(key: T) -> Self {
+        Self::translatable_with(key, Vec::new())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn translatable_with<T: ToString>(key: T, with: Vec<TextComponent>) -> Self {
+        Self::new(TextContent::Translatable {
+            key: key.to_string(),
+            fallback: None,
+            with,
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn score<T: ToString>(name: EntitySelector, objective: T) -> Self {
+        Self::new(TextContent::Score {
+            name,
+            objective: objective.to_string(),
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn selector(selector: EntitySelector) -> Self {
+        Self::new(TextContent::Selector {
+            selector,
+            separator: None,
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn keybind<T: ToString>(keybind: T) -> Self {
+        Self::new(TextContent::Keybind(keybind.to_string()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn nbt(path: NbtPath, source: NbtSource) -> Self {
+        Self::new(TextContent::Nbt {
+            path,
+            source,
+            interpret: None,
+            separator: None,
+        })
+    }
+
+    /// Sets the fallback of a translatable component.
+    #[must_use]
+    pub fn fallback<T: ToString>(mut self, value: T) -> Self {
+        if let TextContent::Translatable { fallback, .. } = &mut self.content {
+            *fallback = Some(value.to_string());
+        }
+
+        self
+    }
+
+    /// Sets the separator of a selector or NBT component.
+    #[must_use]
+    pub fn separator(mut self, value: TextComponent) -> Self {
+        if let TextContent::Selector { separator, .. } | TextContent::Nbt { separator, .. } =
+            &mut self.content
+        {
+            *separator = Some(Box::new(value));
+        }
+
+        self
+    }
+
+    /// Sets whether an NBT component parses its values as text components.
+    #[must_use]
+    pub fn interpret(mut self, value: bool) -> Self {
+        if let TextContent::Nbt { interpret, .. } = &mut self.content {
+            *interpret = Some(value);
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn color(mut self, color: impl Into<TextColor>) -> Self {
+        self.style.color = Some(color.into());
+        self
+    }
+
+    #[must_use]
+    pub fn font(mut self, font: ResourceLocation) -> Self {
+        self.style.font = Some(font);
+        self
+    }
+
+    #[must_use]
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.style.bold = Some(bold);
+        self
+    }
+
+    #[must_use]
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = Some(italic);
+        self
+    }
+
+    #[must_use]
+    pub fn underlined(mut self, underlined: bool) -> Self {
+        self.style.underlined = Some(underlined);
+        self
+    }
+
+    #[must_use]
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.style.strikethrough = Some(strikethrough);
+        self
+    }
+
+    #[must_use]
+    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+        self.style.obfuscated = Some(obfuscated);
+        self
+    }
+
+    #[must_use]
+    pub fn shadow_color(mut self, argb: i32) -> Self {
+        self.style.shadow_color = Some(argb);
+        self
+    }
+
+    #[must_use]
+    pub fn insertion<T: ToString>(mut self, insertion: T) -> Self {
+        self.style.insertion = Some(insertion.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn click_event(mut self, click_event: ClickEvent) -> Self {
+        self.style.click_event = Some(click_event);
+        self
+    }
+
+    #[must_use]
+    pub fn hover_event(mut self, hover_event: HoverEvent) -> Self {
+        self.style.hover_event = Some(hover_event);
+        self
+    }
+
+    /// Shows `text` when hovering over this component.
+    #[must_use]
+    pub fn hover_text(self, text: impl Into<TextComponent>) -> Self {
+        self.hover_event(HoverEvent::ShowText(Box::new(text.into())))
+    }
+
+    /// Appends a child to `extra`.
+    #[must_use]
+    pub fn append(mut self, child: impl Into<TextComponent>) -> Self {
+        self.extra.push(child.into());
+        self
+    }
+
+    /// Returns the SNBT form used by current versions.
+    #[must_use]
+    pub fn to_snbt(&self) -> SNBT {
+        self.encode(false)
+    }
+
+    /// Returns the JSON form used by current versions.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        to_json(&self.encode(false))
+    }
+
+    /// Returns the JSON form read by `target`, which uses `clickEvent` and `hoverEvent` before
+    /// [`TextComponent::SNBT_VERSION`].
+    #[must_use]
+    pub fn to_json_for(&self, target: GameVersion) -> Value {
+        to_json(&self.encode(target < Self::SNBT_VERSION))
+    }
+
+    /// Reads a component from the SNBT form used by current versions. Returns `None` if `value`
+    /// isn't a text component or has fields this model doesn't know.
+    #[must_use]
+    pub fn from_snbt(value: &SNBT) -> Option<TextComponent> {
+        let mut fields = match value {
+            SNBT::String(SNBTString(_, text)) => return Some(TextComponent::text(text)),
+            SNBT::List(values) => {
+                let (first, rest) = values.split_first()?;
+                let mut component = TextComponent::from_snbt(first)?;
+
+                for value in rest {
+                    component.extra.push(TextComponent::from_snbt(value)?);
+                }
+
+                return Some(component);
+            }
+            value => Fields::of(value)?,
+        };
+
+        fields.string("type")?;
+
+        let content = if let Some(text) = fields.string("text")? {
+            TextContent::Text(text)
+        } else if let Some(key) = fields.string("translate")? {
+            TextContent::Translatable {
+                key,
+                fallback: fields.string("fallback")?,
+                with: fields.components("with")?,
+            }
+        } else if let Some(score) = fields.take("score") {
+            let mut score = Fields::of(&score)?;
+            let content = TextContent::Score {
+                name: score.string("name")??.parse().ok()?,
+                objective: score.string("objective")??,
+            };
+
+            score.finish(content)?
+        } else if let Some(selector) = fields.string("selector")? {
+            TextContent::Selector {
+                selector: selector.parse().ok()?,
+                separator: fields.component("separator")?.map(Box::new),
+            }
+        } else if let Some(keybind) = fields.string("keybind")? {
+            TextContent::Keybind(keybind)
+        } else if let Some(path) = fields.string("nbt")? {
+            let source = if let Some(block) = fields.string("block")? {
+                NbtSource::Block(block.parse().ok()?)
+            } else if let Some(entity) = fields.string("entity")? {
+                NbtSource::Entity(entity.parse().ok()?)
+            } else {
+                NbtSource::Storage(fields.string("storage")??.parse().ok()?)
+            };
+
+            TextContent::Nbt {
+                path: path.parse().ok()?,
+                source,
+                interpret: fields.boolean("interpret")?,
+                separator: fields.component("separator")?.map(Box::new),
+            }
+        } else {
+            return None;
+        };
+
+        let style = Style {
+            color: match fields.string("color")? {
+                Some(color) => Some(parse_color(&color)?),
+                None => None,
+            },
+            font: fields
+                .string("font")?
+                .map(|font| font.parse())
+                .transpose()
+                .ok()?,
+            bold: fields.boolean("bold")?,
+            italic: fields.boolean("italic")?,
+            underlined: fields.boolean("underlined")?,
+            strikethrough: fields.boolean("strikethrough")?,
+            obfuscated: fields.boolean("obfuscated")?,
+            shadow_color: fields.integer("shadow_color")?,
+            insertion: fields.string("insertion")?,
+            click_event: match fields.take("click_event") {
+                Some(event) => Some(ClickEvent::decode(&event)?),
+                None => None,
+            },
+            hover_event: match fields.take("hover_event") {
+                Some(event) => Some(HoverEvent::decode(&event)?),
+                None => None,
+            },
+        };
+        let extra = fields.components("extra")?;
+
+        fields.finish(TextComponent {
+            content,
+            style,
+            extra,
+        })
+    }
+
+    fn encode(&self, legacy: bool) -> SNBT {
+        if let TextContent::Text(text) = &self.content
+            && self.style == Style::default()
+            && self.extra.is_empty()
+        {
+            return SNBT::string(text);
+        }
+
+        let mut compound = SNBTCompound::new();
+        let encode_all = |components: &[TextComponent]| {
+            SNBT::List(components.iter().map(|c| c.encode(legacy)).collect())
+        };
+
+        match &self.content {
+            TextContent::Text(text) => {
+                compound.insert(key("text"), SNBT::string(text));
+            }
+            TextContent::Translatable {
+                key: translation_key,
+                fallback,
+                with,
+            } => {
+                compound.insert(key("translate"), SNBT::string(translation_key));
+
+                if let Some(fallback) = fallback {
+                    compound.insert(key("fallback"), SNBT::string(fallback));
+                }
+
+                if !with.is_empty() {
+                    compound.insert(key("with"), encode_all(with));
+                }
+            }
+            TextContent::Score { name, objective } => {
+                let mut score = SNBTCompound::new();
+                score.insert(key("name"), SNBT::string(name));
+                score.insert(key("objective"), SNBT::string(objective));
+
+                compound.insert(key("score"), SNBT::Compound(score));
+            }
+            TextContent::Selector {
+                selector,
+                separator,
+            } => {
+                compound.insert(key("selector"), SNBT::string(selector));
+
+                if let Some(separator) = separator {
+                    compound.insert(key("separator"), separator.encode(legacy));
+                }
+            }
+            TextContent::Keybind(keybind) => {
+                compound.insert(key("keybind"), SNBT::string(keybind));
+            }
+            TextContent::Nbt {
+                path,
+                source,
+                interpret,
+                separator,
+            } => {
+                compound.insert(key("nbt"), SNBT::string(path));
+
+                let (source_key, value) = match source {
+                    NbtSource::Block(coordinates) => ("block", coordinates.to_string()),
+                    NbtSource::Entity(selector) => ("entity", selector.to_string()),
+                    NbtSource::Storage(storage) => ("storage", storage.to_string()),
+                };
+                compound.insert(key(source_key), SNBT::string(value));
+
+                if let Some(interpret) = interpret {
+                    compound.insert(key("interpret"), boolean(*interpret));
+                }
+
+                if let Some(separator) = separator {
+                    compound.insert(key("separator"), separator.encode(legacy));
+                }
+            }
+        }
+
+        let style = &self.style;
+
+        if let Some(color) = &style.color {
+            compound.insert(key("color"), SNBT::string(color));
+        }
+
+        if let Some(font) = &style.font {
+            compound.insert(key("font"), SNBT::string(font));
+        }
+
+        let flags = [
+            ("bold", style.bold),
+            ("italic", style.italic),
+            ("underlined", style.underlined),
+            ("strikethrough", style.strikethrough),
+            ("obfuscated", style.obfuscated),
+        ];
+
+        for (name, value) in flags {
+            if let Some(value) = value {
+                compound.insert(key(name), boolean(value));
+            }
+        }
+
+        if let Some(shadow_color) = style.shadow_color {
+            compound.insert(key("shadow_color"), SNBT::Integer(shadow_color));
+        }
+
+        if let Some(insertion) = &style.insertion {
+            compound.insert(key("insertion"), SNBT::string(insertion));
+        }
+
+        if let Some(click_event) = &style.click_event {
+            let name = if legacy { "clickEvent" } else { "click_event" };
+            compound.insert(key(name), click_event.encode(legacy));
+        }
+
+        if let Some(hover_event) = &style.hover_event {
+            let name = if legacy { "hoverEvent" } else { "hover_event" };
+            compound.insert(key(name), hover_event.encode(legacy));
+        }
+
+        if !self.extra.is_empty() {
+            compound.insert(key("extra"), encode_all(&self.extra));
+        }
+
+        SNBT::Compound(compound)
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        TextComponent::text(text)
+    }
+}
+
+impl From<String> for TextComponent {
+    fn from(text: String) -> Self {
+        TextComponent::new(TextContent::Text(text))
+    }
+}
+
+impl From<TextComponent> for SNBT {
+    fn from(component: TextComponent) -> Self {
+        component.to_snbt()
+    }
+}
+
+impl From<TextComponent> for Value {
+    fn from(component: TextComponent) -> Self {
+        component.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::command::enums::title_type::TitleType;
+    use crate::command::title::TitleCommand;
+
+    fn greeting() -> TextComponent {
+        TextComponent::text("Hello ")
+            .color(TeamColor::Gold)
+            .bold(true)
+            .click_event(ClickEvent::RunCommand("/trigger greet".to_string()))
+            .hover_text("Click me")
+            .append(TextComponent::selector(EntitySelector::s_no_options()))
+    }
+
+    #[test]
+    fn test_snbt() {
+        assert_eq!(TextComponent::text("hi").to_snbt(), SNBT::string("hi"));
+
+        let tellraw = Command::Tellraw(EntitySelector::a_no_options(), greeting().into());
+        assert_eq!(
+            tellraw.to_string(),
+            "tellraw @a {bold:1b, click_event:{action:\"run_command\", command:\"/trigger greet\"}, color:\"gold\", extra:[{selector:\"@s\"}], hover_event:{action:\"show_text\", value:\"Click me\"}, text:\"Hello \"}"
+        );
+        assert_eq!(tellraw.to_string().parse::<Command>().unwrap(), tellraw);
+
+        let title = TitleCommand::Title(
+            TitleType::Actionbar,
+            TextComponent::translatable_with(
+                "chat.type.text",
+                vec![TextComponent::score(
+                    EntitySelector::s_no_options(),
+                    "kills",
+                )],
+            )
+            .fallback("%s")
+            .into(),
+        );
+        assert_eq!(
+            title.to_string(),
+            "actionbar {fallback:\"%s\", translate:\"chat.type.text\", with:[{score:{name:\"@s\", objective:\"kills\"}}]}"
+        );
+    }
+
+    #[test]
+    fn test_from_snbt() {
+        assert_eq!(
+            TextComponent::from_snbt(&greeting().to_snbt()),
+            Some(greeting())
+        );
+        assert_eq!(
+            TextComponent::from_snbt(&"{text:\"x\",bold:true}".parse().unwrap()),
+            Some(TextComponent::text("x").bold(true))
+        );
+        assert_eq!(
+            TextComponent::from_snbt(&"[\"a\",{text:\"b\",color:\"#FF0000\"}]".parse().unwrap()),
+            Some(
+                TextComponent::text("a")
+                    .append(TextComponent::text("b").color(TextColor::Hex(0xFF0000)))
+            )
+        );
+        assert_eq!(
+            TextComponent::from_snbt(&"{text:\"x\",unknown:1b}".parse().unwrap()),
+            None
+        );
+        assert_eq!(TextComponent::from_snbt(&SNBT::Integer(1)), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let component = TextComponent::text("Page")
+            .color(TextColor::Hex(0xFF8000))
+            .click_event(ClickEvent::ChangePage(2))
+            .hover_event(HoverEvent::ShowEntity {
+                id: ResourceLocation::new_namespace_path("minecraft", "pig"),
+                uuid: "00000000-0000-0000-0000-000000000001".to_string(),
+                name: None,
+            });
+
+        assert_eq!(
+            component.to_json(),
+            serde_json::json!({
+                "text": "Page",
+                "color": "#FF8000",
+                "click_event": {"action": "change_page", "page": 2},
+                "hover_event": {
+                    "action": "show_entity",
+                    "id": "pig",
+                    "uuid": "00000000-0000-0000-0000-000000000001",
+                },
+            })
+        );
+        assert_eq!(
+            component.to_json_for(GameVersion::new(1, 20, 4)),
+            serde_json::json!({
+                "text": "Page",
+                "color": "#FF8000",
+                "clickEvent": {"action": "change_page", "value": "2"},
+                "hoverEvent": {
+                    "action": "show_entity",
+                    "contents": {"type": "pig", "id": "00000000-0000-0000-0000-000000000001"},
+                },
+            })
+        );
+        assert_eq!(
+            greeting().to_json_for(GameVersion::new(1, 20, 4))["hoverEvent"],
+            serde_json::json!({"action": "show_text", "contents": "Click me"})
+        );
+    }
+}
@@ -1,8 +1,11 @@
-use minecraft_command_types_derive::HasMacro;
+use crate::parser::{Parse, ParseError, ParseErrorKind, StringReader, parse_all};
+use minecraft_command_types_derive::{Fold, HasMacro, Visit, VisitMut};
 use ordered_float::NotNan;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeSuffix {
     Days,
     Seconds,
@@ -14,14 +17,33 @@ impl Display for TimeSuffix {
         match self {
             TimeSuffix::Days => f.write_str("d"),
             TimeSuffix::Seconds => f.write_str("s"),
-            TimeSuffix::Ticks => Ok(()),
+            TimeSuffix::Ticks => f.write_str("t"),
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, HasMacro, Visit, VisitMut, Fold)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Time(NotNan<f32>, Option<TimeSuffix>);
 
+impl Time {
+    #[inline]
+    #[must_use]
+    pub fn new(value: NotNan<f32>, suffix: Option<TimeSuffix>) -> Self {
+        Self(value, suffix)
+    }
+
+    #[inline]
+    pub fn value(&self) -> NotNan<f32> {
+        self.0
+    }
+
+    #[inline]
+    pub fn suffix(&self) -> Option<&TimeSuffix> {
+        self.1.as_ref()
+    }
+}
+
 impl Display for Time {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)?;
@@ -33,3 +55,60 @@ impl Display for Time {
         Ok(())
     }
 }
+
+impl Parse for Time {
+    fn parse(reader: &mut StringReader<'_>) -> Result<Self, ParseError> {
+        let start = reader.cursor();
+        let value = reader.read_float()?;
+
+        if value.into_inner() < 0.0 {
+            return Err(ParseError::new(
+                start,
+                ParseErrorKind::InvalidValue("time", value.to_string()),
+            ));
+        }
+
+        let suffix = match reader.peek() {
+            Some('d') => Some(TimeSuffix::Days),
+            Some('s') => Some(TimeSuffix::Seconds),
+            Some('t') => Some(TimeSuffix::Ticks),
+            _ => None,
+        };
+
+        if suffix.is_some() {
+            reader.skip();
+        }
+
+        Ok(Time(value, suffix))
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let time: Time = "20".parse().unwrap();
+        assert_eq!(time.value(), NotNan::new(20.0).unwrap());
+        assert_eq!(time.suffix(), None);
+
+        let time: Time = "5s".parse().unwrap();
+        assert_eq!(time.suffix(), Some(&TimeSuffix::Seconds));
+
+        let time: Time = "1d".parse().unwrap();
+        assert_eq!(time.to_string(), "1d");
+
+        assert!("5m".parse::<Time>().is_err());
+        assert!("s".parse::<Time>().is_err());
+        assert!("-1".parse::<Time>().is_err());
+    }
+}
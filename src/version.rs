@@ -0,0 +1,929 @@
+use crate::command::Command;
+use crate::command::bossbar::{BossbarCommand, BossbarSetType};
+use crate::command::data::{DataCommand, DataCommandModification};
+use crate::command::execute::{
+    ExecuteIfSubcommand, ExecuteSubcommand, Positioned, final_command, if_next, store_next,
+};
+use crate::command::item::ItemCommand;
+use crate::command::loot::{LootItemSource, LootSource};
+use crate::command::particle::ParticleCommand;
+use crate::command::r#return::ReturnCommand;
+use crate::command::scoreboard::{
+    ObjectivesScoreboardCommand, PlayersDisplayScoreboardCommand, PlayersScoreboardCommand,
+    ScoreboardCommand, ScoreboardModification, ScoreboardNumberFormat,
+};
+use crate::command::team::{TeamCommand, TeamOption};
+use crate::command::title::TitleCommand;
+use crate::datapack::pack::format::PackFormat;
+use crate::entity_selector::{EntitySelector, EntitySelectorVariable};
+use crate::has_macro::HasMacro;
+use crate::parser::{ParseError, ParseErrorKind};
+use crate::snbt::{SNBT, SNBTString};
+use crate::text::TextComponent;
+use crate::visit::{Visit, Visitor};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A release version of Java Edition, such as `1.21.5`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct GameVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl GameVersion {
+    /// The first version that uses item components instead of item NBT.
+    pub const ITEM_COMPONENTS: GameVersion = GameVersion::new(1, 20, 5);
+    /// The first version that uses singular folder names such as `function/`.
+    pub const SINGULAR_FOLDERS: GameVersion = GameVersion::new(1, 21, 0);
+
+    #[inline]
+    #[must_use]
+    pub const fn new(major: u16, minor: u16, patch: u16) -> GameVersion {
+        GameVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for GameVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+
+        if self.patch != 0 {
+            write!(f, ".{}", self.patch)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GameVersion {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ParseError::new(
+                0,
+                ParseErrorKind::InvalidValue("game version", s.to_string()),
+            )
+        };
+
+        let parts = s
+            .split('.')
+            .map(|part| part.parse::<u16>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match parts.as_slice() {
+            [major, minor] => Ok(GameVersion::new(*major, *minor, 0)),
+            [major, minor, patch] => Ok(GameVersion::new(*major, *minor, *patch)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Every release with the data pack format it uses, as `(first, last, format)`.
+const DATA_PACK_FORMATS: &[(GameVersion, GameVersion, PackFormat)] = &[
+    (
+        GameVersion::new(1, 13, 0),
+        GameVersion::new(1, 14, 4),
+        PackFormat::new(4, 0),
+    ),
+    (
+        GameVersion::new(1, 15, 0),
+        GameVersion::new(1, 16, 1),
+        PackFormat::new(5, 0),
+    ),
+    (
+        GameVersion::new(1, 16, 2),
+        GameVersion::new(1, 16, 5),
+        PackFormat::new(6, 0),
+    ),
+    (
+        GameVersion::new(1, 17, 0),
+        GameVersion::new(1, 17, 1),
+        PackFormat::new(7, 0),
+    ),
+    (
+        GameVersion::new(1, 18, 0),
+        GameVersion::new(1, 18, 1),
+        PackFormat::new(8, 0),
+    ),
+    (
+        GameVersion::new(1, 18, 2),
+        GameVersion::new(1, 18, 2),
+        PackFormat::new(9, 0),
+    ),
+    (
+        GameVersion::new(1, 19, 0),
+        GameVersion::new(1, 19, 3),
+        PackFormat::new(10, 0),
+    ),
+    (
+        GameVersion::new(1, 19, 4),
+        GameVersion::new(1, 19, 4),
+        PackFormat::new(12, 0),
+    ),
+    (
+        GameVersion::new(1, 20, 0),
+        GameVersion::new(1, 20, 1),
+        PackFormat::new(15, 0),
+    ),
+    (
+        GameVersion::new(1, 20, 2),
+        GameVersion::new(1, 20, 2),
+        PackFormat::new(18, 0),
+    ),
+    (
+        GameVersion::new(1, 20, 3),
+        GameVersion::new(1, 20, 4),
+        PackFormat::new(26, 0),
+    ),
+    (
+        GameVersion::new(1, 20, 5),
+        GameVersion::new(1, 20, 6),
+        PackFormat::new(41, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 0),
+        GameVersion::new(1, 21, 1),
+        PackFormat::new(48, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 2),
+        GameVersion::new(1, 21, 3),
+        PackFormat::new(57, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 4),
+        GameVersion::new(1, 21, 4),
+        PackFormat::new(61, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 5),
+        GameVersion::new(1, 21, 5),
+        PackFormat::new(71, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 6),
+        GameVersion::new(1, 21, 6),
+        PackFormat::new(80, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 7),
+        GameVersion::new(1, 21, 8),
+        PackFormat::new(81, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 9),
+        GameVersion::new(1, 21, 10),
+        PackFormat::new(88, 0),
+    ),
+    (
+        GameVersion::new(1, 21, 11),
+        GameVersion::new(1, 21, 11),
+        PackFormat::new(94, 1),
+    ),
+];
+
+fn data_pack_formats() -> impl Iterator<Item = (GameVersion, GameVersion, PackFormat)> {
+    DATA_PACK_FORMATS.iter().copied()
+}
+
+impl GameVersion {
+    /// Returns the data pack format of this release, if it's a known release.
+    pub fn data_pack_format(self) -> Option<PackFormat> {
+        data_pack_formats()
+            .find(|(first, last, _)| (*first..=*last).contains(&self))
+            .map(|(_, _, format)| format)
+    }
+
+    /// Returns the releases that use the data pack format `format`.
+    pub fn for_data_pack_format(format: PackFormat) -> Option<RangeInclusive<GameVersion>> {
+        data_pack_formats()
+            .find(|(_, _, known_format)| *known_format == format)
+            .map(|(first, last, _)| first..=last)
+    }
+
+    /// Returns the newest known release whose data pack format is at most `format`.
+    pub fn latest_for_data_pack_format(format: PackFormat) -> Option<GameVersion> {
+        data_pack_formats()
+            .take_while(|(_, _, known_format)| *known_format <= format)
+            .last()
+            .map(|(_, last, _)| last)
+    }
+
+    /// Returns the oldest known release whose data pack format is at least `format`.
+    pub fn earliest_for_data_pack_format(format: PackFormat) -> Option<GameVersion> {
+        data_pack_formats()
+            .find(|(_, _, known_format)| *known_format >= format)
+            .map(|(first, _, _)| first)
+    }
+
+    pub fn known_versions() -> impl Iterator<Item = RangeInclusive<GameVersion>> {
+        data_pack_formats().map(|(first, last, _)| first..=last)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VersionError {
+    Unavailable {
+        feature: &'static str,
+        since: GameVersion,
+        target: GameVersion,
+    },
+    CannotLower {
+        feature: String,
+        target: GameVersion,
+    },
+}
+
+impl Display for VersionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionError::Unavailable {
+                feature,
+                since,
+                target,
+            } => write!(
+                f,
+                "{} requires {} or newer, but the target is {}",
+                feature, since, target
+            ),
+            VersionError::CannotLower { feature, target } => {
+                write!(f, "{} can't be expressed in {}", feature, target)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
+
+fn require(
+    feature: &'static str,
+    since: (u16, u16, u16),
+    target: GameVersion,
+) -> Result<(), VersionError> {
+    let since = GameVersion::new(since.0, since.1, since.2);
+
+    if target < since {
+        return Err(VersionError::Unavailable {
+            feature,
+            since,
+            target,
+        });
+    }
+
+    Ok(())
+}
+
+/// Finds `@n` selectors.
+#[derive(Default)]
+struct NearestSelector(bool);
+
+impl Visitor for NearestSelector {
+    fn visit_entity_selector(&mut self, selector: &EntitySelector) {
+        self.0 |= matches!(
+            selector,
+            EntitySelector::Variable(EntitySelectorVariable::N, _)
+        );
+
+        selector.visit_children(self);
+    }
+}
+
+type LowerText = fn(&SNBT, GameVersion) -> Option<Value>;
+
+/// The text component argument of a command and how to lower it to JSON. It's always the last
+/// argument.
+fn text_argument(command: &Command) -> Option<(&SNBT, LowerText)> {
+    match command {
+        Command::Tellraw(_, text)
+        | Command::Title(_, TitleCommand::Title(_, text))
+        | Command::Bossbar(
+            BossbarCommand::Add(_, text) | BossbarCommand::Set(_, BossbarSetType::Name(text)),
+        )
+        | Command::Scoreboard(
+            ScoreboardCommand::Objectives(
+                ObjectivesScoreboardCommand::Add(_, _, Some(text))
+                | ObjectivesScoreboardCommand::Modify(
+                    _,
+                    ScoreboardModification::DisplayName(text)
+                    | ScoreboardModification::NumberFormat(Some(ScoreboardNumberFormat::Fixed(text))),
+                ),
+            )
+            | ScoreboardCommand::Players(PlayersScoreboardCommand::Display(
+                PlayersDisplayScoreboardCommand::Name(_, Some(text))
+                | PlayersDisplayScoreboardCommand::NumberFormat(
+                    _,
+                    Some(ScoreboardNumberFormat::Fixed(text)),
+                ),
+            )),
+        )
+        | Command::Team(
+            TeamCommand::Add(_, Some(text))
+            | TeamCommand::Modify(
+                _,
+                TeamOption::DisplayName(text) | TeamOption::Prefix(text) | TeamOption::Suffix(text),
+            ),
+        ) => Some((text, legacy_text)),
+        Command::Scoreboard(
+            ScoreboardCommand::Objectives(ObjectivesScoreboardCommand::Modify(
+                _,
+                ScoreboardModification::NumberFormat(Some(ScoreboardNumberFormat::Styled(style))),
+            ))
+            | ScoreboardCommand::Players(PlayersScoreboardCommand::Display(
+                PlayersDisplayScoreboardCommand::NumberFormat(
+                    _,
+                    Some(ScoreboardNumberFormat::Styled(style)),
+                ),
+            )),
+        ) => Some((style, legacy_style)),
+        _ => None,
+    }
+}
+
+fn legacy_text(text: &SNBT, target: GameVersion) -> Option<Value> {
+    TextComponent::from_snbt(text).map(|text| text.to_json_for(target))
+}
+
+/// Styles are lowered as a component with empty text, without the text.
+fn legacy_style(style: &SNBT, target: GameVersion) -> Option<Value> {
+    let SNBT::Compound(style) = style else {
+        return None;
+    };
+
+    let mut component = style.clone();
+    component.insert(SNBTString(false, "text".to_string()), SNBT::string(""));
+
+    let mut json = legacy_text(&SNBT::Compound(component), target)?;
+    json.as_object_mut()?.remove("text");
+
+    Some(json)
+}
+
+fn particle_number(value: &SNBT) -> Option<String> {
+    Some(match value {
+        SNBT::Byte(v) => v.to_string(),
+        SNBT::Short(v) => v.to_string(),
+        SNBT::Integer(v) => v.to_string(),
+        SNBT::Long(v) => v.to_string(),
+        SNBT::Float(v) => v.to_string(),
+        SNBT::Double(v) => v.to_string(),
+        _ => return None,
+    })
+}
+
+fn particle_color(value: &SNBT) -> Option<String> {
+    match value {
+        SNBT::List(channels) if channels.len() == 3 => Some(
+            channels
+                .iter()
+                .map(particle_number)
+                .collect::<Option<Vec<_>>>()?
+                .join(" "),
+        ),
+        SNBT::Integer(rgb) => Some(
+            [16, 8, 0]
+                .map(|shift| ((rgb >> shift) & 0xFF) as f32 / 255.0)
+                .map(|channel| channel.to_string())
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+fn particle_block(value: &SNBT) -> Option<String> {
+    let SNBT::Compound(state) = value else {
+        return match value {
+            SNBT::String(SNBTString(false, id)) => Some(id.clone()),
+            _ => None,
+        };
+    };
+
+    let mut result = match state.get(&SNBTString(false, "Name".to_string()))? {
+        SNBT::String(SNBTString(false, id)) => id.clone(),
+        _ => return None,
+    };
+
+    if let Some(properties) = state.get(&SNBTString(false, "Properties".to_string())) {
+        let SNBT::Compound(properties) = properties else {
+            return None;
+        };
+
+        let properties = properties
+            .iter()
+            .map(|(SNBTString(_, key), value)| match value {
+                SNBT::String(SNBTString(false, value)) => Some(format!("{}={}", key, value)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        result.push_str(&format!("[{}]", properties.join(",")));
+    }
+
+    Some(result)
+}
+
+/// Lowers inline particle options, like `dust{color:[1.0,0.0,0.0],scale:1.0}`, to the arguments
+/// read before 1.20.5, like `dust 1.0 0.0 0.0 1.0`.
+fn legacy_particle(name: &str) -> Option<String> {
+    let Some(start) = name.find('{') else {
+        return Some(name.to_string());
+    };
+
+    let (id, options) = name.split_at(start);
+    let Ok(SNBT::Compound(options)) = options.parse::<SNBT>() else {
+        return None;
+    };
+
+    let option = |key: &str| options.get(&SNBTString(false, key.to_string()));
+    let arguments = match id.strip_prefix("minecraft:").unwrap_or(id) {
+        "dust" => vec![
+            particle_color(option("color")?)?,
+            particle_number(option("scale")?)?,
+        ],
+        "dust_color_transition" => vec![
+            particle_color(option("from_color")?)?,
+            particle_number(option("scale")?)?,
+            particle_color(option("to_color")?)?,
+        ],
+        "block" | "block_marker" | "falling_dust" => {
+            vec![particle_block(option("block_state")?)?]
+        }
+        "item" => match option("item")? {
+            SNBT::String(SNBTString(false, id)) => vec![id.clone()],
+            _ => return None,
+        },
+        "sculk_charge" => vec![particle_number(option("roll")?)?],
+        "shriek" => vec![particle_number(option("delay")?)?],
+        _ => return None,
+    };
+
+    Some(format!("{} {}", id, arguments.join(" ")))
+}
+
+impl ExecuteSubcommand {
+    /// Checks this subcommand and everything chained after it, including the final command.
+    pub fn check_version(&self, target: GameVersion) -> Result<(), VersionError> {
+        match self {
+            ExecuteSubcommand::On(_, next) => {
+                require("execute on", (1, 19, 4), target)?;
+                next.check_version(target)
+            }
+            ExecuteSubcommand::Summon(_, next) => {
+                require("execute summon", (1, 19, 4), target)?;
+                next.check_version(target)
+            }
+            ExecuteSubcommand::Positioned(positioned, next) => {
+                if let Positioned::Over(_) = positioned {
+                    require("execute positioned over", (1, 19, 4), target)?;
+                }
+
+                next.check_version(target)
+            }
+            ExecuteSubcommand::Align(_, next)
+            | ExecuteSubcommand::Anchored(_, next)
+            | ExecuteSubcommand::As(_, next)
+            | ExecuteSubcommand::At(_, next)
+            | ExecuteSubcommand::Facing(_, next)
+            | ExecuteSubcommand::In(_, next)
+            | ExecuteSubcommand::Rotated(_, next) => next.check_version(target),
+            ExecuteSubcommand::If(_, if_subcommand) => {
+                match if_subcommand {
+                    ExecuteIfSubcommand::Dimension(..) => {
+                        require("execute if dimension", (1, 19, 4), target)?
+                    }
+                    ExecuteIfSubcommand::Loaded(..) => {
+                        require("execute if loaded", (1, 19, 4), target)?
+                    }
+                    ExecuteIfSubcommand::Function(..) => {
+                        require("execute if function", (1, 20, 3), target)?
+                    }
+                    ExecuteIfSubcommand::Items(..) => {
+                        require("execute if items", (1, 20, 5), target)?
+                    }
+                    ExecuteIfSubcommand::Stopwatch(..) => {
+                        require("execute if stopwatch", (1, 21, 11), target)?
+                    }
+                    _ => {}
+                }
+
+                match if_next(if_subcommand) {
+                    Some(next) => next.check_version(target),
+                    None => Ok(()),
+                }
+            }
+            ExecuteSubcommand::Store(_, store) => store_next(store).check_version(target),
+            ExecuteSubcommand::Run(command) => command.check_version(target),
+        }
+    }
+}
+
+impl Command {
+    /// Checks that this command, including any nested commands, exists in `target`.
+    pub fn check_version(&self, target: GameVersion) -> Result<(), VersionError> {
+        if self.has_macro() {
+            require("macro", (1, 20, 2), target)?;
+        }
+
+        let mut nearest = NearestSelector::default();
+        self.visit(&mut nearest);
+
+        if nearest.0 {
+            require("@n", (1, 21, 0), target)?;
+        }
+
+        match self {
+            Command::Attribute(..) => require("attribute", (1, 16, 0), target),
+            Command::Clone {
+                source_dimension,
+                target_dimension,
+                strict,
+                ..
+            } => {
+                if source_dimension.is_some() || target_dimension.is_some() {
+                    require("clone dimensions", (1, 20, 2), target)?;
+                }
+
+                if *strict {
+                    require("clone strict", (1, 20, 2), target)?;
+                }
+
+                Ok(())
+            }
+            Command::Damage(..) => require("damage", (1, 19, 4), target),
+            Command::Data(DataCommand::Modify(_, _, _, DataCommandModification::String(..))) => {
+                require("data modify string", (1, 19, 4), target)
+            }
+            Command::Dialog(..) => require("dialog", (1, 21, 6), target),
+            Command::Execute(subcommand) => subcommand.check_version(target),
+            Command::FetchProfile(..) => require("fetchprofile", (1, 21, 9), target),
+            Command::FillBiome(..) => require("fillbiome", (1, 19, 3), target),
+            Command::Function(_, Some(_)) => require("function arguments", (1, 20, 2), target),
+            Command::Item(..) => require("item", (1, 17, 0), target),
+            Command::JFR(..) => require("jfr", (1, 17, 0), target),
+            Command::Locate(..) => require("locate", (1, 19, 0), target),
+            Command::Place(..) => require("place", (1, 19, 0), target),
+            Command::Random(..) => require("random", (1, 20, 2), target),
+            Command::Return(return_command) => {
+                require("return", (1, 20, 0), target)?;
+
+                match return_command {
+                    ReturnCommand::Value(_) => Ok(()),
+                    ReturnCommand::Fail => require("return fail", (1, 20, 3), target),
+                    ReturnCommand::Run(command) => {
+                        require("return run", (1, 20, 2), target)?;
+                        command.check_version(target)
+                    }
+                }
+            }
+            Command::Ride(..) => require("ride", (1, 19, 4), target),
+            Command::Rotate(..) => require("rotate", (1, 21, 2), target),
+            Command::Scoreboard(ScoreboardCommand::Players(PlayersScoreboardCommand::Display(
+                _,
+            ))) => require("scoreboard players display", (1, 20, 3), target),
+            Command::Scoreboard(ScoreboardCommand::Objectives(
+                ObjectivesScoreboardCommand::Modify(
+                    _,
+                    ScoreboardModification::DisplayAutoUpdate(_)
+                    | ScoreboardModification::NumberFormat(_),
+                ),
+            )) => require(
+                "scoreboard objectives modify numberformat",
+                (1, 20, 3),
+                target,
+            ),
+            Command::Spectate(..) => require("spectate", (1, 15, 0), target),
+            Command::Stopwatch(..) => require("stopwatch", (1, 21, 11), target),
+            Command::Test(..) => require("test", (1, 21, 5), target),
+            Command::Tick(..) => require("tick", (1, 20, 3), target),
+            Command::Transfer(..) => require("transfer", (1, 20, 5), target),
+            Command::Version => require("version", (1, 21, 6), target),
+            Command::Waypoint(..) => require("waypoint", (1, 21, 6), target),
+            _ => Ok(()),
+        }
+    }
+
+    /// Renders this command for `target`, lowering syntax that changed where possible.
+    pub fn render(&self, target: GameVersion) -> Result<String, VersionError> {
+        self.check_version(target)?;
+
+        if target >= TextComponent::SNBT_VERSION {
+            return Ok(self.to_string());
+        }
+
+        if let Some((text, lower)) = text_argument(self) {
+            let lowered = lower(text, target).ok_or_else(|| VersionError::CannotLower {
+                feature: format!("text component '{}'", text),
+                target,
+            })?;
+
+            let command = self.to_string();
+            let prefix = &command[..command.len() - text.to_string().len()];
+
+            return Ok(format!("{}{}", prefix, lowered));
+        }
+
+        if target >= GameVersion::ITEM_COMPONENTS {
+            return Ok(match self {
+                Command::Execute(_) | Command::Return(_) => self.render_nested(target)?,
+                _ => self.to_string(),
+            });
+        }
+
+        Ok(match self {
+            Command::Clear(_, Some(predicate), _) if !predicate.tests.is_empty() => {
+                return Err(VersionError::CannotLower {
+                    feature: format!("item predicate '{}'", predicate),
+                    target,
+                });
+            }
+            Command::Particle(particle) => {
+                let mut particle = particle.clone();
+                let (ParticleCommand::Regular(name, ..) | ParticleCommand::Extra(name, ..)) =
+                    &mut particle;
+
+                *name = legacy_particle(name).ok_or_else(|| VersionError::CannotLower {
+                    feature: format!("particle '{}'", name),
+                    target,
+                })?;
+
+                format!("particle {}", particle)
+            }
+            Command::Give(selector, item, count) => {
+                let mut result = format!("give {} {}", selector, item.render(target)?);
+
+                if let Some(count) = count {
+                    result.push_str(&format!(" {}", count));
+                }
+
+                result
+            }
+            Command::Item(source, slot, ItemCommand::With(item, count)) => {
+                let mut result = format!(
+                    "item replace {} {} with {}",
+                    source,
+                    slot,
+                    item.render(target)?
+                );
+
+                if let Some(count) = count {
+                    result.push_str(&format!(" {}", count));
+                }
+
+                result
+            }
+            Command::Loot(
+                loot_target,
+                source @ (LootSource::Fish(.., Some(LootItemSource::Tool(item)))
+                | LootSource::Mine(_, Some(LootItemSource::Tool(item)))),
+            ) => {
+                // The tool is always the last argument.
+                let source = source.to_string();
+                let prefix = &source[..source.len() - item.to_string().len()];
+
+                format!("loot {} {}{}", loot_target, prefix, item.render(target)?)
+            }
+            Command::Execute(_) | Command::Return(_) => self.render_nested(target)?,
+            _ => self.to_string(),
+        })
+    }
+
+    /// Renders the command run by `execute` or `return run` for `target`.
+    fn render_nested(&self, target: GameVersion) -> Result<String, VersionError> {
+        Ok(match self {
+            Command::Execute(subcommand) => match final_command(subcommand) {
+                Some(command) => {
+                    // The command after `run` is always the end of the chain.
+                    let text = self.to_string();
+                    let prefix = &text[..text.len() - command.to_string().len()];
+
+                    format!("{}{}", prefix, command.render(target)?)
+                }
+                None => self.to_string(),
+            },
+            Command::Return(ReturnCommand::Run(command)) => {
+                format!("return run {}", command.render(target)?)
+            }
+            _ => self.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(command: &str, version: &str) -> Result<String, VersionError> {
+        command
+            .parse::<Command>()
+            .unwrap()
+            .render(version.parse().unwrap())
+    }
+
+    #[test]
+    fn test_game_version() {
+        let version: GameVersion = "1.21".parse().unwrap();
+        assert_eq!(version, GameVersion::SINGULAR_FOLDERS);
+        assert_eq!(version.to_string(), "1.21");
+        assert!("1.20.5".parse::<GameVersion>().unwrap() < version);
+        assert!("1".parse::<GameVersion>().is_err());
+    }
+
+    #[test]
+    fn test_data_pack_formats() {
+        assert_eq!(
+            GameVersion::new(1, 20, 4).data_pack_format(),
+            Some(PackFormat::new(26, 0))
+        );
+        assert_eq!(GameVersion::new(1, 12, 2).data_pack_format(), None);
+        assert_eq!(
+            GameVersion::for_data_pack_format(PackFormat::new(48, 0)),
+            Some(GameVersion::new(1, 21, 0)..=GameVersion::new(1, 21, 1))
+        );
+        assert_eq!(
+            GameVersion::latest_for_data_pack_format(PackFormat::new(50, 0)),
+            Some(GameVersion::new(1, 21, 1))
+        );
+    }
+
+    #[test]
+    fn test_unavailable() {
+        assert_eq!(
+            render("execute as @a run return run say hi", "1.20.1"),
+            Err(VersionError::Unavailable {
+                feature: "return run",
+                since: GameVersion::new(1, 20, 2),
+                target: GameVersion::new(1, 20, 1),
+            })
+        );
+        assert!(render("tick freeze", "1.20.2").is_err());
+        assert!(render("tick freeze", "1.20.3").is_ok());
+        assert!(render("execute on passengers run kill @s", "1.19.3").is_err());
+
+        let since = |command: &str, version: &str, previous: &str| {
+            assert!(
+                render(command, version).is_ok(),
+                "{} in {}",
+                command,
+                version
+            );
+            assert!(
+                render(command, previous).is_err(),
+                "{} in {}",
+                command,
+                previous
+            );
+        };
+
+        since(
+            "locate structure minecraft:village_plains",
+            "1.19",
+            "1.18.2",
+        );
+        since("locate biome minecraft:plains", "1.19", "1.18.2");
+        since("locate poi minecraft:home", "1.19", "1.18.2");
+        since("jfr start", "1.17", "1.16.5");
+        since("spectate @p @s", "1.15", "1.14.4");
+        since("clone ~ ~ ~ ~1 ~1 ~1 ~ ~5 ~ strict", "1.20.2", "1.20.1");
+        since(
+            "clone from minecraft:the_nether ~ ~ ~ ~1 ~1 ~1 to minecraft:overworld ~ ~5 ~",
+            "1.20.2",
+            "1.20.1",
+        );
+        since(
+            "data modify storage foo:bar a set string storage foo:bar b 1",
+            "1.19.4",
+            "1.19.3",
+        );
+        since(
+            "scoreboard players display name @s foo {\"text\":\"a\"}",
+            "1.20.3",
+            "1.20.2",
+        );
+        since(
+            "scoreboard players display numberformat @s foo blank",
+            "1.20.3",
+            "1.20.2",
+        );
+        since(
+            "scoreboard objectives modify foo numberformat blank",
+            "1.20.3",
+            "1.20.2",
+        );
+        since("kill @n[type=pig]", "1.21", "1.20.6");
+        since("execute as @a run tp @s @n", "1.21", "1.20.6");
+        assert!(render("clone ~ ~ ~ ~1 ~1 ~1 ~ ~5 ~", "1.13").is_ok());
+        assert!(render("scoreboard objectives modify foo rendertype hearts", "1.13").is_ok());
+    }
+
+    #[test]
+    fn test_lower_items() {
+        assert_eq!(
+            render(
+                "give @s diamond_sword[damage=5, unbreakable={}] 2",
+                "1.20.4"
+            ),
+            Ok("give @s diamond_sword{Damage:5, Unbreakable:1b} 2".to_string())
+        );
+        assert_eq!(
+            render(
+                "execute as @a run item replace entity @s weapon.mainhand with stick[custom_data={foo:1b}]",
+                "1.20.4"
+            ),
+            Ok(
+                "execute as @a run item replace entity @s weapon.mainhand with stick{foo:1b}"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            render("give @s stick[damage=5]", "1.21"),
+            Ok("give @s stick[damage=5]".to_string())
+        );
+        assert!(render("give @s stick[!damage]", "1.20.4").is_err());
+        assert_eq!(
+            render("give @s stick[custom_name=\"Sword\"]", "1.20.4"),
+            Ok(r#"give @s stick{display:{Name:"\"Sword\""}}"#.to_string())
+        );
+        assert_eq!(
+            render(
+                "give @s stick[custom_name={text:\"x\",bold:true}]",
+                "1.20.4"
+            ),
+            Ok(r#"give @s stick{display:{Name:"{\"bold\":true,\"text\":\"x\"}"}}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_lower_particles() {
+        assert_eq!(
+            render(
+                "particle dust{color:[1.0,0.0,0.0],scale:1.0} ~ ~ ~",
+                "1.20.4"
+            ),
+            Ok("particle dust 1 0 0 1 ~ ~ ~".to_string())
+        );
+        assert_eq!(
+            render(
+                "particle minecraft:block{block_state:{Name:\"stone_slab\",Properties:{type:\"top\"}}} ~ ~ ~ 0 0 0 1 5",
+                "1.20.4"
+            ),
+            Ok("particle minecraft:block stone_slab[type=top] ~ ~ ~ 0 0 0 1 5".to_string())
+        );
+        assert_eq!(
+            render("particle flame ~ ~ ~", "1.20.4"),
+            Ok("particle flame ~ ~ ~".to_string())
+        );
+        assert_eq!(
+            render(
+                "particle dust{color:[1.0,0.0,0.0],scale:1.0} ~ ~ ~",
+                "1.20.5"
+            ),
+            Ok("particle dust{color:[1.0,0.0,0.0],scale:1.0} ~ ~ ~".to_string())
+        );
+        assert!(render("particle entity_effect{color:[1.0,0.0,0.0,1.0]}", "1.20.4").is_err());
+    }
+
+    #[test]
+    fn test_lower_text() {
+        assert_eq!(
+            render("tellraw @a {text:\"x\",bold:true}", "1.20.4"),
+            Ok(r#"tellraw @a {"bold":true,"text":"x"}"#.to_string())
+        );
+        assert_eq!(
+            render("tellraw @a {text:\"x\",bold:true}", "1.21.5"),
+            Ok("tellraw @a {bold:1b, text:\"x\"}".to_string())
+        );
+        assert_eq!(
+            render(
+                "execute as @a run title @s actionbar {text:\"a\",click_event:{action:\"change_page\",page:2}}",
+                "1.21.4"
+            ),
+            Ok(r#"execute as @a run title @s actionbar {"clickEvent":{"action":"change_page","value":"2"},"text":"a"}"#
+                .to_string())
+        );
+        assert_eq!(
+            render("bossbar set foo:bar name \"Boss\"", "1.20.4"),
+            Ok(r#"bossbar set foo:bar name "Boss""#.to_string())
+        );
+        assert_eq!(
+            render(
+                "scoreboard objectives add foo dummy {text:\"Foo\",italic:false}",
+                "1.20.4"
+            ),
+            Ok(r#"scoreboard objectives add foo dummy {"italic":false,"text":"Foo"}"#.to_string())
+        );
+        assert_eq!(
+            render("scoreboard objectives modify foo numberformat styled {color:\"red\",bold:true}", "1.20.4"),
+            Ok(r#"scoreboard objectives modify foo numberformat styled {"bold":true,"color":"red"}"#
+                .to_string())
+        );
+        assert!(render("tellraw @a {text:\"x\",unknown:1b}", "1.20.4").is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Traversal of the command AST.
+//!
+//! [`Visit`], [`VisitMut`] and [`Fold`] are derived for every AST type and walk all of its
+//! fields. The nodes listed on [`Visitor`], [`VisitorMut`] and [`Folder`] are routed through the
+//! matching method, whose default implementation keeps walking into the node's children.
+
+use crate::command::{Command, PlayerScore};
+use crate::coordinate::Coordinates;
+use crate::entity_selector::EntitySelector;
+use crate::nbt_path::NbtPath;
+use crate::resource_location::ResourceLocation;
+use crate::snbt::SNBT;
+use nonempty::NonEmpty;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub trait Visitor {
+    fn visit_resource_location(&mut self, resource_location: &ResourceLocation) {
+        resource_location.visit_children(self);
+    }
+
+    fn visit_entity_selector(&mut self, selector: &EntitySelector) {
+        selector.visit_children(self);
+    }
+
+    fn visit_snbt(&mut self, snbt: &SNBT) {
+        snbt.visit_children(self);
+    }
+
+    fn visit_nbt_path(&mut self, path: &NbtPath) {
+        path.visit_children(self);
+    }
+
+    fn visit_coordinates(&mut self, coordinates: &Coordinates) {
+        coordinates.visit_children(self);
+    }
+
+    fn visit_player_score(&mut self, score: &PlayerScore) {
+        score.visit_children(self);
+    }
+
+    fn visit_command(&mut self, command: &Command) {
+        command.visit_children(self);
+    }
+}
+
+pub trait VisitorMut {
+    fn visit_resource_location_mut(&mut self, resource_location: &mut ResourceLocation) {
+        resource_location.visit_children_mut(self);
+    }
+
+    fn visit_entity_selector_mut(&mut self, selector: &mut EntitySelector) {
+        selector.visit_children_mut(self);
+    }
+
+    fn visit_snbt_mut(&mut self, snbt: &mut SNBT) {
+        snbt.visit_children_mut(self);
+    }
+
+    fn visit_nbt_path_mut(&mut self, path: &mut NbtPath) {
+        path.visit_children_mut(self);
+    }
+
+    fn visit_coordinates_mut(&mut self, coordinates: &mut Coordinates) {
+        coordinates.visit_children_mut(self);
+    }
+
+    fn visit_player_score_mut(&mut self, score: &mut PlayerScore) {
+        score.visit_children_mut(self);
+    }
+
+    fn visit_command_mut(&mut self, command: &mut Command) {
+        command.visit_children_mut(self);
+    }
+}
+
+pub trait Folder {
+    fn fold_resource_location(&mut self, resource_location: ResourceLocation) -> ResourceLocation {
+        resource_location.fold_children(self)
+    }
+
+    fn fold_entity_selector(&mut self, selector: EntitySelector) -> EntitySelector {
+        selector.fold_children(self)
+    }
+
+    fn fold_snbt(&mut self, snbt: SNBT) -> SNBT {
+        snbt.fold_children(self)
+    }
+
+    fn fold_nbt_path(&mut self, path: NbtPath) -> NbtPath {
+        path.fold_children(self)
+    }
+
+    fn fold_coordinates(&mut self, coordinates: Coordinates) -> Coordinates {
+        coordinates.fold_children(self)
+    }
+
+    fn fold_player_score(&mut self, score: PlayerScore) -> PlayerScore {
+        score.fold_children(self)
+    }
+
+    fn fold_command(&mut self, command: Command) -> Command {
+        command.fold_children(self)
+    }
+}
+
+pub trait Visit {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V);
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.visit_children(visitor);
+    }
+}
+
+pub trait VisitMut {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V);
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.visit_children_mut(visitor);
+    }
+}
+
+pub trait Fold: Sized {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self;
+
+    fn fold<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        self.fold_children(folder)
+    }
+}
+
+macro_rules! impl_visit_leaf {
+    ($($t:ty),*) => {
+        $(
+            impl Visit for $t {
+                #[inline(always)]
+                fn visit_children<V: Visitor + ?Sized>(&self, _: &mut V) {}
+            }
+
+            impl VisitMut for $t {
+                #[inline(always)]
+                fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, _: &mut V) {}
+            }
+
+            impl Fold for $t {
+                #[inline(always)]
+                fn fold_children<F: Folder + ?Sized>(self, _: &mut F) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_visit_leaf!(
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    String,
+    ordered_float::NotNan<f32>,
+    ordered_float::NotNan<f64>
+);
+
+impl<A: Visit, B: Visit> Visit for (A, B) {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.0.visit(visitor);
+        self.1.visit(visitor);
+    }
+}
+
+impl<A: VisitMut, B: VisitMut> VisitMut for (A, B) {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.0.visit_mut(visitor);
+        self.1.visit_mut(visitor);
+    }
+}
+
+impl<A: Fold, B: Fold> Fold for (A, B) {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        (self.0.fold(folder), self.1.fold(folder))
+    }
+}
+
+impl<T: Visit> Visit for Option<T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        if let Some(t) = self {
+            t.visit(visitor);
+        }
+    }
+}
+
+impl<T: VisitMut> VisitMut for Option<T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(t) = self {
+            t.visit_mut(visitor);
+        }
+    }
+}
+
+impl<T: Fold> Fold for Option<T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|t| t.fold(folder))
+    }
+}
+
+impl<T: Visit> Visit for Box<T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.as_ref().visit(visitor);
+    }
+}
+
+impl<T: VisitMut> VisitMut for Box<T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.as_mut().visit_mut(visitor);
+    }
+}
+
+impl<T: Fold> Fold for Box<T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold(folder))
+    }
+}
+
+impl<T: Visit> Visit for Vec<T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.iter().for_each(|t| t.visit(visitor));
+    }
+}
+
+impl<T: VisitMut> VisitMut for Vec<T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.iter_mut().for_each(|t| t.visit_mut(visitor));
+    }
+}
+
+impl<T: Fold> Fold for Vec<T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter().map(|t| t.fold(folder)).collect()
+    }
+}
+
+impl<T: Visit> Visit for NonEmpty<T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.iter().for_each(|t| t.visit(visitor));
+    }
+}
+
+impl<T: VisitMut> VisitMut for NonEmpty<T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.iter_mut().for_each(|t| t.visit_mut(visitor));
+    }
+}
+
+impl<T: Fold> Fold for NonEmpty<T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        NonEmpty {
+            head: self.head.fold(folder),
+            tail: self.tail.fold(folder),
+        }
+    }
+}
+
+impl<K: Visit, T: Visit> Visit for BTreeMap<K, T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.iter().for_each(|(k, t)| {
+            k.visit(visitor);
+            t.visit(visitor);
+        });
+    }
+}
+
+/// Keys of a map can't be changed in place, so the map is rebuilt.
+impl<K: VisitMut + Ord, T: VisitMut> VisitMut for BTreeMap<K, T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        *self = std::mem::take(self)
+            .into_iter()
+            .map(|(mut k, mut t)| {
+                k.visit_mut(visitor);
+                t.visit_mut(visitor);
+                (k, t)
+            })
+            .collect();
+    }
+}
+
+impl<K: Fold + Ord, T: Fold> Fold for BTreeMap<K, T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter()
+            .map(|(k, t)| (k.fold(folder), t.fold(folder)))
+            .collect()
+    }
+}
+
+impl<T: Visit> Visit for BTreeSet<T> {
+    fn visit_children<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.iter().for_each(|t| t.visit(visitor));
+    }
+}
+
+/// Elements of a set can't be changed in place, so the set is rebuilt.
+impl<T: VisitMut + Ord> VisitMut for BTreeSet<T> {
+    fn visit_children_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        *self = std::mem::take(self)
+            .into_iter()
+            .map(|mut t| {
+                t.visit_mut(visitor);
+                t
+            })
+            .collect();
+    }
+}
+
+impl<T: Fold + Ord> Fold for BTreeSet<T> {
+    fn fold_children<F: Folder + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter().map(|t| t.fold(folder)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity_selector::EntitySelectorVariable;
+
+    #[derive(Default)]
+    struct Collector {
+        resource_locations: Vec<String>,
+        commands: usize,
+    }
+
+    impl Visitor for Collector {
+        fn visit_resource_location(&mut self, resource_location: &ResourceLocation) {
+            self.resource_locations.push(resource_location.to_string());
+        }
+
+        fn visit_command(&mut self, command: &Command) {
+            self.commands += 1;
+            command.visit_children(self);
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let command: Command =
+            "execute as @e[type=pig] if function foo:check run function bar:baz {a:1}"
+                .parse()
+                .unwrap();
+
+        let mut collector = Collector::default();
+        command.visit(&mut collector);
+
+        assert_eq!(
+            collector.resource_locations,
+            vec!["pig", "foo:check", "bar:baz"]
+        );
+        assert_eq!(collector.commands, 2);
+
+        let command: Command = "kill @a[advancements={minecraft:story/root=true}]"
+            .parse()
+            .unwrap();
+
+        let mut collector = Collector::default();
+        command.visit(&mut collector);
+
+        assert_eq!(collector.resource_locations, vec!["story/root"]);
+    }
+
+    #[test]
+    fn test_visit_mut() {
+        struct RenameObjective;
+
+        impl VisitorMut for RenameObjective {
+            fn visit_player_score_mut(&mut self, score: &mut PlayerScore) {
+                if score.objective == "old" {
+                    score.objective = "new".to_string();
+                }
+            }
+        }
+
+        let mut command: Command =
+            "execute if entity @s run scoreboard players operation @s old += @s other"
+                .parse()
+                .unwrap();
+        command.visit_mut(&mut RenameObjective);
+
+        assert_eq!(
+            command.to_string(),
+            "execute if entity @s run scoreboard players operation @s new += @s other"
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        struct ReplaceSelf;
+
+        impl Folder for ReplaceSelf {
+            fn fold_entity_selector(&mut self, selector: EntitySelector) -> EntitySelector {
+                match selector {
+                    EntitySelector::Variable(EntitySelectorVariable::S, options) => {
+                        EntitySelector::Variable(EntitySelectorVariable::P, options)
+                    }
+                    selector => selector.fold_children(self),
+                }
+            }
+        }
+
+        let command: Command = "execute as @s[tag=a] run kill @s".parse().unwrap();
+
+        assert_eq!(
+            command.fold(&mut ReplaceSelf).to_string(),
+            "execute as @p[tag=a] run kill @p"
+        );
+    }
+}